@@ -0,0 +1,383 @@
+pub mod gesture_bindings {
+    use crate::gesture::gestures::{EventSink, GESTURE_NAMES, GestureEvent};
+    use anyhow::{Context, Result, anyhow};
+    use enigo::{Button, Direction, Enigo, Key, Keyboard, Mouse};
+    use serde::Deserialize;
+    use std::collections::HashMap;
+    use std::path::Path;
+    use std::process::Command;
+    use std::time::{Duration, Instant};
+
+    // Bindings config (TOML), e.g.
+    //
+    //   [[binding]]
+    //   gesture = "pinch_start"
+    //   action = { type = "click", button = "left" }
+    //
+    //   [[binding]]
+    //   gesture = "swipe_left"
+    //   action = { type = "key", key = "left" }
+    //
+    //   [[binding]]
+    //   gesture = "swipe_up"
+    //   action = { type = "shell", command = "playerctl play-pause" }
+    //
+    //   [[binding]]
+    //   gesture = "swipe_right"
+    //   action = { type = "macro", steps = ["alt+tab", "wait 200", "enter"] }
+    //
+    //   [[binding]]
+    //   gesture = "closed_fist"
+    //   action = { type = "cancel_macro" }
+    //
+    // Gesture names are listed in GESTURE_NAMES. Keys are single characters or
+    // one of the names accepted by parse_key. Buttons are left, right or middle.
+    // Macro steps are described at parse_step.
+    #[derive(Debug, Deserialize)]
+    struct RawConfig {
+        #[serde(default)]
+        binding: Vec<RawBinding>,
+    }
+
+    #[derive(Debug, Deserialize)]
+    struct RawBinding {
+        gesture: String,
+        action: RawAction,
+    }
+
+    #[derive(Debug, Deserialize)]
+    struct RawAction {
+        #[serde(rename = "type")]
+        kind: String,
+        key: Option<String>,
+        button: Option<String>,
+        command: Option<String>,
+        steps: Option<Vec<String>>,
+    }
+
+    // Longest macro accepted, and longest single wait in one, so a typo can't leave a
+    // macro typing for minutes
+    pub const MAX_MACRO_STEPS: usize = 32;
+    pub const MAX_MACRO_WAIT: Duration = Duration::from_secs(5);
+
+    #[derive(Debug, Clone, PartialEq)]
+    pub enum MacroStep {
+        // Hold all keys but the last, tap the last, then release in reverse (e.g. alt+tab)
+        Keys(Vec<Key>),
+        Click(Button),
+        Wait(Duration),
+    }
+
+    // Scripted sequence of input actions bound to one gesture
+    #[derive(Debug, Clone, PartialEq)]
+    pub struct Macro {
+        steps: Vec<MacroStep>,
+    }
+
+    impl Macro {
+        pub fn new(steps: Vec<MacroStep>) -> Result<Self> {
+            if steps.is_empty() {
+                return Err(anyhow!("Macro has no steps"));
+            }
+            if steps.len() > MAX_MACRO_STEPS {
+                return Err(anyhow!(
+                    "Macro has {} steps (at most {} allowed)",
+                    steps.len(),
+                    MAX_MACRO_STEPS
+                ));
+            }
+            Ok(Self { steps })
+        }
+
+        pub fn steps(&self) -> &[MacroStep] {
+            &self.steps
+        }
+    }
+
+    #[derive(Debug, Clone, PartialEq)]
+    pub enum Action {
+        KeyPress(Key),
+        MouseClick(Button),
+        Shell(String),
+        Macro(Macro),
+        // Stop the macro being played, if any
+        CancelMacro,
+    }
+
+    // Gesture name -> actions to run, in config order
+    #[derive(Debug, Default)]
+    pub struct DispatchTable {
+        bindings: HashMap<&'static str, Vec<Action>>,
+    }
+
+    fn parse_key(name: &str) -> Result<Key> {
+        let mut chars = name.chars();
+        if let (Some(c), None) = (chars.next(), chars.next()) {
+            return Ok(Key::Unicode(c));
+        }
+
+        let key = match name.to_lowercase().as_str() {
+            "alt" => Key::Alt,
+            "backspace" => Key::Backspace,
+            "control" | "ctrl" => Key::Control,
+            "delete" => Key::Delete,
+            "down" => Key::DownArrow,
+            "end" => Key::End,
+            "enter" | "return" => Key::Return,
+            "escape" | "esc" => Key::Escape,
+            "home" => Key::Home,
+            "left" => Key::LeftArrow,
+            "meta" | "super" | "command" => Key::Meta,
+            "pagedown" => Key::PageDown,
+            "pageup" => Key::PageUp,
+            "right" => Key::RightArrow,
+            "shift" => Key::Shift,
+            "space" => Key::Space,
+            "tab" => Key::Tab,
+            "up" => Key::UpArrow,
+            "volumeup" => Key::VolumeUp,
+            "volumedown" => Key::VolumeDown,
+            "mute" => Key::VolumeMute,
+            "playpause" => Key::MediaPlayPause,
+            "nexttrack" => Key::MediaNextTrack,
+            "prevtrack" => Key::MediaPrevTrack,
+            "f1" => Key::F1,
+            "f2" => Key::F2,
+            "f3" => Key::F3,
+            "f4" => Key::F4,
+            "f5" => Key::F5,
+            "f6" => Key::F6,
+            "f7" => Key::F7,
+            "f8" => Key::F8,
+            "f9" => Key::F9,
+            "f10" => Key::F10,
+            "f11" => Key::F11,
+            "f12" => Key::F12,
+            _ => return Err(anyhow!("Unknown key '{}'", name)),
+        };
+        Ok(key)
+    }
+
+    pub fn parse_button(name: &str) -> Result<Button> {
+        match name.to_lowercase().as_str() {
+            "left" => Ok(Button::Left),
+            "right" => Ok(Button::Right),
+            "middle" => Ok(Button::Middle),
+            _ => Err(anyhow!(
+                "Unknown mouse button '{}' (expected left, right or middle)",
+                name
+            )),
+        }
+    }
+
+    // Macro steps:
+    //   "enter", "alt+tab", "ctrl+shift+t"  tap a key or a key combination
+    //   "click left"                         click a mouse button
+    //   "wait 200"                           pause for this many ms (at most MAX_MACRO_WAIT)
+    fn parse_step(step: &str) -> Result<MacroStep> {
+        let step = step.trim();
+        if let Some(millis) = step.strip_prefix("wait ") {
+            let millis: u64 = millis
+                .trim()
+                .parse()
+                .with_context(|| format!("Invalid wait '{}' (expected ms)", step))?;
+            let wait = Duration::from_millis(millis);
+            if wait > MAX_MACRO_WAIT {
+                return Err(anyhow!(
+                    "Wait of {} ms is longer than the {} ms allowed",
+                    millis,
+                    MAX_MACRO_WAIT.as_millis()
+                ));
+            }
+            return Ok(MacroStep::Wait(wait));
+        }
+        if let Some(button) = step.strip_prefix("click ") {
+            return Ok(MacroStep::Click(parse_button(button.trim())?));
+        }
+        // A lone "+" is the plus key, not an empty combination
+        if step.len() == 1 {
+            return Ok(MacroStep::Keys(vec![parse_key(step)?]));
+        }
+        let keys = step
+            .split('+')
+            .map(|key| parse_key(key.trim()))
+            .collect::<Result<Vec<_>>>()?;
+        Ok(MacroStep::Keys(keys))
+    }
+
+    fn parse_macro(steps: &[String]) -> Result<Macro> {
+        let steps = steps
+            .iter()
+            .enumerate()
+            .map(|(i, step)| parse_step(step).with_context(|| format!("Macro step {}", i + 1)))
+            .collect::<Result<Vec<_>>>()?;
+        Macro::new(steps)
+    }
+
+    fn parse_action(raw: RawAction) -> Result<Action> {
+        let missing = |field: &str| anyhow!("Action '{}' requires a '{}' field", raw.kind, field);
+        match raw.kind.as_str() {
+            "key" => Ok(Action::KeyPress(parse_key(
+                raw.key.as_deref().ok_or_else(|| missing("key"))?,
+            )?)),
+            "click" => Ok(Action::MouseClick(parse_button(
+                raw.button.as_deref().ok_or_else(|| missing("button"))?,
+            )?)),
+            "shell" => Ok(Action::Shell(
+                raw.command.clone().ok_or_else(|| missing("command"))?,
+            )),
+            "macro" => Ok(Action::Macro(parse_macro(
+                raw.steps.as_deref().ok_or_else(|| missing("steps"))?,
+            )?)),
+            "cancel_macro" => Ok(Action::CancelMacro),
+            other => Err(anyhow!(
+                "Unknown action type '{}' (expected key, click, shell, macro or cancel_macro)",
+                other
+            )),
+        }
+    }
+
+    impl DispatchTable {
+        pub fn parse(text: &str) -> Result<Self> {
+            let raw: RawConfig = toml::from_str(text)?;
+
+            let mut table = DispatchTable::default();
+            for (i, binding) in raw.binding.into_iter().enumerate() {
+                let gesture = GESTURE_NAMES
+                    .iter()
+                    .find(|name| **name == binding.gesture)
+                    .ok_or_else(|| {
+                        anyhow!(
+                            "Binding {}: unknown gesture '{}' (expected one of: {})",
+                            i + 1,
+                            binding.gesture,
+                            GESTURE_NAMES.join(", ")
+                        )
+                    })?;
+                let action = parse_action(binding.action)
+                    .with_context(|| format!("Binding {} ({})", i + 1, gesture))?;
+
+                table.bindings.entry(gesture).or_default().push(action);
+            }
+
+            // Return
+            Ok(table)
+        }
+
+        pub fn load<P: AsRef<Path>>(path: P) -> Result<Self> {
+            let path = path.as_ref();
+            let text = std::fs::read_to_string(path)
+                .with_context(|| format!("Failed to read bindings from {}", path.display()))?;
+            Self::parse(&text).with_context(|| format!("Invalid bindings in {}", path.display()))
+        }
+
+        pub fn actions(&self, event: &GestureEvent) -> &[Action] {
+            self.bindings
+                .get(event.name())
+                .map(Vec::as_slice)
+                .unwrap_or(&[])
+        }
+    }
+
+    // Macro being played: the remaining steps run once `resume_at` has passed
+    struct Playback {
+        steps: Vec<MacroStep>,
+        next: usize,
+        resume_at: Instant,
+    }
+
+    // Event sink that runs the bound actions through the input controller
+    pub struct ActionSink {
+        enigo: Enigo,
+        table: DispatchTable,
+        playing: Option<Playback>,
+    }
+
+    impl ActionSink {
+        pub fn new(enigo: Enigo, table: DispatchTable) -> Self {
+            Self {
+                enigo,
+                table,
+                playing: None,
+            }
+        }
+
+        pub fn is_playing(&self) -> bool {
+            self.playing.is_some()
+        }
+
+        // Stop the macro being played. Keys are only held within a step, so none stay down.
+        pub fn cancel_macro(&mut self) {
+            self.playing = None;
+        }
+
+        // Run the playing macro's steps that are due. Waits don't block: call this every
+        // frame and the macro carries on once its wait is over.
+        pub fn tick(&mut self, now: Instant) -> Result<()> {
+            let Some(playback) = self.playing.as_mut() else {
+                return Ok(());
+            };
+            if now < playback.resume_at {
+                return Ok(());
+            }
+            while let Some(step) = playback.steps.get(playback.next) {
+                playback.next += 1;
+                match step {
+                    MacroStep::Keys(keys) => {
+                        if let Some((last, held)) = keys.split_last() {
+                            for key in held {
+                                self.enigo.key(*key, Direction::Press)?;
+                            }
+                            self.enigo.key(*last, Direction::Click)?;
+                            for key in held.iter().rev() {
+                                self.enigo.key(*key, Direction::Release)?;
+                            }
+                        }
+                    }
+                    MacroStep::Click(button) => self.enigo.button(*button, Direction::Click)?,
+                    MacroStep::Wait(wait) => {
+                        playback.resume_at = now + *wait;
+                        return Ok(());
+                    }
+                }
+            }
+            self.playing = None;
+            Ok(())
+        }
+    }
+
+    impl EventSink for ActionSink {
+        fn handle(&mut self, event: &GestureEvent) -> Result<()> {
+            for action in self.table.actions(event) {
+                match action {
+                    Action::KeyPress(key) => self.enigo.key(*key, Direction::Click)?,
+                    // One macro at a time: triggering it again while it plays is ignored
+                    // rather than queued, so a flickering gesture can't pile up replays
+                    Action::Macro(bound) if self.playing.is_none() => {
+                        self.playing = Some(Playback {
+                            steps: bound.steps().to_vec(),
+                            next: 0,
+                            resume_at: Instant::now(),
+                        });
+                    }
+                    Action::Macro(_) => {}
+                    Action::CancelMacro => self.playing = None,
+                    Action::MouseClick(button) => self.enigo.button(*button, Direction::Click)?,
+                    Action::Shell(command) => {
+                        // Fire and forget, so a slow command doesn't stall the frame loop
+                        let spawned = if cfg!(windows) {
+                            Command::new("cmd").args(["/C", command]).spawn()
+                        } else {
+                            Command::new("sh").args(["-c", command]).spawn()
+                        };
+                        if let Err(e) = spawned {
+                            eprintln!("Failed to run '{}': {}", command, e);
+                        }
+                    }
+                }
+            }
+            // A macro just started runs its steps up to the first wait right away
+            self.tick(Instant::now())
+        }
+    }
+}
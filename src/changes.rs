@@ -0,0 +1,71 @@
+pub mod detection_changes {
+    use crate::gesture::gestures::GestureEvent;
+    use crate::tracker::hand_tracker::TrackedHand;
+    use serde::Serialize;
+    use std::collections::HashMap;
+
+    // A change since the previous frame, for consumers that don't need the full detection
+    // set every frame (network clients, event logs). Positions are box centers, normalized.
+    #[derive(Debug, Clone, PartialEq, Serialize)]
+    #[serde(tag = "type", rename_all = "snake_case")]
+    pub enum Change {
+        Appeared { id: u32, x: f32, y: f32 },
+        Moved { id: u32, x: f32, y: f32 },
+        Disappeared { id: u32 },
+        Gesture { id: u32, gesture: &'static str },
+    }
+
+    // Turns frame-by-frame tracked hands into compact Change events. A hand is reported as
+    // moved once it is more than `move_threshold` (normalized) away from the position last
+    // reported for it, so slow drift still gets through without a message every frame.
+    pub struct ChangeDiffer {
+        move_threshold: f32,
+        // Last reported position of each hand
+        reported: HashMap<u32, (f32, f32)>,
+    }
+
+    impl ChangeDiffer {
+        pub fn new(move_threshold: f32) -> Self {
+            Self {
+                move_threshold,
+                reported: HashMap::new(),
+            }
+        }
+
+        // Changes between the previous call and this frame's hands and gesture events
+        pub fn update(&mut self, tracked: &[TrackedHand], events: &[GestureEvent]) -> Vec<Change> {
+            let mut changes = Vec::new();
+
+            self.reported.retain(|id, _| {
+                let alive = tracked.iter().any(|hand| hand.id == *id);
+                if !alive {
+                    changes.push(Change::Disappeared { id: *id });
+                }
+                alive
+            });
+
+            for hand in tracked {
+                let (x, y) = hand.details.bbox.center();
+                match self.reported.get_mut(&hand.id) {
+                    None => {
+                        self.reported.insert(hand.id, (x, y));
+                        changes.push(Change::Appeared { id: hand.id, x, y });
+                    }
+                    Some(last) => {
+                        let distance = ((x - last.0).powi(2) + (y - last.1).powi(2)).sqrt();
+                        if distance > self.move_threshold {
+                            *last = (x, y);
+                            changes.push(Change::Moved { id: hand.id, x, y });
+                        }
+                    }
+                }
+            }
+
+            changes.extend(events.iter().map(|event| Change::Gesture {
+                id: event.id(),
+                gesture: event.name(),
+            }));
+            changes
+        }
+    }
+}
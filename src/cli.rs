@@ -0,0 +1,817 @@
+pub mod args {
+    use crate::bindings::gesture_bindings::parse_button;
+    use crate::detector::hand_detector::{
+        ActivationMode, Box, NmsMode, OutputOrder, resize_filter_from_name,
+    };
+    use crate::gesture::gestures::{Finger, GESTURE_NAMES};
+    use crate::heatmap::occupancy;
+    use crate::pipeline::frame_pipeline::{DropPolicy, QueueConfig};
+    use crate::sensor::webcam::ControlKind;
+    use crate::smoothing::filters::LandmarkSmoothing;
+    use crate::snapshot::frame_snapshot::ImageFormat;
+    use crate::tracker::hand_tracker::PrimarySelection;
+    use crate::viz::overlay::PixelOrder;
+    use anyhow::{Result, anyhow};
+    use enigo::Button;
+    use image::imageops::FilterType;
+    use std::path::PathBuf;
+
+    #[derive(Debug, Default)]
+    pub struct Options {
+        // Print the formats supported by the camera at this index and exit
+        pub list_formats: Option<u32>,
+        // Print the inputs/outputs of an ONNX model and exit
+        pub describe_model: Option<PathBuf>,
+        // Check camera, detector and input controller, print a summary and exit
+        pub selftest: bool,
+        // Time detection on this image with each resize filter and exit
+        pub compare_filters: Option<PathBuf>,
+        // Time batch detection on this directory of frames, with and without the
+        // preprocessing pool, and exit
+        pub bench_batch: Option<PathBuf>,
+        // Time detection on this directory of frames with full scans and with ROI tracking,
+        // and exit
+        pub compare_roi: Option<PathBuf>,
+        // Read raw RGB frames from this pipe/file ("-" for stdin) instead of the camera
+        pub pipe: Option<PathBuf>,
+        // Frame size of the pipe input (WIDTHxHEIGHT)
+        pub pipe_size: Option<(u32, u32)>,
+        // Bytes per row of the pipe input, if rows are padded
+        pub pipe_stride: Option<usize>,
+        // Generate frames (a blob moving left and right) instead of using a camera
+        pub synthetic: bool,
+        // Image pasted into the synthetic frames instead of the blob (e.g. a hand photo)
+        pub synthetic_sprite: Option<PathBuf>,
+        // Replay an image sequence (a directory of frames) instead of using a camera
+        pub replay: Option<PathBuf>,
+        // Frame of the replay to start at (clamped to the last frame)
+        pub replay_from: Option<usize>,
+        // Detect hands on this monitor's screen instead of the camera (needs the `screen` feature)
+        pub screen: Option<usize>,
+        // Only capture this part of the screen (X,Y,WIDTH,HEIGHT in pixels)
+        pub screen_region: Option<(u32, u32, u32, u32)>,
+        // Screen captures per second (24 by default)
+        pub screen_fps: Option<u32>,
+        // Camera controls to set after opening the camera (NAME=VALUE, repeatable)
+        pub camera_controls: Vec<(ControlKind, i64)>,
+        // Turn the camera's automatic exposure on or off (Linux only)
+        pub auto_exposure: Option<bool>,
+        // Capture on a separate thread, queueing frames for the detect loop like this
+        pub capture_queue: Option<QueueConfig>,
+        // Run detection on a grid of overlapping tiles (COLSxROWS) for small hands
+        pub tiles: Option<(u32, u32)>,
+        // After hands are found, only detect in a region this many times their size
+        pub roi_tracking: Option<f32>,
+        // With ROI tracking, scan the full frame every this many frames (10 by default)
+        pub rescan_every: Option<u32>,
+        // Frames are this many camera images stitched side by side
+        pub stitched_cameras: Option<u32>,
+        // Fraction of each tile overlapping its neighbours
+        pub tile_overlap: Option<f32>,
+        // Only detect hands inside this normalized region (XMIN,YMIN,XMAX,YMAX)
+        pub active_zone: Option<Box>,
+        // Score activation applied before thresholding (raw logits or sigmoid)
+        pub score_activation: Option<ActivationMode>,
+        // Single-threaded, deterministic inference (bit-identical outputs for the same frame)
+        pub deterministic: bool,
+        // Filter for scaling frames to the model input (nearest, triangle, catmull-rom, lanczos3)
+        pub resize_filter: Option<FilterType>,
+        // Drop detections whose wrist or index knuckle keypoint is less visible than this
+        pub min_key_visibility: Option<f32>,
+        // Drop detections whose box is within this normalized margin of the frame edge
+        pub ignore_border: Option<f32>,
+        // Estimate wrist and palm keypoints less visible than this from the box
+        pub box_fallback: Option<f32>,
+        // Load the detector's anchors from this file instead of generating them
+        pub anchors: Option<PathBuf>,
+        // Maximum number of hands reported per frame
+        pub max_hands: Option<usize>,
+        // Warn when more than max_hands hands are in view
+        pub warn_overflow: bool,
+        // Order of the reported hands (score, left-to-right or handedness)
+        pub hand_order: Option<OutputOrder>,
+        // Whether overlapping hands of different handedness suppress each other
+        pub nms_mode: Option<NmsMode>,
+        // Show a terminal dashboard instead of the window (needs the `tui` feature)
+        pub tui: bool,
+        // Print each frame's detections as MediaPipe JSON on stdout (needs the `json` feature)
+        pub mediapipe_json: bool,
+        // Serve the latest detections over HTTP on this address (needs the `http` feature)
+        pub http: Option<String>,
+        // Print only changes (appeared, moved by more than this distance, disappeared,
+        // gestures) as JSON lines (needs the `json` feature)
+        pub changes_json: Option<f32>,
+        // Log detections to this JSON Lines file (needs the `json` feature)
+        pub log_jsonl: Option<PathBuf>,
+        // Only log every Nth frame
+        pub log_every: Option<u64>,
+        // Log only each hand's score and box center
+        pub log_minimal: bool,
+        // Run headless, without creating a window (servers, CI)
+        pub no_window: bool,
+        // Click when a hand holds still for this many milliseconds
+        pub dwell_click_ms: Option<u64>,
+        // How far (normalized) the pointer may drift during a dwell
+        pub dwell_radius: Option<f32>,
+        // Minimum hand stability (0.0 to 1.0) for a dwell to click
+        pub dwell_min_stability: Option<f32>,
+        // Scroll by wrist height, up to this many lines per second
+        pub positional_scroll: Option<f32>,
+        // Normalized heights above / below which positional scrolling goes up / down
+        pub scroll_zones: Option<(f32, f32)>,
+        // Only the primary hand, picked like this, drives input
+        pub lock_hand: Option<PrimarySelection>,
+        // How long the primary hand may be missing before another one takes over
+        pub lock_timeout_ms: Option<u64>,
+        // A closed fist toggles input control on and off
+        pub clutch: bool,
+        // Also draw the rotated box fitted around each hand's landmarks
+        pub rotated_boxes: bool,
+        // Draw each hand's detection score next to its box
+        pub show_scores: bool,
+        // Draw the FPS and inference time in the top left corner
+        pub show_fps: bool,
+        // Frames the FPS and inference time are averaged over (30 by default)
+        pub perf_window: Option<usize>,
+        // Title of the window
+        pub window_title: Option<String>,
+        // Channel order of the window pixels (for platforms showing red and blue swapped)
+        pub pixel_order: Option<PixelOrder>,
+        // Click on pinch start
+        pub pinch_click: bool,
+        // Finger -> mouse button mapping for pinch clicks
+        pub click_buttons: Option<Vec<(Finger, Button)>>,
+        // Minimum time between pinch clicks, in milliseconds
+        pub click_cooldown_ms: Option<u64>,
+        // Skip inference when the frame differs less than this from the previous one
+        pub motion_threshold: Option<f32>,
+        // Most frames in a row that can be skipped by the motion gate
+        pub max_skip: Option<u32>,
+        // Run inference at most this many times a second (the display still shows every frame)
+        pub inference_fps: Option<f32>,
+        // Window update rate limit (24 by default)
+        pub window_fps: Option<u32>,
+        // Draw the per-edge median of the last N boxes instead of the EMA-smoothed box
+        pub box_median: Option<usize>,
+        // One-Euro smoothing of the landmarks before gesture recognition
+        pub smooth_landmarks: Option<LandmarkSmoothing>,
+        // Gesture -> action bindings file (TOML)
+        pub bindings: Option<PathBuf>,
+        // Where to export the hand occupancy heatmap (.csv or .png) on exit
+        pub heatmap: Option<PathBuf>,
+        // Cells of the heatmap grid (COLSxROWS, 16x9 by default)
+        pub heatmap_grid: Option<(u32, u32)>,
+        // Save a snapshot into this directory on a thumbs-up gesture
+        pub snapshot_dir: Option<PathBuf>,
+        // Save snapshots with the drawn overlays (at window resolution)
+        pub snapshot_overlays: bool,
+        // Image format of saved snapshots (PNG by default)
+        pub snapshot_format: ImageFormat,
+        // Keep the last N frames in memory, saved with the D key or the trigger gesture
+        pub recent_frames: Option<usize>,
+        // Memory cap of the recent frames in MB (512 by default)
+        pub recent_max_mb: Option<usize>,
+        // Directory the recent frames are saved into ("recent" by default)
+        pub recent_dir: Option<PathBuf>,
+        // Gesture that also saves the recent frames (a name from GESTURE_NAMES)
+        pub recent_trigger: Option<&'static str>,
+        // Append labeled landmark sequences (R key) to gestures.csv in this directory
+        pub record_dir: Option<PathBuf>,
+        // Label of the recorded samples
+        pub record_label: Option<String>,
+        // Frames per recorded sample
+        pub record_frames: Option<usize>,
+    }
+
+    // "640x480" -> (640, 480)
+    fn parse_size(value: &str) -> Result<(u32, u32)> {
+        let (width, height) = value
+            .split_once('x')
+            .ok_or_else(|| anyhow!("Expected a size like 640x480, got '{}'", value))?;
+        Ok((width.parse()?, height.parse()?))
+    }
+
+    // "0.2,0.1,0.8,0.9" -> Box { xmin: 0.2, ymin: 0.1, xmax: 0.8, ymax: 0.9 }
+    fn parse_zone(value: &str) -> Result<Box> {
+        let values = value
+            .split(',')
+            .map(|v| v.trim().parse::<f32>())
+            .collect::<Result<Vec<f32>, _>>()?;
+        match values[..] {
+            [xmin, ymin, xmax, ymax] => Ok(Box {
+                xmin,
+                ymin,
+                xmax,
+                ymax,
+            }),
+            _ => Err(anyhow!(
+                "Expected XMIN,YMIN,XMAX,YMAX like 0.2,0.1,0.8,0.9, got '{}'",
+                value
+            )),
+        }
+    }
+
+    // "100,50,640,480" -> (x 100, y 50, width 640, height 480)
+    fn parse_region(value: &str) -> Result<(u32, u32, u32, u32)> {
+        let values = value
+            .split(',')
+            .map(|v| v.trim().parse::<u32>())
+            .collect::<Result<Vec<u32>, _>>()?;
+        match values[..] {
+            [x, y, width, height] if width > 0 && height > 0 => Ok((x, y, width, height)),
+            _ => Err(anyhow!(
+                "Expected X,Y,WIDTH,HEIGHT like 100,50,640,480, got '{}'",
+                value
+            )),
+        }
+    }
+
+    // "brightness=128" -> (Brightness, 128)
+    fn parse_control(value: &str) -> Result<(ControlKind, i64)> {
+        let (name, setting) = value
+            .split_once('=')
+            .ok_or_else(|| anyhow!("Expected a control like brightness=128, got '{}'", value))?;
+        let kind = ControlKind::from_name(name).ok_or_else(|| {
+            anyhow!(
+                "Unknown camera control '{}' (expected brightness, contrast, saturation, sharpness, gamma, gain, exposure, white_balance or backlight)",
+                name
+            )
+        })?;
+        Ok((kind, setting.parse()?))
+    }
+
+    // "index=left,middle=right" -> [(Index, Left), (Middle, Right)]
+    fn parse_click_buttons(value: &str) -> Result<Vec<(Finger, Button)>> {
+        value
+            .split(',')
+            .map(|pair| {
+                let (finger, button) = pair.split_once('=').ok_or_else(|| {
+                    anyhow!("Expected FINGER=BUTTON like index=left, got '{}'", pair)
+                })?;
+                let finger = Finger::from_name(finger.trim()).ok_or_else(|| {
+                    anyhow!(
+                        "Unknown finger '{}' (expected index, middle, ring or pinky)",
+                        finger
+                    )
+                })?;
+                Ok((finger, parse_button(button.trim())?))
+            })
+            .collect()
+    }
+
+    pub fn parse() -> Result<Options> {
+        let mut options = Options::default();
+        let mut args = std::env::args().skip(1);
+
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--list-formats" => {
+                    let index = args
+                        .next()
+                        .ok_or_else(|| anyhow!("--list-formats expects a camera index"))?;
+                    options.list_formats = Some(index.parse()?);
+                }
+                "--selftest" => options.selftest = true,
+                "--describe-model" => {
+                    let path = args
+                        .next()
+                        .ok_or_else(|| anyhow!("--describe-model expects a model path"))?;
+                    options.describe_model = Some(PathBuf::from(path));
+                }
+                "--pipe" => {
+                    let path = args
+                        .next()
+                        .ok_or_else(|| anyhow!("--pipe expects a file path or -"))?;
+                    options.pipe = Some(PathBuf::from(path));
+                }
+                "--pipe-size" => {
+                    let size = args
+                        .next()
+                        .ok_or_else(|| anyhow!("--pipe-size expects WIDTHxHEIGHT"))?;
+                    options.pipe_size = Some(parse_size(&size)?);
+                }
+                "--pipe-stride" => {
+                    let stride = args
+                        .next()
+                        .ok_or_else(|| anyhow!("--pipe-stride expects a byte count"))?;
+                    options.pipe_stride = Some(stride.parse()?);
+                }
+                "--synthetic" => options.synthetic = true,
+                "--synthetic-sprite" => {
+                    let path = args
+                        .next()
+                        .ok_or_else(|| anyhow!("--synthetic-sprite expects an image path"))?;
+                    options.synthetic_sprite = Some(PathBuf::from(path));
+                }
+                "--replay" => {
+                    let dir = args
+                        .next()
+                        .ok_or_else(|| anyhow!("--replay expects a directory of frames"))?;
+                    options.replay = Some(PathBuf::from(dir));
+                }
+                "--replay-from" => {
+                    let frame = args
+                        .next()
+                        .ok_or_else(|| anyhow!("--replay-from expects a frame index"))?;
+                    options.replay_from = Some(frame.parse()?);
+                }
+                "--camera-control" => {
+                    let control = args
+                        .next()
+                        .ok_or_else(|| anyhow!("--camera-control expects NAME=VALUE"))?;
+                    options.camera_controls.push(parse_control(&control)?);
+                }
+                "--auto-exposure" => {
+                    let setting = args
+                        .next()
+                        .ok_or_else(|| anyhow!("--auto-exposure expects on or off"))?;
+                    options.auto_exposure = Some(match setting.as_str() {
+                        "on" => true,
+                        "off" => false,
+                        _ => return Err(anyhow!("--auto-exposure expects on or off")),
+                    });
+                }
+                "--screen" => {
+                    let index = args
+                        .next()
+                        .ok_or_else(|| anyhow!("--screen expects a monitor index"))?;
+                    options.screen = Some(index.parse()?);
+                }
+                "--screen-region" => {
+                    let region = args
+                        .next()
+                        .ok_or_else(|| anyhow!("--screen-region expects X,Y,WIDTH,HEIGHT"))?;
+                    options.screen_region = Some(parse_region(&region)?);
+                }
+                "--screen-fps" => {
+                    let fps = args
+                        .next()
+                        .ok_or_else(|| anyhow!("--screen-fps expects a rate"))?;
+                    options.screen_fps = Some(fps.parse()?);
+                }
+                "--capture-thread" => {
+                    options
+                        .capture_queue
+                        .get_or_insert_with(QueueConfig::default);
+                }
+                "--queue-capacity" => {
+                    let capacity = args
+                        .next()
+                        .ok_or_else(|| anyhow!("--queue-capacity expects a number of frames"))?;
+                    let capacity: usize = capacity.parse()?;
+                    if capacity == 0 {
+                        return Err(anyhow!("--queue-capacity must be at least 1"));
+                    }
+                    options
+                        .capture_queue
+                        .get_or_insert_with(QueueConfig::default)
+                        .capacity = capacity;
+                }
+                "--drop-policy" => {
+                    let policy = args.next().ok_or_else(|| {
+                        anyhow!("--drop-policy expects drop-oldest, drop-newest or block")
+                    })?;
+                    options
+                        .capture_queue
+                        .get_or_insert_with(QueueConfig::default)
+                        .policy = DropPolicy::from_name(&policy).ok_or_else(|| {
+                        anyhow!(
+                            "Unknown drop policy '{}' (expected drop-oldest, drop-newest or block)",
+                            policy
+                        )
+                    })?;
+                }
+                "--roi-tracking" => {
+                    let expansion = args.next().ok_or_else(|| {
+                        anyhow!("--roi-tracking expects an expansion factor (e.g. 2.0)")
+                    })?;
+                    options.roi_tracking = Some(expansion.parse()?);
+                }
+                "--rescan-every" => {
+                    let frames = args
+                        .next()
+                        .ok_or_else(|| anyhow!("--rescan-every expects a frame count"))?;
+                    options.rescan_every = Some(frames.parse()?);
+                }
+                "--stitched" => {
+                    let cameras = args
+                        .next()
+                        .ok_or_else(|| anyhow!("--stitched expects a camera count"))?;
+                    options.stitched_cameras = Some(cameras.parse()?);
+                }
+                "--tiles" => {
+                    let grid = args
+                        .next()
+                        .ok_or_else(|| anyhow!("--tiles expects COLSxROWS"))?;
+                    options.tiles = Some(parse_size(&grid)?);
+                }
+                "--tile-overlap" => {
+                    let overlap = args
+                        .next()
+                        .ok_or_else(|| anyhow!("--tile-overlap expects a fraction"))?;
+                    options.tile_overlap = Some(overlap.parse()?);
+                }
+                "--active-zone" => {
+                    let zone = args
+                        .next()
+                        .ok_or_else(|| anyhow!("--active-zone expects XMIN,YMIN,XMAX,YMAX"))?;
+                    options.active_zone = Some(parse_zone(&zone)?);
+                }
+                "--score-activation" => {
+                    let mode = args
+                        .next()
+                        .ok_or_else(|| anyhow!("--score-activation expects raw or sigmoid"))?;
+                    options.score_activation =
+                        Some(ActivationMode::from_name(&mode).ok_or_else(|| {
+                            anyhow!(
+                                "Unknown score activation '{}' (expected raw or sigmoid)",
+                                mode
+                            )
+                        })?);
+                }
+                "--min-key-visibility" => {
+                    let threshold = args.next().ok_or_else(|| {
+                        anyhow!("--min-key-visibility expects a threshold (0.0 to 1.0)")
+                    })?;
+                    options.min_key_visibility = Some(threshold.parse()?);
+                }
+                "--ignore-border" => {
+                    let margin = args.next().ok_or_else(|| {
+                        anyhow!("--ignore-border expects a margin (e.g. 0.02 of the frame)")
+                    })?;
+                    options.ignore_border = Some(margin.parse()?);
+                }
+                "--box-fallback" => {
+                    let threshold = args.next().ok_or_else(|| {
+                        anyhow!("--box-fallback expects a visibility threshold (0.0 to 1.0)")
+                    })?;
+                    options.box_fallback = Some(threshold.parse()?);
+                }
+                "--anchors" => {
+                    let path = args
+                        .next()
+                        .ok_or_else(|| anyhow!("--anchors expects a file path"))?;
+                    options.anchors = Some(PathBuf::from(path));
+                }
+                "--warn-overflow" => options.warn_overflow = true,
+                "--hand-order" => {
+                    let order = args.next().ok_or_else(|| {
+                        anyhow!("--hand-order expects score, left-to-right or handedness")
+                    })?;
+                    options.hand_order = Some(OutputOrder::from_name(&order).ok_or_else(|| {
+                        anyhow!(
+                            "Unknown hand order '{}' (expected score, left-to-right or handedness)",
+                            order
+                        )
+                    })?);
+                }
+                "--nms" => {
+                    let mode = args
+                        .next()
+                        .ok_or_else(|| anyhow!("--nms expects class-agnostic or class-aware"))?;
+                    options.nms_mode = Some(NmsMode::from_name(&mode).ok_or_else(|| {
+                        anyhow!(
+                            "Unknown NMS mode '{}' (expected class-agnostic or class-aware)",
+                            mode
+                        )
+                    })?);
+                }
+                "--max-hands" => {
+                    let count = args
+                        .next()
+                        .ok_or_else(|| anyhow!("--max-hands expects a number"))?;
+                    options.max_hands = Some(count.parse()?);
+                }
+                "--deterministic" => options.deterministic = true,
+                "--resize-filter" => {
+                    let name = args.next().ok_or_else(|| {
+                        anyhow!(
+                            "--resize-filter expects nearest, triangle, catmull-rom or lanczos3"
+                        )
+                    })?;
+                    options.resize_filter =
+                        Some(resize_filter_from_name(&name).ok_or_else(|| {
+                            anyhow!(
+                                "Unknown resize filter '{}' (expected nearest, triangle, catmull-rom or lanczos3)",
+                                name
+                            )
+                        })?);
+                }
+                "--compare-filters" => {
+                    let path = args
+                        .next()
+                        .ok_or_else(|| anyhow!("--compare-filters expects an image path"))?;
+                    options.compare_filters = Some(PathBuf::from(path));
+                }
+                "--bench-batch" => {
+                    let dir = args
+                        .next()
+                        .ok_or_else(|| anyhow!("--bench-batch expects a directory of frames"))?;
+                    options.bench_batch = Some(PathBuf::from(dir));
+                }
+                "--compare-roi" => {
+                    let dir = args
+                        .next()
+                        .ok_or_else(|| anyhow!("--compare-roi expects a directory of frames"))?;
+                    options.compare_roi = Some(PathBuf::from(dir));
+                }
+                "--tui" => options.tui = true,
+                "--mediapipe-json" => options.mediapipe_json = true,
+                "--changes-json" => {
+                    let threshold = args
+                        .next()
+                        .ok_or_else(|| anyhow!("--changes-json expects a movement threshold"))?;
+                    options.changes_json = Some(threshold.parse()?);
+                }
+                "--log-jsonl" => {
+                    let path = args
+                        .next()
+                        .ok_or_else(|| anyhow!("--log-jsonl expects a file path"))?;
+                    options.log_jsonl = Some(PathBuf::from(path));
+                }
+                "--log-every" => {
+                    let every = args
+                        .next()
+                        .ok_or_else(|| anyhow!("--log-every expects a frame count"))?;
+                    options.log_every = Some(every.parse()?);
+                }
+                "--log-minimal" => options.log_minimal = true,
+                "--no-window" => options.no_window = true,
+                "--show-scores" => options.show_scores = true,
+                "--show-fps" => options.show_fps = true,
+                "--perf-window" => {
+                    let frames = args
+                        .next()
+                        .ok_or_else(|| anyhow!("--perf-window expects a frame count"))?;
+                    options.perf_window = Some(frames.parse()?);
+                }
+                "--rotated-boxes" => options.rotated_boxes = true,
+                "--clutch" => options.clutch = true,
+                "--dwell-click" => {
+                    let millis = args
+                        .next()
+                        .ok_or_else(|| anyhow!("--dwell-click expects milliseconds"))?;
+                    options.dwell_click_ms = Some(millis.parse()?);
+                }
+                "--dwell-radius" => {
+                    let radius = args
+                        .next()
+                        .ok_or_else(|| anyhow!("--dwell-radius expects a distance"))?;
+                    options.dwell_radius = Some(radius.parse()?);
+                }
+                "--positional-scroll" => {
+                    let speed = args.next().ok_or_else(|| {
+                        anyhow!("--positional-scroll expects a speed (lines per second)")
+                    })?;
+                    options.positional_scroll = Some(speed.parse()?);
+                }
+                "--scroll-zones" => {
+                    let zones = args
+                        .next()
+                        .ok_or_else(|| anyhow!("--scroll-zones expects UP,DOWN"))?;
+                    let (up, down) = zones.split_once(',').ok_or_else(|| {
+                        anyhow!("Expected UP,DOWN like 0.33,0.67, got '{}'", zones)
+                    })?;
+                    options.scroll_zones = Some((up.trim().parse()?, down.trim().parse()?));
+                }
+                "--lock-hand" => {
+                    let selection = args.next().ok_or_else(|| {
+                        anyhow!("--lock-hand expects first, score, left or right")
+                    })?;
+                    options.lock_hand =
+                        Some(PrimarySelection::from_name(&selection).ok_or_else(|| {
+                            anyhow!(
+                                "Unknown hand selection '{}' (expected first, score, left or right)",
+                                selection
+                            )
+                        })?);
+                }
+                "--lock-timeout" => {
+                    let millis = args
+                        .next()
+                        .ok_or_else(|| anyhow!("--lock-timeout expects milliseconds"))?;
+                    options.lock_timeout_ms = Some(millis.parse()?);
+                }
+                "--dwell-min-stability" => {
+                    let stability = args.next().ok_or_else(|| {
+                        anyhow!("--dwell-min-stability expects a stability (0.0 to 1.0)")
+                    })?;
+                    options.dwell_min_stability = Some(stability.parse()?);
+                }
+                "--http" => {
+                    let address = args.next().ok_or_else(|| {
+                        anyhow!("--http expects an address (e.g. 127.0.0.1:8080)")
+                    })?;
+                    options.http = Some(address);
+                }
+                "--pixel-order" => {
+                    let order = args
+                        .next()
+                        .ok_or_else(|| anyhow!("--pixel-order expects argb or abgr"))?;
+                    options.pixel_order = Some(PixelOrder::from_name(&order).ok_or_else(|| {
+                        anyhow!("Unknown pixel order '{}' (expected argb or abgr)", order)
+                    })?);
+                }
+                "--window-title" => {
+                    let title = args
+                        .next()
+                        .ok_or_else(|| anyhow!("--window-title expects a title"))?;
+                    options.window_title = Some(title);
+                }
+                "--pinch-click" => options.pinch_click = true,
+                "--click-buttons" => {
+                    let mapping = args.next().ok_or_else(|| {
+                        anyhow!("--click-buttons expects FINGER=BUTTON pairs like index=left")
+                    })?;
+                    options.click_buttons = Some(parse_click_buttons(&mapping)?);
+                }
+                "--click-cooldown" => {
+                    let millis = args
+                        .next()
+                        .ok_or_else(|| anyhow!("--click-cooldown expects milliseconds"))?;
+                    options.click_cooldown_ms = Some(millis.parse()?);
+                }
+                "--motion-gate" => {
+                    let threshold = args
+                        .next()
+                        .ok_or_else(|| anyhow!("--motion-gate expects a threshold"))?;
+                    options.motion_threshold = Some(threshold.parse()?);
+                }
+                "--inference-fps" => {
+                    let fps = args
+                        .next()
+                        .ok_or_else(|| anyhow!("--inference-fps expects a rate"))?;
+                    let fps: f32 = fps.parse()?;
+                    if fps <= 0.0 {
+                        return Err(anyhow!("--inference-fps must be positive"));
+                    }
+                    options.inference_fps = Some(fps);
+                }
+                "--window-fps" => {
+                    let fps = args
+                        .next()
+                        .ok_or_else(|| anyhow!("--window-fps expects a rate"))?;
+                    let fps: u32 = fps.parse()?;
+                    if fps == 0 {
+                        return Err(anyhow!("--window-fps must be at least 1"));
+                    }
+                    options.window_fps = Some(fps);
+                }
+                "--max-skip" => {
+                    let frames = args
+                        .next()
+                        .ok_or_else(|| anyhow!("--max-skip expects a frame count"))?;
+                    options.max_skip = Some(frames.parse()?);
+                }
+                "--box-median" => {
+                    let frames = args
+                        .next()
+                        .ok_or_else(|| anyhow!("--box-median expects a frame count"))?;
+                    options.box_median = Some(frames.parse()?);
+                }
+                "--smooth-landmarks" => {
+                    let mode = args.next().ok_or_else(|| {
+                        anyhow!("--smooth-landmarks expects pointing or gesturing")
+                    })?;
+                    options.smooth_landmarks = Some(match mode.as_str() {
+                        "pointing" => LandmarkSmoothing::pointing(),
+                        "gesturing" => LandmarkSmoothing::gesturing(),
+                        _ => {
+                            return Err(anyhow!(
+                                "Unknown smoothing '{}' (expected pointing or gesturing)",
+                                mode
+                            ));
+                        }
+                    });
+                }
+                "--bindings" => {
+                    let path = args
+                        .next()
+                        .ok_or_else(|| anyhow!("--bindings expects a file path"))?;
+                    options.bindings = Some(PathBuf::from(path));
+                }
+                "--heatmap" => {
+                    let path = args
+                        .next()
+                        .ok_or_else(|| anyhow!("--heatmap expects a file path"))?;
+                    let path = PathBuf::from(path);
+                    occupancy::check_path(&path)?;
+                    options.heatmap = Some(path);
+                }
+                "--heatmap-grid" => {
+                    let grid = args
+                        .next()
+                        .ok_or_else(|| anyhow!("--heatmap-grid expects COLSxROWS"))?;
+                    options.heatmap_grid = Some(parse_size(&grid)?);
+                }
+                "--snapshot-dir" => {
+                    let path = args
+                        .next()
+                        .ok_or_else(|| anyhow!("--snapshot-dir expects a directory"))?;
+                    options.snapshot_dir = Some(PathBuf::from(path));
+                }
+                "--snapshot-overlays" => options.snapshot_overlays = true,
+                "--snapshot-format" => {
+                    let format = args.next().ok_or_else(|| {
+                        anyhow!("--snapshot-format expects png, jpeg, jpeg:QUALITY or bmp")
+                    })?;
+                    options.snapshot_format = ImageFormat::parse(&format)?;
+                }
+                "--recent-frames" => {
+                    let frames = args
+                        .next()
+                        .ok_or_else(|| anyhow!("--recent-frames expects a frame count"))?;
+                    options.recent_frames = Some(frames.parse()?);
+                }
+                "--recent-max-mb" => {
+                    let megabytes = args
+                        .next()
+                        .ok_or_else(|| anyhow!("--recent-max-mb expects a size in MB"))?;
+                    options.recent_max_mb = Some(megabytes.parse()?);
+                }
+                "--recent-dir" => {
+                    let path = args
+                        .next()
+                        .ok_or_else(|| anyhow!("--recent-dir expects a directory"))?;
+                    options.recent_dir = Some(PathBuf::from(path));
+                }
+                "--recent-trigger" => {
+                    let name = args
+                        .next()
+                        .ok_or_else(|| anyhow!("--recent-trigger expects a gesture name"))?;
+                    let gesture = GESTURE_NAMES
+                        .iter()
+                        .find(|known| **known == name)
+                        .ok_or_else(|| {
+                            anyhow!(
+                                "Unknown gesture '{}' (expected one of: {})",
+                                name,
+                                GESTURE_NAMES.join(", ")
+                            )
+                        })?;
+                    options.recent_trigger = Some(gesture);
+                }
+                "--record-dir" => {
+                    let path = args
+                        .next()
+                        .ok_or_else(|| anyhow!("--record-dir expects a directory"))?;
+                    options.record_dir = Some(PathBuf::from(path));
+                }
+                "--record-label" => {
+                    let label = args
+                        .next()
+                        .ok_or_else(|| anyhow!("--record-label expects a label"))?;
+                    options.record_label = Some(label);
+                }
+                "--record-frames" => {
+                    let frames = args
+                        .next()
+                        .ok_or_else(|| anyhow!("--record-frames expects a frame count"))?;
+                    options.record_frames = Some(frames.parse()?);
+                }
+                _ => return Err(anyhow!("Unknown argument: {}", arg)),
+            }
+        }
+
+        if options.tui && !cfg!(feature = "tui") {
+            return Err(anyhow!("--tui needs fingers built with the `tui` feature"));
+        }
+
+        if options.mediapipe_json && !cfg!(feature = "json") {
+            return Err(anyhow!(
+                "--mediapipe-json needs fingers built with the `json` feature"
+            ));
+        }
+
+        if options.screen.is_some() && !cfg!(feature = "screen") {
+            return Err(anyhow!(
+                "--screen needs fingers built with the `screen` feature"
+            ));
+        }
+
+        if options.changes_json.is_some() && !cfg!(feature = "json") {
+            return Err(anyhow!(
+                "--changes-json needs fingers built with the `json` feature"
+            ));
+        }
+
+        if options.log_jsonl.is_some() && !cfg!(feature = "json") {
+            return Err(anyhow!(
+                "--log-jsonl needs fingers built with the `json` feature"
+            ));
+        }
+
+        if options.http.is_some() && !cfg!(feature = "http") {
+            return Err(anyhow!(
+                "--http needs fingers built with the `http` feature"
+            ));
+        }
+
+        if options.pipe.is_some() && options.pipe_size.is_none() {
+            return Err(anyhow!("--pipe requires --pipe-size WIDTHxHEIGHT"));
+        }
+
+        // Return
+        Ok(options)
+    }
+}
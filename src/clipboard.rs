@@ -0,0 +1,28 @@
+pub mod detection_clipboard {
+    use crate::detector::hand_detector::HandDetails;
+    use anyhow::Result;
+    use serde::Serialize;
+
+    // Everything needed to reproduce a bad detection in a bug report
+    #[derive(Serialize)]
+    pub struct DetectionReport<'a> {
+        pub version: &'static str,
+        pub model: &'a str,
+        pub source: &'a str,
+        pub frame_width: u32,
+        pub frame_height: u32,
+        pub hands: &'a [HandDetails],
+    }
+
+    pub fn to_json(report: &DetectionReport) -> Result<String> {
+        Ok(serde_json::to_string_pretty(report)?)
+    }
+
+    // Copy the report as pretty-printed JSON to the system clipboard
+    pub fn copy(report: &DetectionReport) -> Result<()> {
+        let json = to_json(report)?;
+        let mut clipboard = arboard::Clipboard::new()?;
+        clipboard.set_text(json)?;
+        Ok(())
+    }
+}
@@ -1,15 +1,735 @@
 pub mod input_device {
 
-    use anyhow::Error;
-    use enigo::{Enigo, Settings, Mouse};
+    use crate::gesture::gestures::{Finger, GestureEvent};
+    use crate::landmarker::hand_landmarker::INDEX_TIP;
+    use crate::tracker::hand_tracker::TrackedHand;
+    use anyhow::{Error, anyhow};
+    use enigo::{Axis, Button, Coordinate, Direction, Enigo, Mouse, Settings};
+    use std::collections::{HashMap, HashSet};
+    use std::time::{Duration, Instant};
 
-    pub fn create() -> Result<Enigo, Error> {
+    // A display's region in the absolute (virtual desktop) coordinate space
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct Display {
+        pub x: i32,
+        pub y: i32,
+        pub width: i32,
+        pub height: i32,
+    }
+
+    // Maps normalized hand coordinates (0.0 to 1.0) onto one display.
+    // enigo can only report the main display's size. On multi-monitor setups, build with
+    // the `screen` feature to query the layout with `from_monitors`, or supply it with
+    // `with_displays` (in the OS's virtual desktop coordinates, where the main display's
+    // top-left corner is 0,0).
+    #[derive(Debug, Clone)]
+    pub struct CursorMapper {
+        displays: Vec<Display>,
+        target_display: usize,
+    }
+
+    impl CursorMapper {
+        pub fn from_main_display(enigo: &Enigo) -> Result<Self, Error> {
+            let (width, height) = enigo.main_display()?;
+            Ok(Self::with_displays(vec![Display {
+                x: 0,
+                y: 0,
+                width,
+                height,
+            }]))
+        }
+
+        // Every monitor with its position in the virtual desktop, in the order xcap lists
+        // them (the same indices as `--screen`). Not available without the `screen` feature,
+        // and fails where xcap can't list monitors (e.g. Wayland without the desktop portal).
+        #[cfg(feature = "screen")]
+        pub fn from_monitors() -> Result<Self, Error> {
+            let displays = xcap::Monitor::all()?
+                .iter()
+                .map(|monitor| {
+                    Ok(Display {
+                        x: monitor.x()?,
+                        y: monitor.y()?,
+                        width: monitor.width()? as i32,
+                        height: monitor.height()? as i32,
+                    })
+                })
+                .collect::<Result<Vec<_>, xcap::XCapError>>()?;
+            if displays.is_empty() {
+                return Err(anyhow!("No monitors found"));
+            }
+            Ok(Self::with_displays(displays))
+        }
 
+        pub fn with_displays(displays: Vec<Display>) -> Self {
+            Self {
+                displays,
+                target_display: 0,
+            }
+        }
+
+        // Confine the cursor to the display at this index
+        pub fn with_target_display(mut self, target_display: usize) -> Result<Self, Error> {
+            if target_display >= self.displays.len() {
+                return Err(anyhow!(
+                    "Target display {} out of range ({} displays known)",
+                    target_display,
+                    self.displays.len()
+                ));
+            }
+            self.target_display = target_display;
+            Ok(self)
+        }
+
+        pub fn target_display(&self) -> Display {
+            self.displays[self.target_display]
+        }
+
+        pub fn to_screen(&self, x: f32, y: f32) -> (i32, i32) {
+            let display = self.target_display();
+            let px = display.x + (x.clamp(0.0, 1.0) * (display.width - 1) as f32) as i32;
+            let py = display.y + (y.clamp(0.0, 1.0) * (display.height - 1) as f32) as i32;
+            (px, py)
+        }
+    }
+
+    pub fn create() -> Result<Enigo, Error> {
         // Setup Input Controller (Enigo)
-        let enigo_controller = Enigo::new(&Settings::default()).unwrap();
+        let enigo_controller = Enigo::new(&Settings::default())?;
 
         // Return
         Ok(enigo_controller)
     }
-    
-}
\ No newline at end of file
+
+    pub fn move_cursor_to_normalized(
+        enigo: &mut Enigo,
+        mapper: &CursorMapper,
+        x: f32,
+        y: f32,
+    ) -> Result<(), Error> {
+        let (px, py) = mapper.to_screen(x, y);
+        enigo.move_mouse(px, py, Coordinate::Abs)?;
+        Ok(())
+    }
+
+    // Pointer movement per frame (normalized) the cursor response curve treats as full speed
+    const CURVE_REFERENCE: f32 = 0.05;
+
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    pub struct CursorSettings {
+        // EMA weight of the new position: 1.0 follows the hand exactly, lower is smoother
+        pub alpha: f32,
+        // Normalized distance the smoothed position can move without moving the cursor
+        pub dead_zone: f32,
+        // Response curve exponent (1.0 is linear, higher slows small movements more)
+        pub curve: f32,
+    }
+
+    // Named CursorFilter settings for common uses:
+    //   Precise:  alpha 0.7,  dead zone 0.001, curve 1.6 (clicking small targets)
+    //   Balanced: alpha 0.5,  dead zone 0.003, curve 1.2 (the default)
+    //   Smooth:   alpha 0.25, dead zone 0.006, curve 1.0 (presenting, steady but laggy)
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+    pub enum CursorPreset {
+        Precise,
+        #[default]
+        Balanced,
+        Smooth,
+    }
+
+    impl CursorPreset {
+        pub fn from_name(name: &str) -> Option<Self> {
+            match name {
+                "precise" => Some(CursorPreset::Precise),
+                "balanced" => Some(CursorPreset::Balanced),
+                "smooth" => Some(CursorPreset::Smooth),
+                _ => None,
+            }
+        }
+
+        pub fn settings(self) -> CursorSettings {
+            let (alpha, dead_zone, curve) = match self {
+                CursorPreset::Precise => (0.7, 0.001, 1.6),
+                CursorPreset::Balanced => (0.5, 0.003, 1.2),
+                CursorPreset::Smooth => (0.25, 0.006, 1.0),
+            };
+            CursorSettings {
+                alpha,
+                dead_zone,
+                curve,
+            }
+        }
+    }
+
+    // Filters the normalized pointer position before it moves the cursor, in three steps:
+    // an EMA (`alpha`), a dead zone that holds the cursor while the smoothed position stays
+    // within `dead_zone` of it (hand tremor), and a response curve: movement past the dead
+    // zone of less than CURVE_REFERENCE per frame is scaled down by
+    // (movement / CURVE_REFERENCE)^(curve - 1), so small motions position precisely while
+    // large ones still travel at full speed. Starts from the Balanced preset.
+    #[derive(Debug, Clone)]
+    pub struct CursorFilter {
+        settings: CursorSettings,
+        smoothed: Option<(f32, f32)>,
+        cursor: Option<(f32, f32)>,
+    }
+
+    impl Default for CursorFilter {
+        fn default() -> Self {
+            Self::new()
+        }
+    }
+
+    impl CursorFilter {
+        pub fn new() -> Self {
+            Self {
+                settings: CursorPreset::Balanced.settings(),
+                smoothed: None,
+                cursor: None,
+            }
+        }
+
+        // Take all settings from a preset (individual ones can be overridden afterwards)
+        pub fn with_cursor_preset(mut self, preset: CursorPreset) -> Self {
+            self.settings = preset.settings();
+            self
+        }
+
+        pub fn with_alpha(mut self, alpha: f32) -> Self {
+            self.settings.alpha = alpha.clamp(0.01, 1.0);
+            self
+        }
+
+        pub fn with_dead_zone(mut self, dead_zone: f32) -> Self {
+            self.settings.dead_zone = dead_zone.max(0.0);
+            self
+        }
+
+        // Curves below 1.0 would speed up small movements past the hand, so they act as 1.0
+        pub fn with_curve(mut self, curve: f32) -> Self {
+            self.settings.curve = curve.max(1.0);
+            self
+        }
+
+        pub fn settings(&self) -> CursorSettings {
+            self.settings
+        }
+
+        // Feed this frame's pointer position, returns where to put the cursor
+        pub fn filter(&mut self, x: f32, y: f32) -> (f32, f32) {
+            let alpha = self.settings.alpha;
+            let smoothed = match self.smoothed {
+                Some((sx, sy)) => (sx + alpha * (x - sx), sy + alpha * (y - sy)),
+                None => (x, y),
+            };
+            self.smoothed = Some(smoothed);
+
+            let Some(cursor) = self.cursor else {
+                self.cursor = Some(smoothed);
+                return smoothed;
+            };
+            let (dx, dy) = (smoothed.0 - cursor.0, smoothed.1 - cursor.1);
+            let distance = (dx * dx + dy * dy).sqrt();
+            if distance <= self.settings.dead_zone {
+                return cursor;
+            }
+
+            let moved = distance - self.settings.dead_zone;
+            let step = moved
+                * (moved / CURVE_REFERENCE)
+                    .min(1.0)
+                    .powf(self.settings.curve - 1.0);
+            let cursor = (
+                cursor.0 + dx / distance * step,
+                cursor.1 + dy / distance * step,
+            );
+            self.cursor = Some(cursor);
+            cursor
+        }
+
+        // Forget the last position, so the next one is taken as is (e.g. a new hand)
+        pub fn reset(&mut self) {
+            self.smoothed = None;
+            self.cursor = None;
+        }
+    }
+
+    // Drag and drop by hand: the left button is pressed on pinch start, the cursor
+    // follows the pinching hand each frame and the button is released on pinch end.
+    // If the dragging hand is lost mid-drag the button is released as a safety measure,
+    // so it can never get stuck pressed.
+    pub struct DragGesture {
+        mapper: CursorMapper,
+        filter: Option<CursorFilter>,
+        dragging: Option<u32>,
+    }
+
+    impl DragGesture {
+        pub fn new(mapper: CursorMapper) -> Self {
+            Self {
+                mapper,
+                filter: None,
+                dragging: None,
+            }
+        }
+
+        // Filter the dragged cursor's movement (restarted with each drag)
+        pub fn with_cursor_filter(mut self, filter: CursorFilter) -> Self {
+            self.filter = Some(filter);
+            self
+        }
+
+        fn filter(&mut self, x: f32, y: f32) -> (f32, f32) {
+            match self.filter.as_mut() {
+                Some(filter) => filter.filter(x, y),
+                None => (x, y),
+            }
+        }
+
+        pub fn is_dragging(&self) -> bool {
+            self.dragging.is_some()
+        }
+
+        // Call once per frame with that frame's gesture events and tracked hands
+        pub fn update(
+            &mut self,
+            enigo: &mut Enigo,
+            events: &[GestureEvent],
+            tracked: &[TrackedHand],
+        ) -> Result<(), Error> {
+            for event in events {
+                match *event {
+                    GestureEvent::PinchStart {
+                        id,
+                        finger: Finger::Index,
+                        x,
+                        y,
+                    } if self.dragging.is_none() => {
+                        if let Some(filter) = self.filter.as_mut() {
+                            filter.reset();
+                        }
+                        let (x, y) = self.filter(x, y);
+                        move_cursor_to_normalized(enigo, &self.mapper, x, y)?;
+                        enigo.button(Button::Left, Direction::Press)?;
+                        self.dragging = Some(id);
+                    }
+                    GestureEvent::PinchEnd {
+                        id,
+                        finger: Finger::Index,
+                    } if self.dragging == Some(id) => {
+                        self.release(enigo)?;
+                    }
+                    _ => {}
+                }
+            }
+
+            if let Some(id) = self.dragging {
+                match tracked.iter().find(|hand| hand.id == id) {
+                    Some(hand) => {
+                        // Follow the index fingertip, or the palm pointer without landmarks
+                        let point = hand
+                            .details
+                            .landmark(INDEX_TIP)
+                            .unwrap_or_else(|| hand.details.pointer());
+                        let (x, y) = self.filter(point.x, point.y);
+                        move_cursor_to_normalized(enigo, &self.mapper, x, y)?;
+                    }
+                    None => self.release(enigo)?,
+                }
+            }
+
+            Ok(())
+        }
+
+        // Release the button if a drag is in progress (also call this on shutdown)
+        pub fn release(&mut self, enigo: &mut Enigo) -> Result<(), Error> {
+            if self.dragging.take().is_some() {
+                enigo.button(Button::Left, Direction::Release)?;
+            }
+            Ok(())
+        }
+    }
+
+    // Default finger -> button mapping for pinch clicks
+    pub const DEFAULT_CLICK_BUTTONS: [(Finger, Button); 3] = [
+        (Finger::Index, Button::Left),
+        (Finger::Middle, Button::Right),
+        (Finger::Ring, Button::Middle),
+    ];
+
+    // Mouse click on pinch start, with the button picked by the finger touching the thumb
+    // (index = left, middle = right, ring = middle by default; see `with_buttons`).
+    // A pinch hovering around the threshold can start and end every few frames, so clicks
+    // of a button within `click_cooldown` of its last one are dropped, and with
+    // `require_release` a hand must end its pinch before that finger can click again.
+    // Each finger is debounced independently.
+    pub struct PinchClicker {
+        buttons: Vec<(Finger, Button)>,
+        click_cooldown: Duration,
+        require_release: bool,
+        last_click: HashMap<Finger, Instant>,
+        // (hand, finger) pinches that have started and not yet ended
+        pinching: HashSet<(u32, Finger)>,
+    }
+
+    impl PinchClicker {
+        pub fn new() -> Self {
+            Self {
+                buttons: DEFAULT_CLICK_BUTTONS.to_vec(),
+                click_cooldown: Duration::from_millis(250),
+                require_release: true,
+                last_click: HashMap::new(),
+                pinching: HashSet::new(),
+            }
+        }
+
+        // Replace the finger -> button mapping. Fingers left out don't click.
+        pub fn with_buttons(mut self, buttons: Vec<(Finger, Button)>) -> Self {
+            self.buttons = buttons;
+            self
+        }
+
+        pub fn with_click_cooldown(mut self, click_cooldown: Duration) -> Self {
+            self.click_cooldown = click_cooldown;
+            self
+        }
+
+        pub fn with_require_release(mut self, require_release: bool) -> Self {
+            self.require_release = require_release;
+            self
+        }
+
+        pub fn button(&self, finger: Finger) -> Option<Button> {
+            self.buttons
+                .iter()
+                .find(|(f, _)| *f == finger)
+                .map(|(_, button)| *button)
+        }
+
+        // Call once per frame with that frame's gesture events and tracked hands.
+        // Returns whether a click was sent.
+        pub fn update(
+            &mut self,
+            enigo: &mut Enigo,
+            events: &[GestureEvent],
+            tracked: &[TrackedHand],
+            now: Instant,
+        ) -> Result<bool, Error> {
+            // Lost hands can't send their pinch end
+            self.pinching
+                .retain(|(id, _)| tracked.iter().any(|hand| hand.id == *id));
+
+            let mut clicked = false;
+            for event in events {
+                if let Some(button) = self.click_for(event, now) {
+                    enigo.button(button, Direction::Click)?;
+                    clicked = true;
+                }
+            }
+
+            Ok(clicked)
+        }
+
+        // The button one gesture event clicks, or None when it doesn't click: not a pinch
+        // start of a mapped finger, a pinch that was never released, or a button still
+        // cooling down. Keeps track of pinches and clicks, without sending anything.
+        pub fn click_for(&mut self, event: &GestureEvent, now: Instant) -> Option<Button> {
+            match *event {
+                GestureEvent::PinchStart { id, finger, .. } => {
+                    let button = self.button(finger)?;
+                    let held = !self.pinching.insert((id, finger)) && self.require_release;
+                    let cooling_down = self
+                        .last_click
+                        .get(&finger)
+                        .is_some_and(|last| now.duration_since(*last) < self.click_cooldown);
+                    if held || cooling_down {
+                        return None;
+                    }
+                    self.last_click.insert(finger, now);
+                    Some(button)
+                }
+                GestureEvent::PinchEnd { id, finger } => {
+                    self.pinching.remove(&(id, finger));
+                    None
+                }
+                _ => None,
+            }
+        }
+    }
+
+    impl Default for PinchClicker {
+        fn default() -> Self {
+            Self::new()
+        }
+    }
+
+    // Click by holding still: when a hand's pointer (index fingertip, or the palm pointer
+    // without landmarks) stays within `radius` (normalized) for `dwell`, the left button is
+    // clicked. Moving out of the radius restarts the dwell, and after a click the pointer has
+    // to move away before it can click again. A hands-free alternative to pinching.
+    pub struct DwellClicker {
+        dwell: Duration,
+        radius: f32,
+        // Hand being followed, where its dwell started and when
+        anchor: Option<(u32, f32, f32, Instant)>,
+        clicked: bool,
+        min_stability: f32,
+    }
+
+    impl DwellClicker {
+        pub fn new(dwell: Duration) -> Self {
+            Self {
+                dwell,
+                radius: 0.02,
+                anchor: None,
+                clicked: false,
+                min_stability: 0.0,
+            }
+        }
+
+        // Also require the hand's stability (TrackedHand::stability) to be at least this
+        // when the dwell completes, so a hand that only paused mid-movement doesn't click
+        pub fn with_min_stability(mut self, min_stability: f32) -> Self {
+            self.min_stability = min_stability;
+            self
+        }
+
+        pub fn with_radius(mut self, radius: f32) -> Self {
+            self.radius = radius;
+            self
+        }
+
+        fn point(hand: &TrackedHand) -> (f32, f32) {
+            let point = hand
+                .details
+                .landmark(INDEX_TIP)
+                .unwrap_or_else(|| hand.details.pointer());
+            (point.x, point.y)
+        }
+
+        // Call once per frame with the tracked hands. Returns whether a click was sent.
+        pub fn update(
+            &mut self,
+            enigo: &mut Enigo,
+            tracked: &[TrackedHand],
+            now: Instant,
+        ) -> Result<bool, Error> {
+            // Keep following the same hand while it's seen, otherwise take the first one
+            let visible = |hand: &&TrackedHand| hand.missed == 0;
+            let hand = self
+                .anchor
+                .and_then(|(id, ..)| tracked.iter().filter(visible).find(|hand| hand.id == id))
+                .or_else(|| tracked.iter().find(visible));
+            let Some(hand) = hand else {
+                self.anchor = None;
+                return Ok(false);
+            };
+
+            let (x, y) = Self::point(hand);
+            match self.anchor {
+                Some((id, ax, ay, start))
+                    if id == hand.id
+                        && ((x - ax).powi(2) + (y - ay).powi(2)).sqrt() <= self.radius =>
+                {
+                    if !self.clicked
+                        && now.duration_since(start) >= self.dwell
+                        && hand.stability >= self.min_stability
+                    {
+                        enigo.button(Button::Left, Direction::Click)?;
+                        self.clicked = true;
+                        return Ok(true);
+                    }
+                }
+                _ => {
+                    self.anchor = Some((hand.id, x, y, now));
+                    self.clicked = false;
+                }
+            }
+            Ok(false)
+        }
+
+        // Where the current dwell is and how far along it is (0.0 to 1.0), for drawing.
+        // None when no hand is dwelling or it has already clicked.
+        pub fn progress(&self, now: Instant) -> Option<(f32, f32, f32)> {
+            if self.clicked {
+                return None;
+            }
+            let (_, x, y, start) = self.anchor?;
+            let fraction = now.duration_since(start).as_secs_f32() / self.dwell.as_secs_f32();
+            Some((x, y, fraction.clamp(0.0, 1.0)))
+        }
+    }
+
+    // Scroll by where the hand is held instead of by gesture: with the wrist above
+    // `up_zone` (normalized height, the top third by default) the page scrolls up, below
+    // `down_zone` it scrolls down, and in between (the dead zone) it rests. The further into
+    // a zone, the faster, up to `max_speed` lines per second at the frame edge.
+    pub struct PositionalScroller {
+        up_zone: f32,
+        down_zone: f32,
+        max_speed: f32,
+        // Scrolled distance not sent yet, since enigo scrolls in whole lines
+        pending: f32,
+        last_update: Option<Instant>,
+    }
+
+    impl PositionalScroller {
+        pub fn new(max_speed: f32) -> Self {
+            Self {
+                up_zone: 1.0 / 3.0,
+                down_zone: 2.0 / 3.0,
+                max_speed,
+                pending: 0.0,
+                last_update: None,
+            }
+        }
+
+        // Zone boundaries as normalized heights, with 0.0 <= up_zone <= down_zone <= 1.0
+        pub fn with_zones(mut self, up_zone: f32, down_zone: f32) -> Result<Self, Error> {
+            if !(0.0 <= up_zone && up_zone <= down_zone && down_zone <= 1.0) {
+                return Err(anyhow!(
+                    "Scroll zones need 0 <= up <= down <= 1, got {} and {}",
+                    up_zone,
+                    down_zone
+                ));
+            }
+            self.up_zone = up_zone;
+            self.down_zone = down_zone;
+            Ok(self)
+        }
+
+        // Scroll speed in lines per second for a wrist at height `y` (negative is up)
+        pub fn speed(&self, y: f32) -> f32 {
+            let depth = if y < self.up_zone {
+                -(self.up_zone - y) / self.up_zone.max(f32::EPSILON)
+            } else if y > self.down_zone {
+                (y - self.down_zone) / (1.0 - self.down_zone).max(f32::EPSILON)
+            } else {
+                0.0
+            };
+            depth.clamp(-1.0, 1.0) * self.max_speed
+        }
+
+        // Call once per frame with the tracked hands (the first visible one scrolls).
+        // Returns the lines scrolled, negative for up.
+        pub fn update(
+            &mut self,
+            enigo: &mut Enigo,
+            tracked: &[TrackedHand],
+            now: Instant,
+        ) -> Result<i32, Error> {
+            // Capped, so a pause between updates (e.g. control turned off) can't scroll
+            // a whole page at once
+            let elapsed = self
+                .last_update
+                .map_or(0.0, |last| now.duration_since(last).as_secs_f32())
+                .min(0.25);
+            self.last_update = Some(now);
+
+            let Some(hand) = tracked.iter().find(|hand| hand.missed == 0) else {
+                self.pending = 0.0;
+                return Ok(0);
+            };
+            let speed = self.speed(hand.details.wrist.y);
+            if speed == 0.0 {
+                self.pending = 0.0;
+                return Ok(0);
+            }
+
+            self.pending += speed * elapsed;
+            let lines = self.pending.trunc() as i32;
+            if lines != 0 {
+                enigo.scroll(lines, Axis::Vertical)?;
+                self.pending -= lines as f32;
+            }
+            Ok(lines)
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        fn start(id: u32, finger: Finger) -> GestureEvent {
+            GestureEvent::PinchStart {
+                id,
+                finger,
+                x: 0.5,
+                y: 0.5,
+            }
+        }
+
+        fn end(id: u32, finger: Finger) -> GestureEvent {
+            GestureEvent::PinchEnd { id, finger }
+        }
+
+        #[test]
+        fn pinch_clicks_are_debounced_by_the_cooldown() {
+            let mut clicker = PinchClicker::new();
+            let t0 = Instant::now();
+            let at = |millis: u64| t0 + Duration::from_millis(millis);
+
+            assert_eq!(
+                clicker.click_for(&start(1, Finger::Index), at(0)),
+                Some(Button::Left)
+            );
+            assert_eq!(clicker.click_for(&end(1, Finger::Index), at(50)), None);
+            // Released, but within 250 ms of the last click
+            assert_eq!(clicker.click_for(&start(1, Finger::Index), at(100)), None);
+            assert_eq!(clicker.click_for(&end(1, Finger::Index), at(150)), None);
+            assert_eq!(
+                clicker.click_for(&start(1, Finger::Index), at(250)),
+                Some(Button::Left)
+            );
+        }
+
+        #[test]
+        fn pinches_must_be_released_before_clicking_again() {
+            let mut clicker = PinchClicker::new();
+            let t0 = Instant::now();
+            let later = t0 + Duration::from_secs(1);
+
+            assert_eq!(
+                clicker.click_for(&start(1, Finger::Index), t0),
+                Some(Button::Left)
+            );
+            // A repeated start without an end, long after the cooldown
+            assert_eq!(clicker.click_for(&start(1, Finger::Index), later), None);
+
+            let mut clicker = PinchClicker::new().with_require_release(false);
+            assert_eq!(
+                clicker.click_for(&start(1, Finger::Index), t0),
+                Some(Button::Left)
+            );
+            assert_eq!(
+                clicker.click_for(&start(1, Finger::Index), later),
+                Some(Button::Left)
+            );
+        }
+
+        #[test]
+        fn fingers_click_independently() {
+            let mut clicker = PinchClicker::new();
+            let t0 = Instant::now();
+            let soon = t0 + Duration::from_millis(10);
+
+            assert_eq!(
+                clicker.click_for(&start(1, Finger::Index), t0),
+                Some(Button::Left)
+            );
+            // Neither the index finger's cooldown nor its held pinch hold the others back
+            assert_eq!(
+                clicker.click_for(&start(1, Finger::Middle), soon),
+                Some(Button::Right)
+            );
+            assert_eq!(
+                clicker.click_for(&start(1, Finger::Ring), soon),
+                Some(Button::Middle)
+            );
+            // Unmapped fingers never click
+            assert_eq!(clicker.click_for(&start(1, Finger::Pinky), soon), None);
+            // The cooldown is per button, so another hand's index pinch waits too
+            assert_eq!(clicker.click_for(&start(2, Finger::Index), soon), None);
+        }
+    }
+}
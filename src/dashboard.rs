@@ -0,0 +1,135 @@
+pub mod tui_dashboard {
+    use crate::metrics::frame_metrics::MetricsSnapshot;
+    use anyhow::Result;
+    use ratatui::crossterm::event::{self, Event, KeyCode, KeyEventKind};
+    use ratatui::layout::{Constraint, Layout};
+    use ratatui::widgets::{Block, Paragraph};
+    use ratatui::{DefaultTerminal, Frame};
+    use std::sync::mpsc::{Receiver, Sender, TryRecvError, channel};
+    use std::thread::JoinHandle;
+    use std::time::Duration;
+
+    // Size of the wrist position plot, in characters
+    const PLOT_WIDTH: usize = 48;
+    const PLOT_HEIGHT: usize = 16;
+
+    // One frame's worth of state for the dashboard
+    #[derive(Debug, Clone, Default)]
+    pub struct DashboardUpdate {
+        pub metrics: MetricsSnapshot,
+        // Normalized wrist positions of the tracked hands
+        pub wrists: Vec<(f32, f32)>,
+    }
+
+    // Terminal dashboard drawn on its own thread, fed from the detect loop over a channel.
+    // Closing it (q or Esc) makes `send` return false.
+    pub struct Dashboard {
+        updates: Option<Sender<DashboardUpdate>>,
+        handle: Option<JoinHandle<Result<()>>>,
+    }
+
+    impl Dashboard {
+        pub fn spawn() -> Self {
+            let (tx, rx) = channel::<DashboardUpdate>();
+            let handle = std::thread::spawn(move || {
+                let mut terminal = ratatui::init();
+                let result = run(&mut terminal, rx);
+                ratatui::restore();
+                result
+            });
+
+            Self {
+                updates: Some(tx),
+                handle: Some(handle),
+            }
+        }
+
+        // Returns false once the dashboard has been closed
+        pub fn send(&self, update: DashboardUpdate) -> bool {
+            self.updates
+                .as_ref()
+                .is_some_and(|tx| tx.send(update).is_ok())
+        }
+    }
+
+    impl Drop for Dashboard {
+        fn drop(&mut self) {
+            // Closing the channel stops the ui thread, which restores the terminal
+            drop(self.updates.take());
+            if let Some(handle) = self.handle.take()
+                && let Ok(Err(e)) = handle.join()
+            {
+                eprintln!("Dashboard error: {}", e);
+            }
+        }
+    }
+
+    fn run(terminal: &mut DefaultTerminal, updates: Receiver<DashboardUpdate>) -> Result<()> {
+        let mut latest = DashboardUpdate::default();
+        loop {
+            // Only the most recent update is drawn
+            loop {
+                match updates.try_recv() {
+                    Ok(update) => latest = update,
+                    Err(TryRecvError::Empty) => break,
+                    Err(TryRecvError::Disconnected) => return Ok(()),
+                }
+            }
+
+            terminal.draw(|frame| draw(frame, &latest))?;
+
+            if event::poll(Duration::from_millis(50))?
+                && let Event::Key(key) = event::read()?
+                && key.kind == KeyEventKind::Press
+                && matches!(key.code, KeyCode::Char('q') | KeyCode::Esc)
+            {
+                return Ok(());
+            }
+        }
+    }
+
+    // Coarse character grid with the hands' wrists marked by their index (1, 2, ...)
+    fn wrist_plot(wrists: &[(f32, f32)]) -> String {
+        let mut grid = vec![vec!['.'; PLOT_WIDTH]; PLOT_HEIGHT];
+        for (i, &(x, y)) in wrists.iter().enumerate() {
+            let col = ((x.clamp(0.0, 1.0) * (PLOT_WIDTH - 1) as f32).round()) as usize;
+            let row = ((y.clamp(0.0, 1.0) * (PLOT_HEIGHT - 1) as f32).round()) as usize;
+            grid[row][col] = char::from_digit((i as u32 + 1) % 10, 10).unwrap_or('o');
+        }
+
+        grid.into_iter()
+            .map(|row| row.into_iter().collect::<String>())
+            .collect::<Vec<String>>()
+            .join("\n")
+    }
+
+    fn draw(frame: &mut Frame, update: &DashboardUpdate) {
+        let [stats_area, plot_area] = Layout::vertical([
+            Constraint::Length(8),
+            Constraint::Length(PLOT_HEIGHT as u16 + 2),
+        ])
+        .areas(frame.area());
+
+        let metrics = &update.metrics;
+        let stats = format!(
+            "FPS: {:.1}\nInference: {:.1} ms\nFrames: {} ({} skipped)\nHands: {} (total {})\nGesture: {}\n(q to quit)",
+            metrics.fps,
+            metrics.inference_ms,
+            metrics.frames,
+            metrics.skipped_frames,
+            metrics.detections,
+            metrics.total_detections,
+            metrics.last_gesture.as_deref().unwrap_or("-")
+        );
+
+        frame.render_widget(
+            Paragraph::new(stats).block(Block::bordered().title("fingers")),
+            stats_area,
+        );
+        frame.render_widget(
+            Paragraph::new(wrist_plot(&update.wrists))
+                .block(Block::bordered().title("Wrist position")),
+            plot_area,
+        );
+    }
+}
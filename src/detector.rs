@@ -1,16 +1,280 @@
 pub mod hand_detector {
-    use anyhow::Result;
+    use crate::sensor::webcam::FrameSource;
+    use anyhow::{Result, anyhow};
     use image::{ImageBuffer, Rgb, imageops::FilterType};
-    use ndarray::{Array4, ArrayView, Ix3};
-    use ort::{inputs, session::Session, session::builder::GraphOptimizationLevel, value::Value};
+    use ndarray::{Array3, Array4, ArrayView, ArrayView3, Ix3};
+    use ort::ep::ExecutionProviderDispatch;
+    use ort::error::ErrorCode;
+    use ort::session::builder::{GraphOptimizationLevel, SessionBuilder};
+    use ort::{inputs, session::Session, session::SessionOutputs, value::Value};
+    use serde::Serialize;
+    use std::collections::HashMap;
+    use std::fmt;
     use std::path::Path;
+    use std::sync::Arc;
+    use std::time::{Duration, Instant};
 
     const INPUT_SIZE: f32 = 256.0;
     const NUM_ANCHORS: usize = 2944;
+    const NUM_KEYPOINTS: usize = 7;
+    // Default score threshold (raw model score, i.e. a logit: sigmoid(1.0) ~ 0.73),
+    // used to both start and keep a detection
+    const SCORE_THRESHOLD: f32 = 1.0;
+    // How much a candidate must overlap last frame's detection to count as the same region
+    const HYSTERESIS_IOU: f32 = 0.3;
+    // How far the adaptive threshold moves per frame
+    const ADAPTIVE_STEP: f32 = 0.05;
+
+    // Enter a `tracing` span until the end of the enclosing block (no-op without the
+    // `tracing` feature)
+    macro_rules! phase_span {
+        ($name:literal) => {
+            #[cfg(feature = "tracing")]
+            let _span = tracing::info_span!($name).entered();
+        };
+    }
+
+    // Palm keypoint indices (as regressed by the palm detector after the 4 bbox values)
+    pub const WRIST: usize = 0;
+    pub const INDEX_MCP: usize = 1;
+    pub const MIDDLE_MCP: usize = 2;
+    pub const RING_MCP: usize = 3;
+    pub const PINKY_MCP: usize = 4;
+    pub const THUMB_CMC: usize = 5;
+    pub const THUMB_MCP: usize = 6;
+
+    // Custom logic run on the detected hands at the end of `detect` (after NMS and the
+    // max_hands cut), e.g. extra smoothing or filtering. Called every frame, with an empty
+    // list when no hands were found; returning an empty list reports no hands.
+    pub trait PostProcessor {
+        fn process(&mut self, hands: Vec<HandDetails>) -> Vec<HandDetails>;
+    }
 
     pub struct HandDetector {
         session: Session,
+        session_config: SessionConfig,
+        // Name of the active session, and other loaded sessions that can be switched to
+        active_model: String,
+        models: HashMap<String, Session>,
         anchors: Vec<Anchor>,
+        // Divisors applied to raw regression values: INPUT_SIZE for exports that regress in
+        // input pixels, 1.0 for exports that already output normalized values. Offsets
+        // (box center, keypoints) and sizes (box width, height) can differ between exports.
+        offset_scale: f32,
+        size_scale: f32,
+        // Clamp returned boxes to the frame (regression noise can push them slightly outside)
+        clamp_boxes: bool,
+        preprocess_config: PreprocessConfig,
+        // Schmitt-trigger thresholds: a new detection needs score_on,
+        // one overlapping last frame's detections only needs score_off
+        score_on: f32,
+        score_off: f32,
+        activation: ActivationMode,
+        coord_reference: CoordReference,
+        output_order: OutputOrder,
+        active_boxes: Vec<Box>,
+        adaptive: Option<AdaptiveThreshold>,
+        max_hands: usize,
+        overflow_policy: OverflowPolicy,
+        // Hands dropped over max_hands in the last frame
+        overflow: usize,
+        // Candidates whose wrist or index knuckle keypoint is less visible are dropped before NMS
+        min_key_visibility: Option<f32>,
+        // Candidates whose box comes within this (normalized) margin of a frame edge are dropped
+        ignore_border: Option<f32>,
+        // Wrist and palm keypoints less visible than this are estimated from the box
+        box_fallback: Option<f32>,
+        nms_mode: NmsMode,
+        tiling: Option<Tiling>,
+        roi_tracking: Option<RoiTracking>,
+        // Frames since the last full scan with ROI tracking
+        frames_since_scan: u32,
+        // Number of cameras stitched side by side into each frame
+        stitched_cameras: Option<u32>,
+        // Normalized frame region hands are detected in (the rest of the frame is ignored)
+        active_zone: Option<Box>,
+        post_processor: Option<std::boxed::Box<dyn PostProcessor>>,
+        // Print a line for frames without hands
+        logging: bool,
+    }
+
+    // Moves the score threshold a step per frame to keep the number of detections
+    // in [min_detections, max_detections]: up when more hands survive NMS, down when fewer do.
+    // The threshold (score_on, score_off keeps its distance below it) stays in [min, max].
+    #[derive(Debug, Clone, Copy)]
+    pub struct AdaptiveThreshold {
+        pub min_detections: usize,
+        pub max_detections: usize,
+        pub min: f32,
+        pub max: f32,
+        threshold: f32,
+    }
+
+    impl AdaptiveThreshold {
+        fn update(&mut self, detections: usize) {
+            if detections > self.max_detections {
+                self.threshold += ADAPTIVE_STEP;
+            } else if detections < self.min_detections {
+                self.threshold -= ADAPTIVE_STEP;
+            }
+            self.threshold = self.threshold.clamp(self.min, self.max);
+        }
+    }
+
+    // Overlapping grid of tiles to run detection on, for small hands in wide scenes
+    #[derive(Debug, Clone, Copy)]
+    pub struct Tiling {
+        pub cols: u32,
+        pub rows: u32,
+        // Fraction of a tile shared with its neighbour (0.0 to below 1.0)
+        pub overlap: f32,
+    }
+
+    impl Tiling {
+        // Tile rectangles (x, y, width, height) in frame pixels
+        fn tiles(&self, frame_width: u32, frame_height: u32) -> Vec<(u32, u32, u32, u32)> {
+            let span = |size: u32, count: u32| -> (f32, f32) {
+                let tile = size as f32 / (count as f32 - (count - 1) as f32 * self.overlap);
+                (tile, tile * (1.0 - self.overlap))
+            };
+            let (tile_width, step_x) = span(frame_width, self.cols);
+            let (tile_height, step_y) = span(frame_height, self.rows);
+
+            let mut tiles = Vec::new();
+            for row in 0..self.rows {
+                for col in 0..self.cols {
+                    let x = ((col as f32 * step_x) as u32).min(frame_width - 1);
+                    let y = ((row as f32 * step_y) as u32).min(frame_height - 1);
+                    let width = (tile_width.ceil() as u32).clamp(1, frame_width - x);
+                    let height = (tile_height.ceil() as u32).clamp(1, frame_height - y);
+                    tiles.push((x, y, width, height));
+                }
+            }
+            tiles
+        }
+    }
+
+    // "Track then detect": after hands are found, only a region around them is scanned
+    #[derive(Debug, Clone, Copy)]
+    pub struct RoiTracking {
+        // Size of the region relative to the box around last frame's hands (2.0 is twice
+        // as wide and tall)
+        pub expansion: f32,
+        // Scan the full frame every this many frames, to pick up new hands
+        pub rescan_every: u32,
+    }
+
+    // Pixel rectangle (x, y, width, height) of a normalized zone, at least 1x1
+    fn zone_crop(zone: Box, frame_width: u32, frame_height: u32) -> (u32, u32, u32, u32) {
+        let x = ((zone.xmin * frame_width as f32) as u32).min(frame_width - 1);
+        let y = ((zone.ymin * frame_height as f32) as u32).min(frame_height - 1);
+        let width = ((zone.xmax * frame_width as f32) as u32)
+            .saturating_sub(x)
+            .clamp(1, frame_width - x);
+        let height = ((zone.ymax * frame_height as f32) as u32)
+            .saturating_sub(y)
+            .clamp(1, frame_height - y);
+        (x, y, width, height)
+    }
+
+    // Map a detection made on a tile (or zone crop) back to full-frame normalized coordinates
+    fn tile_to_frame(hand: &mut HandDetails, tile: (u32, u32, u32, u32), frame: (u32, u32)) {
+        let (tx, ty, tw, th) = tile;
+        let map_x = |x: f32| (tx as f32 + x * tw as f32) / frame.0 as f32;
+        let map_y = |y: f32| (ty as f32 + y * th as f32) / frame.1 as f32;
+
+        hand.bbox = Box {
+            xmin: map_x(hand.bbox.xmin),
+            ymin: map_y(hand.bbox.ymin),
+            xmax: map_x(hand.bbox.xmax),
+            ymax: map_y(hand.bbox.ymax),
+        };
+        for keypoint in hand.keypoints.iter_mut() {
+            keypoint.x = map_x(keypoint.x);
+            keypoint.y = map_y(keypoint.y);
+        }
+        hand.wrist = hand.keypoints[WRIST];
+    }
+
+    // Map candidates detected on a crop (of the active zone or the ROI) back to the frame,
+    // dropping those centered outside the zone if there is one
+    fn crop_to_frame(
+        hands: Vec<HandDetails>,
+        crop: (u32, u32, u32, u32),
+        frame: (u32, u32),
+        zone: Option<Box>,
+    ) -> Vec<HandDetails> {
+        hands
+            .into_iter()
+            .filter_map(|mut hand| {
+                tile_to_frame(&mut hand, crop, frame);
+                let (cx, cy) = hand.bbox.center();
+                zone.is_none_or(|zone| zone.contains(cx, cy))
+                    .then_some(hand)
+            })
+            .collect()
+    }
+
+    // How close (normalized to the frame width) to the seam between two stitched cameras a
+    // box edge has to be for the box to count as cut by the seam
+    const SEAM_MARGIN: f32 = 0.02;
+    // Share of the shorter box's height two boxes on either side of a seam must overlap by
+    // to be the same hand
+    const SEAM_MIN_OVERLAP: f32 = 0.5;
+
+    fn vertical_overlap(a: &Box, b: &Box) -> f32 {
+        let overlap = (a.ymax.min(b.ymax) - a.ymin.max(b.ymin)).max(0.0);
+        overlap / a.height().min(b.height()).max(f32::EPSILON)
+    }
+
+    // Merge detections of one hand cut in two by the seam at `seam_x` between cameras
+    // `seam` - 1 and `seam`: boxes touching the seam from either side that overlap
+    // vertically become one box around both, keeping the better half's score and keypoints
+    fn merge_across_seam(
+        mut hands: Vec<HandDetails>,
+        seam: usize,
+        seam_x: f32,
+    ) -> Vec<HandDetails> {
+        let touches = |hand: &HandDetails, camera: usize| {
+            hand.camera == Some(camera)
+                && if camera < seam {
+                    seam_x - hand.bbox.xmax <= SEAM_MARGIN
+                } else {
+                    hand.bbox.xmin - seam_x <= SEAM_MARGIN
+                }
+        };
+
+        let mut merged = Vec::with_capacity(hands.len());
+        while let Some(mut hand) = hands.pop() {
+            let other_side = if touches(&hand, seam - 1) {
+                Some(seam)
+            } else if touches(&hand, seam) {
+                Some(seam - 1)
+            } else {
+                None
+            };
+            let partner = other_side.and_then(|side| {
+                hands.iter().position(|other| {
+                    touches(other, side)
+                        && vertical_overlap(&hand.bbox, &other.bbox) >= SEAM_MIN_OVERLAP
+                })
+            });
+            if let Some(index) = partner {
+                let other = hands.swap_remove(index);
+                let bbox = Box {
+                    xmin: hand.bbox.xmin.min(other.bbox.xmin),
+                    ymin: hand.bbox.ymin.min(other.bbox.ymin),
+                    xmax: hand.bbox.xmax.max(other.bbox.xmax),
+                    ymax: hand.bbox.ymax.max(other.bbox.ymax),
+                };
+                if other.score > hand.score {
+                    hand = other;
+                }
+                hand.bbox = bbox;
+            }
+            merged.push(hand);
+        }
+        merged
     }
 
     struct Anchor {
@@ -20,7 +284,7 @@ pub mod hand_detector {
         h: f32,
     }
 
-    #[derive(Debug, Clone, Copy)]
+    #[derive(Debug, Clone, Copy, Serialize)]
     pub struct Box {
         pub xmin: f32,
         pub ymin: f32,
@@ -28,25 +292,313 @@ pub mod hand_detector {
         pub ymax: f32,
     }
 
-    #[derive(Debug, Clone, Copy)]
+    impl Box {
+        pub fn center(&self) -> (f32, f32) {
+            ((self.xmin + self.xmax) / 2.0, (self.ymin + self.ymax) / 2.0)
+        }
+
+        pub fn width(&self) -> f32 {
+            self.xmax - self.xmin
+        }
+
+        pub fn height(&self) -> f32 {
+            self.ymax - self.ymin
+        }
+
+        pub fn contains(&self, x: f32, y: f32) -> bool {
+            (self.xmin..=self.xmax).contains(&x) && (self.ymin..=self.ymax).contains(&y)
+        }
+
+        // Grown by `margin` (fraction of width/height) on every side
+        pub fn expanded(&self, margin: f32) -> Box {
+            let (dx, dy) = (self.width() * margin, self.height() * margin);
+            Box {
+                xmin: self.xmin - dx,
+                ymin: self.ymin - dy,
+                xmax: self.xmax + dx,
+                ymax: self.ymax + dy,
+            }
+        }
+
+        // Ordered (min <= max) and clamped to the 0.0 - 1.0 frame
+        pub fn clamped(&self) -> Box {
+            let [xmin, xmax] = [self.xmin.min(self.xmax), self.xmin.max(self.xmax)];
+            let [ymin, ymax] = [self.ymin.min(self.ymax), self.ymin.max(self.ymax)];
+            Box {
+                xmin: xmin.clamp(0.0, 1.0),
+                ymin: ymin.clamp(0.0, 1.0),
+                xmax: xmax.clamp(0.0, 1.0),
+                ymax: ymax.clamp(0.0, 1.0),
+            }
+        }
+    }
+
+    #[derive(Debug, Clone, Copy, Serialize)]
     pub struct Landmark {
         pub x: f32,
         pub y: f32,
+        // Relative depth (0.0 for the palm detector's keypoints, which are 2D only)
+        pub z: f32,
+        // How reliable the landmark is (0.0 - 1.0). Neither model regresses per-landmark
+        // visibility, so it is estimated from whether the landmark lies inside the hand region.
+        pub visibility: f32,
+        // Not regressed but guessed from the box geometry (see HandDetails::estimate_from_box)
+        #[serde(skip_serializing_if = "std::ops::Not::not")]
+        pub estimated: bool,
     }
 
-    #[derive(Debug, Clone, Copy)]
+    // Rectangle rotated with the hand, fitted around its landmarks. Like MediaPipe's
+    // NormalizedRect, the center is normalized to the frame, width (across the hand) is
+    // relative to the frame width and height (wrist to fingertips) to the frame height, and
+    // rotation is in radians: 0 when the fingers point up, positive turning clockwise.
+    #[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+    pub struct RotatedBox {
+        pub center_x: f32,
+        pub center_y: f32,
+        pub width: f32,
+        pub height: f32,
+        pub rotation: f32,
+    }
+
+    impl RotatedBox {
+        // Fit around the landmarks of a frame_width x frame_height frame, oriented along
+        // `up` (a direction in pixels, e.g. wrist -> middle finger MCP)
+        pub fn fit(
+            landmarks: &[Landmark],
+            up: (f32, f32),
+            frame_width: f32,
+            frame_height: f32,
+        ) -> Option<Self> {
+            let length = (up.0 * up.0 + up.1 * up.1).sqrt();
+            if landmarks.is_empty() || length <= f32::EPSILON {
+                return None;
+            }
+            let up = (up.0 / length, up.1 / length);
+            let right = (-up.1, up.0);
+
+            // Extents along both axes, in pixels
+            let [mut min_r, mut max_r, mut min_u, mut max_u] = [
+                f32::INFINITY,
+                f32::NEG_INFINITY,
+                f32::INFINITY,
+                f32::NEG_INFINITY,
+            ];
+            for landmark in landmarks {
+                let (x, y) = (landmark.x * frame_width, landmark.y * frame_height);
+                let along_right = x * right.0 + y * right.1;
+                let along_up = x * up.0 + y * up.1;
+                min_r = min_r.min(along_right);
+                max_r = max_r.max(along_right);
+                min_u = min_u.min(along_up);
+                max_u = max_u.max(along_up);
+            }
+            let (mid_r, mid_u) = ((min_r + max_r) / 2.0, (min_u + max_u) / 2.0);
+
+            Some(Self {
+                center_x: (right.0 * mid_r + up.0 * mid_u) / frame_width,
+                center_y: (right.1 * mid_r + up.1 * mid_u) / frame_height,
+                width: (max_r - min_r) / frame_width,
+                height: (max_u - min_u) / frame_height,
+                rotation: up.0.atan2(-up.1),
+            })
+        }
+
+        // Normalized corners (bottom left, bottom right, top right, top left in the hand's
+        // own orientation) for a frame of this size
+        pub fn corners(&self, frame_width: f32, frame_height: f32) -> [(f32, f32); 4] {
+            let up = (self.rotation.sin(), -self.rotation.cos());
+            let right = (-up.1, up.0);
+            let half_w = self.width * frame_width / 2.0;
+            let half_h = self.height * frame_height / 2.0;
+            let (cx, cy) = (self.center_x * frame_width, self.center_y * frame_height);
+            [(-1.0, -1.0), (1.0, -1.0), (1.0, 1.0), (-1.0, 1.0)].map(|(sr, su)| {
+                (
+                    (cx + sr * half_w * right.0 + su * half_h * up.0) / frame_width,
+                    (cy + sr * half_w * right.1 + su * half_h * up.1) / frame_height,
+                )
+            })
+        }
+    }
+
+    // Which hand a detection is, as classified by the hand landmarker. Like MediaPipe's,
+    // labels assume a mirrored (selfie) image, as the window shows it.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+    pub enum Handedness {
+        Left,
+        Right,
+    }
+
+    impl Handedness {
+        pub fn name(self) -> &'static str {
+            match self {
+                Handedness::Left => "Left",
+                Handedness::Right => "Right",
+            }
+        }
+    }
+
+    #[derive(Debug, Clone, Serialize)]
     pub struct HandDetails {
         pub score: f32,
         pub bbox: Box,
         pub wrist: Landmark,
+        // The 7 palm keypoints from the detector (see WRIST..THUMB_MCP)
+        pub keypoints: Vec<Landmark>,
+        // The 21 hand landmarks, filled in by the hand landmarker if it is run
+        pub landmarks: Option<Vec<Landmark>>,
+        // Which landmark indices `landmarks` holds, in order, when the landmarker was asked
+        // for a subset (None: all 21, indexed by landmark). Use `landmark` to look one up.
+        pub landmark_indices: Option<Vec<usize>>,
+        // Left or right hand and the confidence (0.5 - 1.0), filled in by the hand landmarker
+        pub handedness: Option<(Handedness, f32)>,
+        // Tighter, rotated box around the landmarks (for cropping), filled in with them
+        pub rotated_bbox: Option<RotatedBox>,
+        // Which camera of a stitched frame the hand was seen by (0 is the leftmost), see
+        // HandDetector::with_stitched_cameras
+        pub camera: Option<usize>,
+    }
+
+    impl HandDetails {
+        // A landmark by its hand_landmarker index, None if it wasn't computed
+        pub fn landmark(&self, index: usize) -> Option<Landmark> {
+            let landmarks = self.landmarks.as_ref()?;
+            match &self.landmark_indices {
+                None => landmarks.get(index).copied(),
+                Some(indices) => indices
+                    .iter()
+                    .position(|&i| i == index)
+                    .and_then(|position| landmarks.get(position).copied()),
+            }
+        }
+
+        // All 21 landmarks indexed by landmark, None if there are none or only a subset
+        pub fn full_landmarks(&self) -> Option<&[Landmark]> {
+            match self.landmark_indices {
+                None => self.landmarks.as_deref(),
+                Some(_) => None,
+            }
+        }
+
+        // Recommended control point for cursor movement.
+        // A weighted centroid of the index MCP, middle MCP and wrist keypoints (more stable
+        // than any single keypoint). Keypoints that stray far from the palm box are
+        // down-weighted, and low-confidence detections lean towards the box center.
+        pub fn pointer(&self) -> Landmark {
+            let (cx, cy) = self.bbox.center();
+            let half_diagonal = (self.bbox.width().powi(2) + self.bbox.height().powi(2))
+                .sqrt()
+                .max(f32::EPSILON)
+                / 2.0;
+
+            let mut sum_x = 0.0;
+            let mut sum_y = 0.0;
+            let mut sum_w = 0.0;
+            for (index, weight) in [(INDEX_MCP, 0.5), (MIDDLE_MCP, 0.2), (WRIST, 0.3)] {
+                let keypoint = self.keypoints[index];
+                let distance = ((keypoint.x - cx).powi(2) + (keypoint.y - cy).powi(2)).sqrt();
+                let consistency = 1.0 / (1.0 + (distance / half_diagonal).powi(2));
+                sum_x += keypoint.x * weight * consistency;
+                sum_y += keypoint.y * weight * consistency;
+                sum_w += weight * consistency;
+            }
+
+            // The raw score is a logit, map it to 0.0 - 1.0 before blending
+            let confidence = 1.0 / (1.0 + (-self.score).exp());
+            Landmark {
+                x: confidence * (sum_x / sum_w) + (1.0 - confidence) * cx,
+                y: confidence * (sum_y / sum_w) + (1.0 - confidence) * cy,
+                z: 0.0,
+                visibility: confidence,
+                estimated: false,
+            }
+        }
+
+        // Replace the wrist and middle knuckle keypoints with guesses from the box when
+        // their visibility is below `min_visibility`: the bottom center of the box for the
+        // wrist and its center for the knuckle (palm center), assuming an upright hand.
+        // They are flagged `estimated`, keeping cursor control steady through brief
+        // keypoint dropouts instead of jumping.
+        pub fn estimate_from_box(&mut self, min_visibility: f32) {
+            let (cx, cy) = self.bbox.center();
+            for (index, (x, y)) in [(WRIST, (cx, self.bbox.ymax)), (MIDDLE_MCP, (cx, cy))] {
+                let Some(keypoint) = self.keypoints.get_mut(index) else {
+                    continue;
+                };
+                if keypoint.visibility < min_visibility {
+                    (keypoint.x, keypoint.y, keypoint.z) = (x, y, 0.0);
+                    keypoint.estimated = true;
+                }
+            }
+            if let Some(&wrist) = self.keypoints.get(WRIST) {
+                self.wrist = wrist;
+            }
+        }
+
+        // The same detection in pixels of a width x height frame
+        pub fn to_pixels(&self, width: u32, height: u32) -> HandDetailsPx {
+            let (w, h) = (width as f32, height as f32);
+            let point = |l: &Landmark| PointPx {
+                x: (l.x * w).round() as i32,
+                y: (l.y * h).round() as i32,
+                visibility: l.visibility,
+            };
+            HandDetailsPx {
+                score: self.score,
+                bbox: BoxPx {
+                    xmin: (self.bbox.xmin * w).round() as i32,
+                    ymin: (self.bbox.ymin * h).round() as i32,
+                    xmax: (self.bbox.xmax * w).round() as i32,
+                    ymax: (self.bbox.ymax * h).round() as i32,
+                },
+                wrist: point(&self.wrist),
+                keypoints: self.keypoints.iter().map(point).collect(),
+                landmarks: self
+                    .landmarks
+                    .as_ref()
+                    .map(|landmarks| landmarks.iter().map(point).collect()),
+                landmark_indices: self.landmark_indices.clone(),
+                handedness: self.handedness,
+            }
+        }
+    }
+
+    // Pixel coordinates in the original frame (top left origin), rounded to whole pixels
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+    pub struct BoxPx {
+        pub xmin: i32,
+        pub ymin: i32,
+        pub xmax: i32,
+        pub ymax: i32,
+    }
+
+    #[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+    pub struct PointPx {
+        pub x: i32,
+        pub y: i32,
+        pub visibility: f32,
+    }
+
+    // HandDetails in the pixel space of the frame it was detected in (see detect_pixels).
+    // Depth isn't included, it has no pixel equivalent.
+    #[derive(Debug, Clone, Serialize)]
+    pub struct HandDetailsPx {
+        pub score: f32,
+        pub bbox: BoxPx,
+        pub wrist: PointPx,
+        pub keypoints: Vec<PointPx>,
+        pub landmarks: Option<Vec<PointPx>>,
+        pub landmark_indices: Option<Vec<usize>>,
+        pub handedness: Option<(Handedness, f32)>,
     }
 
-    fn generate_anchors(num_anchors: usize) -> Vec<Anchor> {
+    // SSD anchors of the 256x256 palm model: 2 per cell on the 32x32 and 16x16 maps,
+    // 6 per cell on the 8x8 map (three stride-32 layers with 2 each, merged per cell)
+    fn generate_anchors(num_anchors: usize) -> Result<Vec<Anchor>> {
         let mut anchors = Vec::with_capacity(num_anchors);
 
-        let strides = [8, 16, 32, 32];
-        let map_sizes = [32, 16, 8, 8];
-        let anchors_per_cell = [2, 2, 2, 6];
+        let strides = [8, 16, 32];
+        let map_sizes = [32, 16, 8];
+        let anchors_per_cell = [2, 2, 6];
 
         for i in 0..strides.len() {
             let stride = strides[i] as f32;
@@ -66,13 +618,77 @@ pub mod hand_detector {
                 }
             }
         }
-        anchors
+
+        // The layout decides the count, make sure it's what the model outputs
+        if anchors.len() != num_anchors {
+            return Err(anyhow!(
+                "Anchor layout produces {} anchors, expected {}",
+                anchors.len(),
+                num_anchors
+            ));
+        }
+        Ok(anchors)
+    }
+
+    // Anchors from a text file with one "x_center,y_center,w,h" line per anchor (commas or
+    // whitespace between the numbers, normalized to the model input), in the order of the
+    // model's outputs. Blank lines, # comments and a header as the first other line are
+    // skipped.
+    fn load_anchors(path: &Path) -> Result<Vec<Anchor>> {
+        let text = std::fs::read_to_string(path)
+            .map_err(|e| anyhow!("Failed to read anchors from {}: {}", path.display(), e))?;
+
+        let mut anchors = Vec::new();
+        let mut first_line = true;
+        for (i, line) in text.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let header_allowed = std::mem::replace(&mut first_line, false);
+            let values: Vec<&str> = line
+                .split(|c: char| c == ',' || c.is_whitespace())
+                .filter(|value| !value.is_empty())
+                .collect();
+            let parsed: Option<Vec<f32>> = values.iter().map(|value| value.parse().ok()).collect();
+            match parsed {
+                Some(values) if values.len() == 4 => anchors.push(Anchor {
+                    x_center: values[0],
+                    y_center: values[1],
+                    w: values[2],
+                    h: values[3],
+                }),
+                // A header naming the columns
+                None if header_allowed => continue,
+                _ => {
+                    return Err(anyhow!(
+                        "{} line {}: expected x_center,y_center,w,h, got '{}'",
+                        path.display(),
+                        i + 1,
+                        line
+                    ));
+                }
+            }
+        }
+        Ok(anchors)
+    }
+
+    // Anchor count of the model's score output, None when the dimension is dynamic
+    fn output_anchor_count(session: &Session) -> Option<usize> {
+        let output = session
+            .outputs()
+            .iter()
+            .find(|output| output.name() == "box_scores")?;
+        let shape = output.dtype().tensor_shape()?;
+        shape.get(1).and_then(|&count| usize::try_from(count).ok())
     }
 
     fn get_bbox(
         best_score_idx: usize,
         coords: &ndarray::ArrayView3<f32>,
         anchors: &[Anchor],
+        offset_scale: f32,
+        size_scale: f32,
     ) -> Box {
         // Extract the raw regression values
         let dx = coords[[0, best_score_idx, 0]];
@@ -85,10 +701,10 @@ pub mod hand_detector {
 
         // Apply the MediaPipe Scale (Standard is 256.0 for 256x256 input)
         // This transforms the raw offsets into normalized coordinates (0.0 to 1.0)
-        let center_x = (dx / INPUT_SIZE) * anchor.w + anchor.x_center;
-        let center_y = (dy / INPUT_SIZE) * anchor.h + anchor.y_center;
-        let w = (dw / INPUT_SIZE) * anchor.w;
-        let h = (dh / INPUT_SIZE) * anchor.h;
+        let center_x = (dx / offset_scale) * anchor.w + anchor.x_center;
+        let center_y = (dy / offset_scale) * anchor.h + anchor.y_center;
+        let w = (dw / size_scale) * anchor.w;
+        let h = (dh / size_scale) * anchor.h;
 
         // Return as a Bounding Box (top-left and bottom-right)
         Box {
@@ -105,6 +721,7 @@ pub mod hand_detector {
         anchors: &[Anchor],
         coords_x_idx: usize,
         coords_y_idx: usize,
+        offset_scale: f32,
     ) -> Landmark {
         // Extract the raw regression values
         let x = coords[[0, best_score_idx, coords_x_idx]];
@@ -115,16 +732,94 @@ pub mod hand_detector {
 
         // Apply the MediaPipe Scale (Standard is 256.0 for 256x256 input)
         // This transforms the raw offsets into normalized coordinates (0.0 to 1.0)
-        let landmark_x = (x / INPUT_SIZE) * anchor.w + anchor.x_center;
-        let landmark_y = (y / INPUT_SIZE) * anchor.h + anchor.y_center;
+        let landmark_x = (x / offset_scale) * anchor.w + anchor.x_center;
+        let landmark_y = (y / offset_scale) * anchor.h + anchor.y_center;
 
         Landmark {
             x: landmark_x,
             y: landmark_y,
+            z: 0.0,
+            visibility: 1.0,
+            estimated: false,
+        }
+    }
+
+    // The settings `candidates` decodes the model outputs with, borrowed from the detector
+    struct Decoding<'a> {
+        anchors: &'a [Anchor],
+        offset_scale: f32,
+        size_scale: f32,
+        activation: ActivationMode,
+        coord_reference: CoordReference,
+        score_off: f32,
+    }
+
+    impl Decoding<'_> {
+        // Turn the raw outputs of one inference into candidates above score_off
+        fn decode(
+            &self,
+            scores: &ArrayView3<f32>,
+            coords: &ArrayView3<f32>,
+            letterbox: LetterboxTransform,
+        ) -> Vec<HandDetails> {
+            let num_anchors = scores.shape()[1];
+
+            let mut candidates = Vec::new();
+            let to_output = |x: f32, y: f32| match self.coord_reference {
+                CoordReference::Frame => letterbox.to_frame(x, y),
+                CoordReference::ModelCanvas => (x, y),
+            };
+
+            for i in 0..num_anchors {
+                let score = self.activation.apply(scores[[0, i, 0]]);
+                if score > self.score_off {
+                    // Regressed coordinates are in model input space, remove the letterbox bars
+                    // unless they are wanted relative to the canvas
+                    let mut bbox =
+                        get_bbox(i, coords, self.anchors, self.offset_scale, self.size_scale);
+                    (bbox.xmin, bbox.ymin) = to_output(bbox.xmin, bbox.ymin);
+                    (bbox.xmax, bbox.ymax) = to_output(bbox.xmax, bbox.ymax);
+
+                    // Keypoints follow the 4 bbox values as (x, y) pairs.
+                    // Keypoints regressed well outside the palm box are considered unreliable.
+                    let palm_region = bbox.expanded(0.25);
+                    let keypoints: Vec<Landmark> = (0..NUM_KEYPOINTS)
+                        .map(|k| {
+                            let mut keypoint = get_landmark(
+                                i,
+                                coords,
+                                self.anchors,
+                                4 + 2 * k,
+                                5 + 2 * k,
+                                self.offset_scale,
+                            );
+                            (keypoint.x, keypoint.y) = to_output(keypoint.x, keypoint.y);
+                            if !palm_region.contains(keypoint.x, keypoint.y) {
+                                keypoint.visibility = 0.0;
+                            }
+                            keypoint
+                        })
+                        .collect();
+
+                    candidates.push(HandDetails {
+                        score,
+                        bbox,
+                        wrist: keypoints[WRIST],
+                        keypoints,
+                        landmarks: None,
+                        landmark_indices: None,
+                        handedness: None,
+                        rotated_bbox: None,
+                        camera: None,
+                    });
+                }
+            }
+
+            candidates
         }
     }
 
-    fn intersection_over_union(box_a: &Box, box_b: &Box) -> f32 {
+    pub fn intersection_over_union(box_a: &Box, box_b: &Box) -> f32 {
         let xmin = box_a.xmin.max(box_b.xmin);
         let ymin = box_a.ymin.max(box_b.ymin);
         let xmax = box_a.xmax.min(box_b.xmax);
@@ -138,7 +833,49 @@ pub mod hand_detector {
         intersection_area / (area_a + area_b - intersection_area)
     }
 
-    fn apply_nms(mut candidates: Vec<HandDetails>, iou_threshold: f32) -> Vec<HandDetails> {
+    // Which candidates non-maximum suppression lets suppress each other.
+    // ClassAgnostic: any box overlapping a better one is dropped.
+    // ClassAware: only boxes of the same handedness suppress each other, so a left and a right
+    //   hand that overlap (e.g. clasped hands) both survive. Candidates without handedness
+    //   count as a class of their own.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+    pub enum NmsMode {
+        #[default]
+        ClassAgnostic,
+        ClassAware,
+    }
+
+    impl NmsMode {
+        pub fn from_name(name: &str) -> Option<Self> {
+            match name {
+                "class-agnostic" => Some(NmsMode::ClassAgnostic),
+                "class-aware" => Some(NmsMode::ClassAware),
+                _ => None,
+            }
+        }
+
+        fn may_suppress(self, a: &HandDetails, b: &HandDetails) -> bool {
+            match self {
+                NmsMode::ClassAgnostic => true,
+                NmsMode::ClassAware => {
+                    a.handedness.map(|(hand, _)| hand) == b.handedness.map(|(hand, _)| hand)
+                }
+            }
+        }
+    }
+
+    // Overlap above which NMS treats two detections as the same hand
+    const NMS_IOU_THRESHOLD: f32 = 0.3;
+    // With ClassAware, palm candidates (no handedness yet) are only merged above this
+    // overlap, so overlapping hands both reach the landmarker; HandDetector::nms_by_handedness
+    // then merges what the landmarker says is the same hand
+    const CLASS_AWARE_PALM_IOU_THRESHOLD: f32 = 0.6;
+
+    pub fn apply_nms(
+        mut candidates: Vec<HandDetails>,
+        iou_threshold: f32,
+        mode: NmsMode,
+    ) -> Vec<HandDetails> {
         // Sort scores by descending order
         candidates.sort_by(|a, b| b.score.partial_cmp(&a.score).unwrap());
 
@@ -147,135 +884,2448 @@ pub mod hand_detector {
             let best = candidates.remove(0);
 
             // Remove all other boxes that overlap too much with the 'best' box
-            candidates
-                .retain(|item| intersection_over_union(&best.bbox, &item.bbox) < iou_threshold);
+            candidates.retain(|item| {
+                !mode.may_suppress(&best, item)
+                    || intersection_over_union(&best.bbox, &item.bbox) < iou_threshold
+            });
 
             selected.push(best);
         }
         selected
     }
 
-    impl HandDetector {
-        pub fn new<P: AsRef<Path>>(model_path: P) -> Result<Self> {
-            // Create new session for model
-            let session = Session::builder()?
-                .with_optimization_level(GraphOptimizationLevel::Level3)?
-                .with_intra_threads(4)?
-                .commit_from_file(model_path)?;
+    // A frame converted to the model's input tensor, plus the letterbox geometry
+    // needed to map detections back onto the frame
+    pub struct Preprocessed {
+        pub input: Array4<f32>,
+        pub letterbox: LetterboxTransform,
+    }
 
-            // Generate array of all anchors
-            let anchors = generate_anchors(NUM_ANCHORS);
+    // Maps between normalized frame coordinates and normalized model input coordinates
+    // of a letterboxed frame (scaled to fit, centered between black bars).
+    // Scale is the fraction of the model input taken by the frame content,
+    // padding the fraction taken by the bar before it (left or top), per axis.
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    pub struct LetterboxTransform {
+        pub scale_x: f32,
+        pub scale_y: f32,
+        pub padding_x: f32,
+        pub padding_y: f32,
+    }
 
-            Ok(Self { session, anchors })
+    impl LetterboxTransform {
+        // The transform `preprocess` uses for a frame of this size on a square model input
+        pub fn fit(frame_width: u32, frame_height: u32, target_size: u32) -> Self {
+            let layout = letterbox_layout(frame_width, frame_height, target_size);
+            let target = target_size as f32;
+            Self {
+                scale_x: layout.width as f32 / target,
+                scale_y: layout.height as f32 / target,
+                padding_x: layout.left as f32 / target,
+                padding_y: layout.top as f32 / target,
+            }
         }
 
-        pub fn new_embedded(model_bytes: &[u8]) -> Result<Self> {
-            // Create new session for model
-            let session = Session::builder()?
-                .with_optimization_level(GraphOptimizationLevel::Level3)?
-                .with_intra_threads(4)?
-                .commit_from_memory(model_bytes)?;
+        pub fn to_model(self, x: f32, y: f32) -> (f32, f32) {
+            (
+                x * self.scale_x + self.padding_x,
+                y * self.scale_y + self.padding_y,
+            )
+        }
 
-            // Generate array of all anchors
-            let anchors = generate_anchors(NUM_ANCHORS);
+        pub fn to_frame(self, x: f32, y: f32) -> (f32, f32) {
+            (
+                (x - self.padding_x) / self.scale_x,
+                (y - self.padding_y) / self.scale_y,
+            )
+        }
+    }
 
-            Ok(Self { session, anchors })
+    // Size and offset of the resized frame inside the model input, in pixels
+    struct LetterboxLayout {
+        width: u32,
+        height: u32,
+        left: u32,
+        top: u32,
+    }
+
+    fn letterbox_layout(frame_width: u32, frame_height: u32, target_size: u32) -> LetterboxLayout {
+        // Fit the longer side, bars go on the shorter one (y for landscape, x for portrait frames)
+        let scale = target_size as f32 / frame_width.max(frame_height).max(1) as f32;
+        let width = ((frame_width as f32 * scale) as u32).clamp(1, target_size);
+        let height = ((frame_height as f32 * scale) as u32).clamp(1, target_size);
+        LetterboxLayout {
+            width,
+            height,
+            left: (target_size - width) / 2,
+            top: (target_size - height) / 2,
         }
+    }
 
-        pub fn detect(
-            &mut self,
-            frame: &ImageBuffer<Rgb<u8>, Vec<u8>>,
-        ) -> Result<Option<Vec<HandDetails>>> {
-            // Preprocessing: Resize the image to what the model expects
-            // (letterbox image to fix aspect ratio without cropping or stretching)
+    // Per-pixel mean of the (letterboxed) model input, for models trained by subtracting a
+    // full mean image rather than per-channel means. INPUT_SIZE x INPUT_SIZE RGB values in
+    // 0.0-1.0 pixel units, row-major with the channels interleaved.
+    #[derive(Debug, Clone, PartialEq)]
+    pub struct MeanImage {
+        values: Vec<f32>,
+    }
 
-            let target_size = INPUT_SIZE as u32;
+    impl MeanImage {
+        pub fn new(width: u32, height: u32, values: Vec<f32>) -> Result<Self> {
+            let size = INPUT_SIZE as u32;
+            if width != size || height != size {
+                return Err(anyhow!(
+                    "Mean image must match the {}x{} model input, got {}x{}",
+                    size,
+                    size,
+                    width,
+                    height
+                ));
+            }
+            if values.len() != (size * size * 3) as usize {
+                return Err(anyhow!(
+                    "Mean image needs {} values ({}x{} RGB), got {}",
+                    size * size * 3,
+                    size,
+                    size,
+                    values.len()
+                ));
+            }
+            Ok(Self { values })
+        }
 
-            let mut canvas = ImageBuffer::from_pixel(target_size, target_size, Rgb([0, 0, 0]));
+        // Read the mean image from an 8-bit image file (e.g. PNG), scaled to 0.0-1.0
+        pub fn load<P: AsRef<Path>>(path: P) -> Result<Self> {
+            let image = image::open(path.as_ref())?.to_rgb8();
+            let (width, height) = image.dimensions();
+            let values = image
+                .into_raw()
+                .into_iter()
+                .map(|v| v as f32 / 255.0)
+                .collect();
+            Self::new(width, height, values)
+        }
 
-            let (frame_width, frame_height) = frame.dimensions();
-            let scale = target_size as f32 / frame_width as f32;
-            let new_height = (frame_height as f32 * scale) as u32;
+        fn at(&self, x: usize, y: usize, c: usize) -> f32 {
+            self.values[(y * INPUT_SIZE as usize + x) * 3 + c]
+        }
+    }
 
-            let resized_frame =
-                image::imageops::resize(frame, target_size, new_height, FilterType::Triangle);
+    // Settings for turning a frame into the model's input tensor
+    #[derive(Debug, Clone)]
+    pub struct PreprocessConfig {
+        // Per-channel (R, G, B) normalization applied after scaling pixels to 0.0-1.0:
+        // value = (pixel / 255 - mean_image - mean) / std
+        pub mean: [f32; 3],
+        pub std: [f32; 3],
+        // Optional per-pixel mean subtracted before the per-channel mean
+        pub mean_image: Option<Arc<MeanImage>>,
+        pub layout: InputLayout,
+        // Filter for scaling the frame down to the model input: Nearest is fastest,
+        // Triangle (the default) keeps fine detail without much cost, CatmullRom and
+        // Lanczos3 are sharper but slower
+        pub filter: FilterType,
+    }
 
-            let top_padding = (target_size - new_height) / 2;
-            image::imageops::overlay(&mut canvas, &resized_frame, 0, top_padding as i64);
+    impl Default for PreprocessConfig {
+        fn default() -> Self {
+            Self {
+                mean: [0.0; 3],
+                std: [1.0; 3],
+                mean_image: None,
+                layout: InputLayout::default(),
+                filter: FilterType::Triangle,
+            }
+        }
+    }
 
-            // Create closure to normalize the y coordinates to the original frame aspect ratio (since only black bars added to y-dimension)
-            let normalized_padding = top_padding as f32 / target_size as f32;
-            let normalized_content_height = new_height as f32 / target_size as f32;
+    // Resize filters worth choosing between, by name (as used on the command line)
+    pub const RESIZE_FILTERS: [(&str, FilterType); 4] = [
+        ("nearest", FilterType::Nearest),
+        ("triangle", FilterType::Triangle),
+        ("catmull-rom", FilterType::CatmullRom),
+        ("lanczos3", FilterType::Lanczos3),
+    ];
 
-            let norm_y = |y: f32| -> f32 { (y - normalized_padding) / normalized_content_height };
+    pub fn resize_filter_from_name(name: &str) -> Option<FilterType> {
+        RESIZE_FILTERS
+            .iter()
+            .find(|(filter_name, _)| *filter_name == name)
+            .map(|&(_, filter)| filter)
+    }
 
-            // Convert Image to Tensor [1, 3, 256, 256]
-            // We normalize pixels from 0-255 (u8) to 0.0-1.0 (f32)
-            let mut input =
-                Array4::<f32>::zeros((1, 3, target_size as usize, target_size as usize));
+    // Axis order of the model input tensor: [1, 3, H, W] (PyTorch/MediaPipe exports)
+    // or [1, H, W, 3] (TensorFlow exports)
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+    pub enum InputLayout {
+        #[default]
+        Nchw,
+        Nhwc,
+    }
 
-            for (x, y, rgb) in canvas.enumerate_pixels() {
-                input[[0, 0, y as usize, x as usize]] = rgb[0] as f32 / 255.0; // R
-                input[[0, 1, y as usize, x as usize]] = rgb[1] as f32 / 255.0; // G
-                input[[0, 2, y as usize, x as usize]] = rgb[2] as f32 / 255.0; // B
+    impl InputLayout {
+        // Guess the layout from a model's input shape (None if it isn't clear, e.g. 3x3 images)
+        pub fn from_shape(shape: &[i64]) -> Option<Self> {
+            match shape {
+                [_, 3, _, last] if *last != 3 => Some(InputLayout::Nchw),
+                [_, second, _, 3] if *second != 3 => Some(InputLayout::Nhwc),
+                _ => None,
             }
+        }
 
-            let input_tensor = Value::from_array(input)?;
+        fn shape(self, size: usize) -> [usize; 4] {
+            match self {
+                InputLayout::Nchw => [1, 3, size, size],
+                InputLayout::Nhwc => [1, size, size, 3],
+            }
+        }
+    }
 
-            // Run Inference
-            let outputs = self.session.run(inputs!["image" => input_tensor])?;
+    // What `detect` does when more than max_hands hands survive NMS:
+    // Truncate: keep the best scoring max_hands.
+    // Error: fail the detect call (the state is still updated with the kept hands).
+    // Flag: keep the best max_hands like Truncate, and report that hands were dropped
+    //   through `HandDetector::truncated` (or `detect_flagged`), e.g. to ask the user to
+    //   show fewer hands.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+    pub enum OverflowPolicy {
+        #[default]
+        Truncate,
+        Error,
+        Flag,
+    }
 
-            // Output processing
-            // Extract both output tensors
-            let (scores_shape, scores_data) = outputs["box_scores"].try_extract_tensor::<f32>()?;
-            let (coords_shape, coords_data) = outputs["box_coords"].try_extract_tensor::<f32>()?;
+    impl OverflowPolicy {
+        pub fn from_name(name: &str) -> Option<Self> {
+            match name {
+                "truncate" => Some(OverflowPolicy::Truncate),
+                "error" => Some(OverflowPolicy::Error),
+                "flag" => Some(OverflowPolicy::Flag),
+                _ => None,
+            }
+        }
+    }
 
-            let scores_shape_usize: Vec<usize> = scores_shape.iter().map(|&x| x as usize).collect();
-            let coords_shape_usize: Vec<usize> = coords_shape.iter().map(|&x| x as usize).collect();
+    // A frame's hands, and whether more than max_hands were found (see OverflowPolicy::Flag)
+    #[derive(Debug, Clone, Default)]
+    pub struct FlaggedHands {
+        pub hands: Vec<HandDetails>,
+        pub truncated: bool,
+    }
 
-            let scores = ArrayView::from_shape(scores_shape_usize, scores_data)?
-                .into_dimensionality::<Ix3>()?;
-            let coords = ArrayView::from_shape(coords_shape_usize, coords_data)?
-                .into_dimensionality::<Ix3>()?;
+    // Order of the hands `detect` returns (the hands kept are the same either way):
+    // Score: best score first, as NMS leaves them.
+    // LeftToRight: by box center x, so two hands keep their positions in the Vec when
+    //   their scores swap from frame to frame.
+    // Handedness: left hands, then right hands, then hands without handedness, each left
+    //   to right. Handedness comes from the landmarker or a post-processor, so call
+    //   `apply` again after refining with the landmarker.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+    pub enum OutputOrder {
+        #[default]
+        Score,
+        LeftToRight,
+        Handedness,
+    }
 
-            let num_anchors = scores.shape()[1];
+    impl OutputOrder {
+        pub fn from_name(name: &str) -> Option<Self> {
+            match name {
+                "score" => Some(OutputOrder::Score),
+                "left-to-right" => Some(OutputOrder::LeftToRight),
+                "handedness" => Some(OutputOrder::Handedness),
+                _ => None,
+            }
+        }
 
-            let mut candidates = Vec::new();
-            let score_threshold = 1.0 as f32;
-            let nms_iou_threshold = 0.3;
+        pub fn apply(self, hands: &mut [HandDetails]) {
+            let center_x = |hand: &HandDetails| hand.bbox.center().0;
+            match self {
+                OutputOrder::Score => hands.sort_by(|a, b| b.score.total_cmp(&a.score)),
+                OutputOrder::LeftToRight => {
+                    hands.sort_by(|a, b| center_x(a).total_cmp(&center_x(b)))
+                }
+                OutputOrder::Handedness => {
+                    let rank = |hand: &HandDetails| match hand.handedness {
+                        Some((Handedness::Left, _)) => 0,
+                        Some((Handedness::Right, _)) => 1,
+                        None => 2,
+                    };
+                    hands.sort_by(|a, b| {
+                        rank(a)
+                            .cmp(&rank(b))
+                            .then(center_x(a).total_cmp(&center_x(b)))
+                    })
+                }
+            }
+        }
+    }
 
-            for i in 0..num_anchors {
-                let score = scores[[0, i, 0]];
-                if score > score_threshold {
-                    let mut bbox = get_bbox(i, &coords, &self.anchors);
-                    let mut wrist = get_landmark(i, &coords, &self.anchors, 4, 5);
+    // What normalized coordinates (bbox, keypoints including the wrist) are relative to:
+    // the frame passed to `detect` with the letterbox undone (Frame), or the square
+    // INPUT_SIZE model canvas including the letterbox bars (ModelCanvas), as the model
+    // regresses them. Landmark fields (landmarks, handedness) added afterwards are
+    // untouched, and the landmarker and drawing expect Frame.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+    pub enum CoordReference {
+        #[default]
+        Frame,
+        ModelCanvas,
+    }
+
+    impl CoordReference {
+        pub fn from_name(name: &str) -> Option<Self> {
+            match name {
+                "frame" => Some(CoordReference::Frame),
+                "canvas" => Some(CoordReference::ModelCanvas),
+                _ => None,
+            }
+        }
+    }
 
-                    [bbox.ymin, bbox.ymax, wrist.y] =
-                        [bbox.ymin, bbox.ymax, wrist.y].map(|y| norm_y(y));
+    // How the model's box scores are turned into the reported score before thresholding.
+    // The MediaPipe palm detector outputs logits (unbounded, negative for most anchors), which
+    // `Raw` compares directly; `Sigmoid` maps them to probabilities so thresholds are in [0, 1].
+    // To tell what a model produces, look at `detect_raw` scores: values outside [0, 1] are
+    // logits, a model that already applies a sigmoid only outputs values in [0, 1].
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+    pub enum ActivationMode {
+        #[default]
+        Raw,
+        Sigmoid,
+    }
 
-                    candidates.push(HandDetails {
-                        score: score,
-                        bbox: bbox,
-                        wrist: wrist,
-                    });
+    impl ActivationMode {
+        pub fn from_name(name: &str) -> Option<Self> {
+            match name {
+                "raw" => Some(ActivationMode::Raw),
+                "sigmoid" => Some(ActivationMode::Sigmoid),
+                _ => None,
+            }
+        }
+
+        pub fn apply(self, score: f32) -> f32 {
+            match self {
+                ActivationMode::Raw => score,
+                ActivationMode::Sigmoid => 1.0 / (1.0 + (-score).exp()),
+            }
+        }
+
+        // Inverse of apply (thresholds outside (0, 1) have no logit and are kept as is)
+        fn invert(self, score: f32) -> f32 {
+            match self {
+                ActivationMode::Sigmoid if score > 0.0 && score < 1.0 => {
+                    (score / (1.0 - score)).ln()
                 }
+                _ => score,
             }
+        }
+    }
 
-            // Get best candidates based on Non-Maximum Suppression
-            let filtered_hands = apply_nms(candidates, nms_iou_threshold);
+    // Frames without pixels can't be letterboxed (the scale would divide by zero)
+    fn check_frame(frame: &ImageBuffer<Rgb<u8>, Vec<u8>>) -> Result<()> {
+        let (width, height) = frame.dimensions();
+        if width == 0 || height == 0 {
+            return Err(anyhow!(
+                "Can't detect on an empty {}x{} frame",
+                width,
+                height
+            ));
+        }
+        Ok(())
+    }
 
-            if filtered_hands.is_empty() {
-                println!("No hands detected");
-                Ok(None)
-            } else {
-                // Return top 2 hands detected
-                Ok(Some(
-                    filtered_hands
-                        .into_iter()
-                        .take(2)
-                        .collect::<Vec<HandDetails>>(),
-                ))
+    // Pure CPU work with no detector state, so it can run on any thread
+    pub fn preprocess(
+        frame: &ImageBuffer<Rgb<u8>, Vec<u8>>,
+        config: &PreprocessConfig,
+    ) -> Preprocessed {
+        phase_span!("preprocess");
+
+        // Preprocessing: Resize the image to what the model expects
+        // (letterbox image to fix aspect ratio without cropping or stretching)
+
+        let target_size = INPUT_SIZE as u32;
+
+        let mut canvas = ImageBuffer::from_pixel(target_size, target_size, Rgb([0, 0, 0]));
+
+        let (frame_width, frame_height) = frame.dimensions();
+        let layout = letterbox_layout(frame_width, frame_height, target_size);
+
+        let resized_frame =
+            image::imageops::resize(frame, layout.width, layout.height, config.filter);
+        image::imageops::overlay(
+            &mut canvas,
+            &resized_frame,
+            layout.left as i64,
+            layout.top as i64,
+        );
+
+        // Convert Image to Tensor [1, 3, 256, 256] (or [1, 256, 256, 3] for NHWC models)
+        // We normalize pixels from 0-255 (u8) to 0.0-1.0 (f32), then apply mean/std
+        let mut input = Array4::<f32>::zeros(config.layout.shape(target_size as usize));
+        let [mean, std] = [config.mean, config.std];
+
+        for (x, y, rgb) in canvas.enumerate_pixels() {
+            let (x, y) = (x as usize, y as usize);
+            for c in 0..3 {
+                let pixel_mean = config.mean_image.as_ref().map_or(0.0, |m| m.at(x, y, c));
+                let value = (rgb[c] as f32 / 255.0 - pixel_mean - mean[c]) / std[c];
+                match config.layout {
+                    InputLayout::Nchw => input[[0, c, y, x]] = value,
+                    InputLayout::Nhwc => input[[0, y, x, c]] = value,
+                }
             }
         }
+
+        Preprocessed {
+            input,
+            letterbox: LetterboxTransform::fit(frame_width, frame_height, target_size),
+        }
+    }
+
+    // Extract both output tensors as [1, anchors, 1] scores and [1, anchors, 18] coords
+    fn output_arrays<'a>(
+        outputs: &'a SessionOutputs<'_>,
+    ) -> Result<(ArrayView3<'a, f32>, ArrayView3<'a, f32>)> {
+        let (scores_shape, scores_data) = outputs["box_scores"].try_extract_tensor::<f32>()?;
+        let (coords_shape, coords_data) = outputs["box_coords"].try_extract_tensor::<f32>()?;
+
+        let scores_shape_usize: Vec<usize> = scores_shape.iter().map(|&x| x as usize).collect();
+        let coords_shape_usize: Vec<usize> = coords_shape.iter().map(|&x| x as usize).collect();
+
+        let scores =
+            ArrayView::from_shape(scores_shape_usize, scores_data)?.into_dimensionality::<Ix3>()?;
+        let coords =
+            ArrayView::from_shape(coords_shape_usize, coords_data)?.into_dimensionality::<Ix3>()?;
+
+        Ok((scores, coords))
+    }
+
+    // A model with a different anchor layout, or fewer keypoints per box, would silently
+    // decode wrong boxes frame after frame
+    fn check_output_shapes(scores: &[usize], coords: &[usize], num_anchors: usize) -> Result<()> {
+        if scores[1] != num_anchors || coords[1] != num_anchors {
+            return Err(anyhow!(
+                "Model outputs {} scores and {} boxes, but {} anchors were generated",
+                scores[1],
+                coords[1],
+                num_anchors
+            ));
+        }
+        if coords[2] < 4 + 2 * NUM_KEYPOINTS {
+            return Err(anyhow!(
+                "Model outputs {} values per box, expected at least {} (a box and {} keypoints)",
+                coords[2],
+                4 + 2 * NUM_KEYPOINTS,
+                NUM_KEYPOINTS
+            ));
+        }
+        Ok(())
+    }
+
+    // The model's outputs before any decoding, for custom postprocessing.
+    // Scores are raw (no sigmoid), coords are anchor-relative offsets in input pixels
+    // (4 box values then 7 keypoint (x, y) pairs per anchor), and `letterbox` maps
+    // model input coordinates back to the frame.
+    pub struct RawOutputs {
+        pub scores: Array3<f32>,
+        pub coords: Array3<f32>,
+        pub letterbox: LetterboxTransform,
+    }
+
+    // Iterator over the detections of a frame source, see HandDetector::stream.
+    // Capture errors are yielded as Err: transient ones can be skipped, after a fatal one
+    // (e.g. the camera was unplugged) the stream ends, as it does without an error once a
+    // finite source (a pipe, a replay) runs out of frames.
+    pub struct DetectionStream<'a, S> {
+        detector: &'a mut HandDetector,
+        source: S,
+        frame_interval: Option<Duration>,
+        last_frame: Option<Instant>,
+        done: bool,
+    }
+
+    impl<S: FrameSource> DetectionStream<'_, S> {
+        // Pull at most `fps` frames per second, sleeping between frames if the source and
+        // detector are faster (like the window's update rate limit)
+        pub fn with_max_fps(mut self, fps: u32) -> Self {
+            self.frame_interval = (fps > 0).then(|| Duration::from_secs(1) / fps);
+            self
+        }
+    }
+
+    impl<S: FrameSource> Iterator for DetectionStream<'_, S> {
+        type Item = Result<Vec<HandDetails>>;
+
+        fn next(&mut self) -> Option<Self::Item> {
+            if self.done {
+                return None;
+            }
+
+            if let (Some(interval), Some(last)) = (self.frame_interval, self.last_frame) {
+                let elapsed = last.elapsed();
+                if elapsed < interval {
+                    std::thread::sleep(interval - elapsed);
+                }
+            }
+            self.last_frame = Some(Instant::now());
+
+            match self.source.next_frame() {
+                Ok(frame) => Some(self.detector.detect(&frame).map(Option::unwrap_or_default)),
+                Err(e) if e.is_end_of_stream() => {
+                    self.done = true;
+                    None
+                }
+                Err(e) => {
+                    self.done = e.is_fatal();
+                    Some(Err(e.into()))
+                }
+            }
+        }
+    }
+
+    // Why a detector model failed to load.
+    //
+    // - ExecutionProvider: the requested provider (CUDA, TensorRT, ...) could not be
+    //   registered, e.g. a CUDA build on a machine without CUDA. Retrying on CPU works.
+    // - InvalidModel: the bytes/file are not a loadable ONNX model.
+    // - ShapeMismatch: the model loads but its inputs/outputs are not the palm detector's.
+    // - Other: any other ONNX Runtime error.
+    #[derive(Debug)]
+    pub enum ModelLoadError {
+        ExecutionProvider(ort::Error),
+        InvalidModel(ort::Error),
+        ShapeMismatch(String),
+        Other(ort::Error),
+    }
+
+    impl ModelLoadError {
+        pub fn classify(error: ort::Error) -> Self {
+            match error.code() {
+                ErrorCode::ExecutionProviderFailure => ModelLoadError::ExecutionProvider(error),
+                ErrorCode::InvalidProtobuf
+                | ErrorCode::InvalidGraph
+                | ErrorCode::NoModel
+                | ErrorCode::NoSuchFile => ModelLoadError::InvalidModel(error),
+                // Providers missing from the build fail registration with a generic error
+                _ if error.message().contains("execution provider") => {
+                    ModelLoadError::ExecutionProvider(error)
+                }
+                _ => ModelLoadError::Other(error),
+            }
+        }
+
+        pub fn is_execution_provider(&self) -> bool {
+            matches!(self, ModelLoadError::ExecutionProvider(_))
+        }
+    }
+
+    impl fmt::Display for ModelLoadError {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            match self {
+                ModelLoadError::ExecutionProvider(e) => {
+                    write!(f, "execution provider unavailable: {}", e)
+                }
+                ModelLoadError::InvalidModel(e) => write!(f, "invalid model: {}", e),
+                ModelLoadError::ShapeMismatch(reason) => {
+                    write!(f, "model shape mismatch: {}", reason)
+                }
+                ModelLoadError::Other(e) => write!(f, "failed to load model: {}", e),
+            }
+        }
+    }
+
+    impl std::error::Error for ModelLoadError {}
+
+    // Name, shape and element type of one model input or output
+    #[derive(Debug, Clone)]
+    pub struct TensorInfo {
+        pub name: String,
+        // None for non-tensor values, dynamic dimensions are -1
+        pub shape: Option<Vec<i64>>,
+        pub dtype: String,
+    }
+
+    #[derive(Debug, Clone)]
+    pub struct ModelSignature {
+        pub inputs: Vec<TensorInfo>,
+        pub outputs: Vec<TensorInfo>,
+    }
+
+    impl ModelSignature {
+        pub fn from_session(session: &Session) -> Self {
+            let describe = |outlets: &[ort::value::Outlet]| -> Vec<TensorInfo> {
+                outlets
+                    .iter()
+                    .map(|outlet| TensorInfo {
+                        name: outlet.name().to_string(),
+                        shape: outlet.dtype().tensor_shape().map(|shape| shape.to_vec()),
+                        dtype: match outlet.dtype().tensor_type() {
+                            Some(element) => element.to_string(),
+                            None => outlet.dtype().to_string(),
+                        },
+                    })
+                    .collect()
+            };
+
+            Self {
+                inputs: describe(session.inputs()),
+                outputs: describe(session.outputs()),
+            }
+        }
+
+        // Signature of any ONNX model file (it doesn't have to be a palm detector)
+        pub fn from_file<P: AsRef<Path>>(model_path: P) -> Result<Self> {
+            let session = Session::builder()?.commit_from_file(model_path)?;
+            Ok(Self::from_session(&session))
+        }
+    }
+
+    impl fmt::Display for ModelSignature {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            for (title, tensors) in [("Inputs", &self.inputs), ("Outputs", &self.outputs)] {
+                writeln!(f, "{}:", title)?;
+                for tensor in tensors {
+                    match &tensor.shape {
+                        Some(shape) => {
+                            writeln!(f, "  {} {:?} {}", tensor.name, shape, tensor.dtype)?
+                        }
+                        None => writeln!(f, "  {} {}", tensor.name, tensor.dtype)?,
+                    }
+                }
+            }
+            Ok(())
+        }
+    }
+
+    fn input_shape(session: &Session) -> Option<Vec<i64>> {
+        let input = session
+            .inputs()
+            .iter()
+            .find(|input| input.name() == "image")?;
+        input.dtype().tensor_shape().map(|shape| shape.to_vec())
+    }
+
+    // A model swapped in for the active one (add_model, reload) must output one score per
+    // anchor and take the same input shape; dynamic (-1) or unknown dimensions match anything
+    fn check_model_matches(
+        anchors: usize,
+        output_anchors: Option<usize>,
+        active_input: Option<&[i64]>,
+        input: Option<&[i64]>,
+    ) -> Result<()> {
+        if let Some(count) = output_anchors
+            && count != anchors
+        {
+            return Err(anyhow!(
+                "Model outputs {} anchors, but the detector has {}",
+                count,
+                anchors
+            ));
+        }
+        if let (Some(active), Some(input)) = (active_input, input) {
+            let dims_match = |a: i64, b: i64| a < 0 || b < 0 || a == b;
+            if active.len() != input.len()
+                || !active.iter().zip(input).all(|(&a, &b)| dims_match(a, b))
+            {
+                return Err(anyhow!(
+                    "Model input shape {:?} doesn't match the active model's {:?}",
+                    input,
+                    active
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    // Make sure a loaded model has the palm detector's inputs and outputs
+    fn check_model_io(session: &Session) -> Result<(), ModelLoadError> {
+        if !session.inputs().iter().any(|input| input.name() == "image") {
+            return Err(ModelLoadError::ShapeMismatch(
+                "no input named 'image'".to_string(),
+            ));
+        }
+
+        // Dynamic dimensions are reported as -1
+        let size = INPUT_SIZE as i64;
+        if let Some(shape) = input_shape(session) {
+            let layouts = [[1, 3, size, size], [1, size, size, 3]];
+            let matches = layouts.iter().any(|expected| {
+                shape.len() == 4
+                    && shape
+                        .iter()
+                        .zip(expected)
+                        .all(|(&dim, &expected)| dim == expected || dim < 0)
+            });
+            if !matches {
+                return Err(ModelLoadError::ShapeMismatch(format!(
+                    "input 'image' has shape {:?}, expected {:?} (NCHW) or {:?} (NHWC)",
+                    shape, layouts[0], layouts[1]
+                )));
+            }
+        }
+
+        for name in ["box_coords", "box_scores"] {
+            if !session.outputs().iter().any(|output| output.name() == name) {
+                return Err(ModelLoadError::ShapeMismatch(format!(
+                    "no output named '{}'",
+                    name
+                )));
+            }
+        }
+
+        Ok(())
+    }
+
+    // ONNX Runtime session settings shared by the detector and the landmarker.
+    // With several intra-op threads, reductions may be summed in a different order from run
+    // to run, so scores can differ in the last bits. `deterministic()` runs on one thread
+    // with ORT's deterministic kernels, so the same frame always gives bit-identical
+    // outputs; tests comparing outputs should use it.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct SessionConfig {
+        pub intra_threads: usize,
+        pub deterministic_compute: bool,
+    }
+
+    impl Default for SessionConfig {
+        fn default() -> Self {
+            Self {
+                intra_threads: 4,
+                deterministic_compute: false,
+            }
+        }
+    }
+
+    impl SessionConfig {
+        pub fn deterministic() -> Self {
+            Self {
+                intra_threads: 1,
+                deterministic_compute: true,
+            }
+        }
+
+        pub fn builder(&self) -> ort::Result<SessionBuilder> {
+            Session::builder()?
+                .with_optimization_level(GraphOptimizationLevel::Level3)?
+                .with_intra_threads(self.intra_threads.max(1))?
+                .with_deterministic_compute(self.deterministic_compute)
+        }
+    }
+
+    fn build_session(
+        config: SessionConfig,
+        providers: &[ExecutionProviderDispatch],
+        commit: impl FnOnce(SessionBuilder) -> ort::Result<Session>,
+    ) -> Result<Session, ModelLoadError> {
+        // Create new session for model
+        let builder = config
+            .builder()
+            .and_then(|b| b.with_execution_providers(providers))
+            .map_err(ModelLoadError::classify)?;
+        let session = commit(builder).map_err(ModelLoadError::classify)?;
+        check_model_io(&session)?;
+
+        Ok(session)
+    }
+
+    fn session_from_memory(
+        config: SessionConfig,
+        model_bytes: &[u8],
+    ) -> Result<Session, ModelLoadError> {
+        build_session(config, &[], |builder| {
+            builder.commit_from_memory(model_bytes)
+        })
+    }
+
+    impl HandDetector {
+        pub fn new<P: AsRef<Path>>(model_path: P) -> Result<Self> {
+            let config = SessionConfig::default();
+            let session =
+                build_session(config, &[], |builder| builder.commit_from_file(model_path))?;
+            Self::from_session(session, config)
+        }
+
+        pub fn new_embedded(model_bytes: &[u8]) -> Result<Self> {
+            Self::new_embedded_with_config(model_bytes, SessionConfig::default())
+        }
+
+        // Models added or reloaded later use the same session settings
+        pub fn new_embedded_with_config(model_bytes: &[u8], config: SessionConfig) -> Result<Self> {
+            Self::from_session(session_from_memory(config, model_bytes)?, config)
+        }
+
+        // Load on the given execution providers (e.g. CUDA). If they can't be registered
+        // and cpu_fallback is set, load again on the default CPU provider instead of failing.
+        pub fn new_embedded_with_providers(
+            model_bytes: &[u8],
+            providers: Vec<ExecutionProviderDispatch>,
+            cpu_fallback: bool,
+        ) -> Result<Self> {
+            // Without error_on_failure ONNX Runtime silently falls back on its own
+            let providers: Vec<ExecutionProviderDispatch> = providers
+                .into_iter()
+                .map(ExecutionProviderDispatch::error_on_failure)
+                .collect();
+
+            let config = SessionConfig::default();
+            match build_session(config, &providers, |builder| {
+                builder.commit_from_memory(model_bytes)
+            }) {
+                Ok(session) => Self::from_session(session, config),
+                Err(e) if cpu_fallback && e.is_execution_provider() => {
+                    eprintln!("{}, falling back to CPU", e);
+                    Self::new_embedded(model_bytes)
+                }
+                Err(e) => Err(e.into()),
+            }
+        }
+
+        fn from_session(session: Session, session_config: SessionConfig) -> Result<Self> {
+            // Generate array of all anchors
+            let anchors = generate_anchors(NUM_ANCHORS)?;
+
+            // Build the input in the model's own axis order when its shape tells
+            let layout = input_shape(&session)
+                .and_then(|shape| InputLayout::from_shape(&shape))
+                .unwrap_or_default();
+
+            Ok(Self {
+                session,
+                session_config,
+                active_model: "default".to_string(),
+                models: HashMap::new(),
+                anchors,
+                offset_scale: INPUT_SIZE,
+                size_scale: INPUT_SIZE,
+                clamp_boxes: false,
+                preprocess_config: PreprocessConfig {
+                    layout,
+                    ..PreprocessConfig::default()
+                },
+                score_on: SCORE_THRESHOLD,
+                score_off: SCORE_THRESHOLD,
+                activation: ActivationMode::Raw,
+                coord_reference: CoordReference::Frame,
+                output_order: OutputOrder::Score,
+                active_boxes: Vec::new(),
+                adaptive: None,
+                max_hands: 2,
+                overflow_policy: OverflowPolicy::Truncate,
+                overflow: 0,
+                min_key_visibility: None,
+                ignore_border: None,
+                box_fallback: None,
+                nms_mode: NmsMode::ClassAgnostic,
+                tiling: None,
+                stitched_cameras: None,
+                roi_tracking: None,
+                frames_since_scan: 0,
+                active_zone: None,
+                post_processor: None,
+                logging: true,
+            })
+        }
+
+        // Load another model under a name, to switch to later with switch_model.
+        // Models must share the input size, input layout and anchors (anchors are not
+        // regenerated), a model that doesn't is rejected here.
+        pub fn add_model(&mut self, name: &str, model_bytes: &[u8]) -> Result<()> {
+            if name == self.active_model {
+                return Err(anyhow!("Model '{}' is already the active model", name));
+            }
+            let session = session_from_memory(self.session_config, model_bytes)?;
+            self.check_matches_active(&session)
+                .map_err(|e| anyhow!("Can't add model '{}': {}", name, e))?;
+            self.models.insert(name.to_string(), session);
+            Ok(())
+        }
+
+        fn check_matches_active(&self, session: &Session) -> Result<()> {
+            check_model_matches(
+                self.anchors.len(),
+                output_anchor_count(session),
+                input_shape(&self.session).as_deref(),
+                input_shape(session).as_deref(),
+            )
+        }
+
+        // Make a previously added model the active one. The old active model stays loaded.
+        pub fn switch_model(&mut self, name: &str) -> Result<()> {
+            if name == self.active_model {
+                return Ok(());
+            }
+            let session = self
+                .models
+                .remove(name)
+                .ok_or_else(|| anyhow!("No model named '{}' is loaded", name))?;
+
+            let previous = std::mem::replace(&mut self.session, session);
+            let previous_name = std::mem::replace(&mut self.active_model, name.to_string());
+            self.models.insert(previous_name, previous);
+            Ok(())
+        }
+
+        // Replace the active model's session in place
+        pub fn reload(&mut self, model_bytes: &[u8]) -> Result<()> {
+            let session = session_from_memory(self.session_config, model_bytes)?;
+            self.check_matches_active(&session)?;
+            self.session = session;
+            Ok(())
+        }
+
+        // Inputs and outputs of the active model
+        pub fn describe_model(&self) -> ModelSignature {
+            ModelSignature::from_session(&self.session)
+        }
+
+        pub fn active_model(&self) -> &str {
+            &self.active_model
+        }
+
+        pub fn model_names(&self) -> Vec<&str> {
+            std::iter::once(self.active_model.as_str())
+                .chain(self.models.keys().map(String::as_str))
+                .collect()
+        }
+
+        // Set both regression divisors (INPUT_SIZE by default, use 1.0 for pre-normalized exports).
+        // A wrong value is easy to spot: boxes come out tiny (too large a divisor)
+        // or far outside the 0.0 - 1.0 frame (too small a divisor).
+        pub fn with_coord_scale(mut self, coord_scale: f32) -> Self {
+            self.offset_scale = coord_scale;
+            self.size_scale = coord_scale;
+            self
+        }
+
+        // Divisor for the box center and keypoint offsets only (INPUT_SIZE by default)
+        pub fn with_offset_scale(mut self, offset_scale: f32) -> Self {
+            self.offset_scale = offset_scale;
+            self
+        }
+
+        // Divisor for the box width and height only (INPUT_SIZE by default), for exports
+        // whose size regression uses another scale than the offsets
+        pub fn with_size_scale(mut self, size_scale: f32) -> Self {
+            self.size_scale = size_scale;
+            self
+        }
+
+        pub fn with_clamp_boxes(mut self, clamp_boxes: bool) -> Self {
+            self.clamp_boxes = clamp_boxes;
+            self
+        }
+
+        // Per-channel mean/std normalization for retrained models (ImageNet-style).
+        // The default (mean 0, std 1) feeds pixels as plain 0.0 - 1.0 values.
+        pub fn with_normalization(mut self, mean: [f32; 3], std: [f32; 3]) -> Result<Self> {
+            if std.iter().any(|s| *s == 0.0 || !s.is_finite()) {
+                return Err(anyhow!(
+                    "Normalization std must be finite and nonzero, got {:?}",
+                    std
+                ));
+            }
+            self.preprocess_config.mean = mean;
+            self.preprocess_config.std = std;
+            Ok(self)
+        }
+
+        // Subtract a full mean image after the /255 step (before the per-channel mean/std),
+        // for palm detectors fine-tuned with that preprocessing
+        pub fn with_mean_image(mut self, mean_image: MeanImage) -> Self {
+            self.preprocess_config.mean_image = Some(Arc::new(mean_image));
+            self
+        }
+
+        // Separate thresholds to start (on) and keep (off) reporting a detection in a region,
+        // to stop detections flickering when the score hovers around a single threshold
+        pub fn with_hysteresis(mut self, on: f32, off: f32) -> Result<Self> {
+            if !on.is_finite() || !off.is_finite() || off > on {
+                return Err(anyhow!(
+                    "Hysteresis thresholds must be finite with off <= on, got on {} off {}",
+                    on,
+                    off
+                ));
+            }
+            self.score_on = on;
+            self.score_off = off;
+            Ok(self)
+        }
+
+        // Score activation (raw logits by default). The current hysteresis thresholds and
+        // adaptive threshold bounds are converted to the new scale (e.g. the default 1.0
+        // becomes ~0.73 with Sigmoid); thresholds set afterwards are in the new scale.
+        pub fn with_activation(mut self, activation: ActivationMode) -> Self {
+            let convert = |score: f32| activation.apply(self.activation.invert(score));
+            self.score_on = convert(self.score_on);
+            self.score_off = convert(self.score_off);
+            if let Some(adaptive) = self.adaptive.as_mut() {
+                adaptive.threshold = convert(adaptive.threshold);
+                adaptive.min = convert(adaptive.min);
+                adaptive.max = convert(adaptive.max);
+            }
+            self.activation = activation;
+            self
+        }
+
+        pub fn activation(&self) -> ActivationMode {
+            self.activation
+        }
+
+        // Return coordinates relative to the model canvas instead of the frame (see
+        // CoordReference). Canvas coordinates only make sense for a single pass over the
+        // whole frame, so `detect` rejects ModelCanvas with tiling, stitched cameras, an
+        // active zone or ROI tracking. With ModelCanvas, box filters (clamping, the border
+        // margin) apply to the canvas and `detect_pixels` returns canvas pixels.
+        pub fn with_coord_reference(mut self, reference: CoordReference) -> Self {
+            self.coord_reference = reference;
+            self
+        }
+
+        pub fn coord_reference(&self) -> CoordReference {
+            self.coord_reference
+        }
+
+        // Order of the returned hands, applied after the post-processor (see OutputOrder)
+        pub fn with_output_order(mut self, order: OutputOrder) -> Self {
+            self.output_order = order;
+            self
+        }
+
+        pub fn output_order(&self) -> OutputOrder {
+            self.output_order
+        }
+
+        // Adapt the score threshold to the scene (e.g. changing lighting) so that between
+        // min_detections and max_detections hands survive NMS each frame, within [min, max].
+        // The threshold only moves a small step per frame, so it takes a moment to settle
+        // after a change, in exchange for not needing a threshold tuned per environment.
+        pub fn with_adaptive_threshold(
+            mut self,
+            min_detections: usize,
+            max_detections: usize,
+            min: f32,
+            max: f32,
+        ) -> Result<Self> {
+            if min_detections > max_detections || !min.is_finite() || !max.is_finite() || min > max
+            {
+                return Err(anyhow!(
+                    "Adaptive threshold needs min <= max, got detections {}..{} and bounds {}..{}",
+                    min_detections,
+                    max_detections,
+                    min,
+                    max
+                ));
+            }
+            self.adaptive = Some(AdaptiveThreshold {
+                min_detections,
+                max_detections,
+                min,
+                max,
+                threshold: self.score_on.clamp(min, max),
+            });
+            Ok(self)
+        }
+
+        // Current (on, off) score thresholds, after adaptation
+        pub fn score_thresholds(&self) -> (f32, f32) {
+            match self.adaptive {
+                Some(adaptive) => (
+                    adaptive.threshold,
+                    adaptive.threshold - (self.score_on - self.score_off),
+                ),
+                None => (self.score_on, self.score_off),
+            }
+        }
+
+        // Use the anchors in this file (see load_anchors) instead of generating them, for
+        // model variants shipped with precomputed anchors. The count must match the model's
+        // output (checked here when the model's output shape is static, otherwise at warmup
+        // and detect time). Anchors are shared with models added later.
+        pub fn with_anchors_from_file(mut self, path: &Path) -> Result<Self> {
+            let anchors = load_anchors(path)?;
+            if anchors.is_empty() {
+                return Err(anyhow!("{} has no anchors", path.display()));
+            }
+            if let Some(expected) = output_anchor_count(&self.session)
+                && anchors.len() != expected
+            {
+                return Err(anyhow!(
+                    "{} has {} anchors, but the model outputs {}",
+                    path.display(),
+                    anchors.len(),
+                    expected
+                ));
+            }
+            self.anchors = anchors;
+            Ok(self)
+        }
+
+        // Maximum number of hands returned per frame (2 by default)
+        pub fn with_max_hands(mut self, max_hands: usize) -> Self {
+            self.max_hands = max_hands;
+            self
+        }
+
+        // What to do when more hands than that are found (truncate by default)
+        pub fn with_overflow_policy(mut self, policy: OverflowPolicy) -> Self {
+            self.overflow_policy = policy;
+            self
+        }
+
+        // With OverflowPolicy::Flag, whether the last detect dropped hands over max_hands
+        // (always false with the other policies)
+        pub fn truncated(&self) -> bool {
+            self.overflow_policy == OverflowPolicy::Flag && self.overflow > 0
+        }
+
+        // Drop candidates whose key keypoints (wrist and index knuckle, which gestures and the
+        // landmark crop rely on) have visibility below this, before NMS. Such boxes often
+        // look plausible but carry keypoints regressed outside the palm, which throw off the
+        // landmarker and gestures. Trades recall (some real hands are dropped, e.g. partly
+        // occluded ones) for more reliable gestures.
+        pub fn with_min_key_visibility(mut self, threshold: f32) -> Self {
+            self.min_key_visibility = Some(threshold);
+            self
+        }
+
+        // Drop detections whose box comes within `margin` (normalized, e.g. 0.02 is 2% of the
+        // frame) of any frame edge. Hands entering from the edge are only partly in view, so
+        // their boxes and landmarks are unreliable. Off by default.
+        pub fn with_ignore_border(mut self, margin: f32) -> Self {
+            self.ignore_border = Some(margin.max(0.0));
+            self
+        }
+
+        // Estimate the wrist and palm center keypoints from the box geometry when their
+        // visibility is below `min_visibility` (see HandDetails::estimate_from_box). Applied
+        // to the hands kept after NMS, so it doesn't rescue candidates dropped by
+        // with_min_key_visibility.
+        pub fn with_box_fallback(mut self, min_visibility: f32) -> Self {
+            self.box_fallback = Some(min_visibility);
+            self
+        }
+
+        // Palm detections carry no handedness, so with ClassAware `detect` only merges
+        // near-identical boxes, and the class-aware pass runs in `nms_by_handedness` once
+        // the landmarker has filled handedness in.
+        pub fn with_nms_mode(mut self, mode: NmsMode) -> Self {
+            self.nms_mode = mode;
+            self
+        }
+
+        pub fn nms_mode(&self) -> NmsMode {
+            self.nms_mode
+        }
+
+        // The class-aware NMS pass over landmarked hands: of overlapping hands with the same
+        // handedness only the best scoring is kept, a left and a right hand both stay.
+        // Hands are returned best score first. Does nothing with ClassAgnostic, where
+        // `detect` already merged every overlap.
+        pub fn nms_by_handedness(&self, hands: Vec<HandDetails>) -> Vec<HandDetails> {
+            match self.nms_mode {
+                NmsMode::ClassAgnostic => hands,
+                NmsMode::ClassAware => apply_nms(hands, NMS_IOU_THRESHOLD, NmsMode::ClassAware),
+            }
+        }
+
+        // Overlap above which `detect` merges palm candidates (see nms_by_handedness)
+        fn palm_nms_threshold(&self) -> f32 {
+            match self.nms_mode {
+                NmsMode::ClassAgnostic => NMS_IOU_THRESHOLD,
+                NmsMode::ClassAware => CLASS_AWARE_PALM_IOU_THRESHOLD,
+            }
+        }
+
+        // Detect on a cols x rows grid of overlapping tiles (plus the whole frame) and merge
+        // them, trading one model run per tile for recall of small, distant hands
+        pub fn with_tiles(mut self, cols: u32, rows: u32, overlap: f32) -> Result<Self> {
+            if cols == 0 || rows == 0 || !(0.0..1.0).contains(&overlap) {
+                return Err(anyhow!(
+                    "Tiling needs at least 1x1 tiles and an overlap in 0.0 to 1.0, got {}x{} overlap {}",
+                    cols,
+                    rows,
+                    overlap
+                ));
+            }
+            self.tiling = Some(Tiling {
+                cols,
+                rows,
+                overlap,
+            });
+            Ok(self)
+        }
+
+        // "Track then detect": once hands are found, the following frames only run detection
+        // on a region `expansion` times the size of the box around them (in one pass, even
+        // with tiling), falling back to the full frame when they are lost and every
+        // `rescan_every` frames to catch new hands.
+        pub fn with_roi_tracking(mut self, expansion: f32, rescan_every: u32) -> Result<Self> {
+            if !expansion.is_finite() || expansion < 1.0 || rescan_every == 0 {
+                return Err(anyhow!(
+                    "ROI tracking needs an expansion of at least 1.0 and a rescan interval of at least 1 frame, got {} and {}",
+                    expansion,
+                    rescan_every
+                ));
+            }
+            self.roi_tracking = Some(RoiTracking {
+                expansion,
+                rescan_every,
+            });
+            Ok(self)
+        }
+
+        // Frames are `cameras` camera images stitched side by side (e.g. 2 for a dual-camera
+        // rig): each camera's part is detected on its own, so it isn't shrunk into a wide
+        // letterbox, and hands are mapped back into the stitched frame and tagged with their
+        // camera. A hand cut by a seam is merged into one detection instead of counted twice.
+        // Replaces tiling.
+        pub fn with_stitched_cameras(mut self, cameras: u32) -> Result<Self> {
+            if cameras == 0 {
+                return Err(anyhow!("A stitched frame needs at least 1 camera"));
+            }
+            self.stitched_cameras = Some(cameras);
+            Ok(self)
+        }
+
+        // Only detect hands inside `zone` (normalized frame coordinates). The frame is cropped
+        // to the zone before inference, and hands whose box center is outside it are dropped.
+        pub fn with_active_zone(mut self, zone: Box) -> Result<Self> {
+            let valid = |min: f32, max: f32| 0.0 <= min && min < max && max <= 1.0;
+            if !valid(zone.xmin, zone.xmax) || !valid(zone.ymin, zone.ymax) {
+                return Err(anyhow!(
+                    "Active zone must satisfy 0 <= min < max <= 1 on both axes, got ({}, {}) ({}, {})",
+                    zone.xmin,
+                    zone.ymin,
+                    zone.xmax,
+                    zone.ymax
+                ));
+            }
+            self.active_zone = Some(zone);
+            Ok(self)
+        }
+
+        pub fn active_zone(&self) -> Option<Box> {
+            self.active_zone
+        }
+
+        pub fn with_post_processor(mut self, post_processor: impl PostProcessor + 'static) -> Self {
+            self.post_processor = Some(std::boxed::Box::new(post_processor));
+            self
+        }
+
+        // Turn off printing to stdout (e.g. while a terminal ui owns it)
+        pub fn with_logging(mut self, logging: bool) -> Self {
+            self.logging = logging;
+            self
+        }
+
+        pub fn tiling(&self) -> Option<Tiling> {
+            self.tiling
+        }
+
+        // Override the input layout detected from the model (for models with dynamic shapes)
+        pub fn with_input_layout(mut self, layout: InputLayout) -> Self {
+            self.preprocess_config.layout = layout;
+            self
+        }
+
+        pub fn with_resize_filter(mut self, filter: FilterType) -> Self {
+            self.preprocess_config.filter = filter;
+            self
+        }
+
+        // Settings to pass to `preprocess` when preprocessing outside of `detect`
+        pub fn preprocess_config(&self) -> PreprocessConfig {
+            self.preprocess_config.clone()
+        }
+
+        pub fn detect(
+            &mut self,
+            frame: &ImageBuffer<Rgb<u8>, Vec<u8>>,
+        ) -> Result<Option<Vec<HandDetails>>> {
+            phase_span!("detect");
+            check_frame(frame)?;
+            self.check_coord_reference()?;
+            let hands = match (self.next_roi(), self.active_zone) {
+                (Some(roi), _) => self.detect_in_roi(frame, roi)?,
+                (None, Some(zone)) => self.detect_in_zone(frame, zone)?,
+                (None, None) => self.detect_frame(frame)?,
+            };
+            self.check_overflow()?;
+            Ok(self.post_process(hands))
+        }
+
+        // Like detect, with whether hands over max_hands were dropped (OverflowPolicy::Flag)
+        pub fn detect_flagged(
+            &mut self,
+            frame: &ImageBuffer<Rgb<u8>, Vec<u8>>,
+        ) -> Result<FlaggedHands> {
+            let hands = self.detect(frame)?.unwrap_or_default();
+            Ok(FlaggedHands {
+                hands,
+                truncated: self.truncated(),
+            })
+        }
+
+        // Like detect, but in pixels of `frame` itself rather than normalized coordinates,
+        // so callers don't have to undo the letterbox or scale results themselves
+        pub fn detect_pixels(
+            &mut self,
+            frame: &ImageBuffer<Rgb<u8>, Vec<u8>>,
+        ) -> Result<Option<Vec<HandDetailsPx>>> {
+            let (width, height) = frame.dimensions();
+            Ok(self
+                .detect(frame)?
+                .map(|hands| self.hands_to_pixels(&hands, width, height)))
+        }
+
+        // Pixels of the frame, or of the model canvas with CoordReference::ModelCanvas
+        fn hands_to_pixels(
+            &self,
+            hands: &[HandDetails],
+            width: u32,
+            height: u32,
+        ) -> Vec<HandDetailsPx> {
+            let (width, height) = match self.coord_reference {
+                CoordReference::Frame => (width, height),
+                CoordReference::ModelCanvas => (INPUT_SIZE as u32, INPUT_SIZE as u32),
+            };
+            hands
+                .iter()
+                .map(|hand| hand.to_pixels(width, height))
+                .collect()
+        }
+
+        // Iterate over the detections of a live source, pulling and detecting one frame per
+        // item (an empty Vec when no hands are found), e.g. `for hands in detector.stream(cam)`.
+        // Pass `&mut source` to keep using the source afterwards.
+        pub fn stream<S: FrameSource>(&mut self, source: S) -> DetectionStream<'_, S> {
+            DetectionStream {
+                detector: self,
+                source,
+                frame_interval: None,
+                last_frame: None,
+                done: false,
+            }
+        }
+
+        // Run the model once on a zeroed input so ORT's lazy initialization and allocations
+        // happen now rather than on the first real frame. Returns how long it took. Also
+        // checks the outputs match the anchors and keypoints, so an incompatible model
+        // fails here with one clear error rather than on every frame.
+        pub fn warmup(&mut self) -> Result<Duration> {
+            let start = Instant::now();
+            let input =
+                Array4::<f32>::zeros(self.preprocess_config.layout.shape(INPUT_SIZE as usize));
+            let input_tensor = Value::from_array(input)?;
+            let outputs = self.session.run(inputs!["image" => input_tensor])?;
+            let elapsed = start.elapsed();
+
+            let (scores, coords) = output_arrays(&outputs)?;
+            check_output_shapes(scores.shape(), coords.shape(), self.anchors.len()).map_err(
+                |e| {
+                    anyhow!(
+                        "Model '{}' is incompatible with the palm detector: {}",
+                        self.active_model,
+                        e
+                    )
+                },
+            )?;
+            Ok(elapsed)
+        }
+
+        // Run the model and return its outputs without candidate decoding or NMS
+        pub fn detect_raw(&mut self, frame: &ImageBuffer<Rgb<u8>, Vec<u8>>) -> Result<RawOutputs> {
+            check_frame(frame)?;
+            let Preprocessed { input, letterbox } = preprocess(frame, &self.preprocess_config);
+            let input_tensor = Value::from_array(input)?;
+            let outputs = self.session.run(inputs!["image" => input_tensor])?;
+
+            let (scores, coords) = output_arrays(&outputs)?;
+            Ok(RawOutputs {
+                scores: scores.to_owned(),
+                coords: coords.to_owned(),
+                letterbox,
+            })
+        }
+
+        // Run the model once and report the raw output tensor shapes (for diagnostics)
+        pub fn output_shapes(
+            &mut self,
+            frame: &ImageBuffer<Rgb<u8>, Vec<u8>>,
+        ) -> Result<Vec<(&'static str, Vec<i64>)>> {
+            check_frame(frame)?;
+            let input_tensor = Value::from_array(preprocess(frame, &self.preprocess_config).input)?;
+            let outputs = self.session.run(inputs!["image" => input_tensor])?;
+
+            ["box_coords", "box_scores"]
+                .into_iter()
+                .map(|name| {
+                    let (shape, _) = outputs[name].try_extract_tensor::<f32>()?;
+                    Ok((name, shape.iter().copied().collect()))
+                })
+                .collect()
+        }
+
+        // Run detection on a frame already converted by `preprocess` (e.g. on another thread)
+        pub fn detect_preprocessed(
+            &mut self,
+            preprocessed: Preprocessed,
+        ) -> Result<Option<Vec<HandDetails>>> {
+            let candidates = self.candidates(preprocessed)?;
+            let hands = self.select(candidates);
+            self.check_overflow()?;
+            Ok(self.post_process(hands))
+        }
+
+        fn check_overflow(&self) -> Result<()> {
+            if self.overflow_policy == OverflowPolicy::Error && self.overflow > 0 {
+                return Err(anyhow!(
+                    "{} hands detected, more than the maximum of {}",
+                    self.max_hands + self.overflow,
+                    self.max_hands
+                ));
+            }
+            Ok(())
+        }
+
+        // Crops and tiles are mapped back through the frame, which canvas coordinates skip
+        fn check_coord_reference(&self) -> Result<()> {
+            let crops = self.tiling.is_some()
+                || self.stitched_cameras.is_some()
+                || self.active_zone.is_some()
+                || self.roi_tracking.is_some();
+            if self.coord_reference == CoordReference::ModelCanvas && crops {
+                return Err(anyhow!(
+                    "Model canvas coordinates need a single pass over the whole frame, without tiling, stitched cameras, an active zone or ROI tracking"
+                ));
+            }
+            Ok(())
+        }
+
+        fn post_process(&mut self, hands: Option<Vec<HandDetails>>) -> Option<Vec<HandDetails>> {
+            let mut hands = match self.post_processor.as_mut() {
+                Some(post_processor) => {
+                    let hands = post_processor.process(hands.unwrap_or_default());
+                    (!hands.is_empty()).then_some(hands)?
+                }
+                None => hands?,
+            };
+            self.output_order.apply(&mut hands);
+            Some(hands)
+        }
+
+        fn detect_frame(
+            &mut self,
+            frame: &ImageBuffer<Rgb<u8>, Vec<u8>>,
+        ) -> Result<Option<Vec<HandDetails>>> {
+            let candidates = self.frame_candidates(frame)?;
+            Ok(self.select(candidates))
+        }
+
+        // Candidates of the whole frame, from the tiles or stitched cameras when set up
+        fn frame_candidates(
+            &mut self,
+            frame: &ImageBuffer<Rgb<u8>, Vec<u8>>,
+        ) -> Result<Vec<HandDetails>> {
+            if let Some(cameras) = self.stitched_cameras {
+                return self.stitched_candidates(frame, cameras);
+            }
+            match self.tiling {
+                Some(tiling) => self.tiled_candidates(frame, tiling),
+                None => self.candidates(preprocess(frame, &self.preprocess_config)),
+            }
+        }
+
+        // Detect on the zone's crop. The candidates are mapped back to the frame before
+        // select, so the border margin and the boxes kept for the hysteresis and ROI
+        // tracking are in frame space like the rest.
+        fn detect_in_zone(
+            &mut self,
+            frame: &ImageBuffer<Rgb<u8>, Vec<u8>>,
+            zone: Box,
+        ) -> Result<Option<Vec<HandDetails>>> {
+            let (frame_width, frame_height) = frame.dimensions();
+            let crop = zone_crop(zone, frame_width, frame_height);
+            let (x, y, width, height) = crop;
+
+            let zone_frame = image::imageops::crop_imm(frame, x, y, width, height).to_image();
+            let candidates = self.frame_candidates(&zone_frame)?;
+            let candidates =
+                crop_to_frame(candidates, crop, (frame_width, frame_height), Some(zone));
+            Ok(self.select(candidates))
+        }
+
+        // Detect in one pass over the crop around last frame's hands, skipping tiles
+        fn detect_in_roi(
+            &mut self,
+            frame: &ImageBuffer<Rgb<u8>, Vec<u8>>,
+            roi: Box,
+        ) -> Result<Option<Vec<HandDetails>>> {
+            let (frame_width, frame_height) = frame.dimensions();
+            let crop = zone_crop(roi, frame_width, frame_height);
+            let (x, y, width, height) = crop;
+
+            let roi_frame = image::imageops::crop_imm(frame, x, y, width, height).to_image();
+            let candidates = self.candidates(preprocess(&roi_frame, &self.preprocess_config))?;
+            // The ROI is kept inside the zone, but boxes cut by its edge can reach out of it
+            let candidates = crop_to_frame(
+                candidates,
+                crop,
+                (frame_width, frame_height),
+                self.active_zone,
+            );
+            Ok(self.select(candidates))
+        }
+
+        // The region to detect in this frame with ROI tracking, None for a full scan
+        fn next_roi(&mut self) -> Option<Box> {
+            let tracking = self.roi_tracking?;
+            let first = self.active_boxes.first()?;
+            self.frames_since_scan += 1;
+            if self.frames_since_scan >= tracking.rescan_every {
+                self.frames_since_scan = 0;
+                return None;
+            }
+
+            let union = self.active_boxes.iter().fold(*first, |union, b| Box {
+                xmin: union.xmin.min(b.xmin),
+                ymin: union.ymin.min(b.ymin),
+                xmax: union.xmax.max(b.xmax),
+                ymax: union.ymax.max(b.ymax),
+            });
+            let mut roi = union.expanded((tracking.expansion - 1.0) / 2.0).clamped();
+            if let Some(zone) = self.active_zone {
+                roi = Box {
+                    xmin: roi.xmin.max(zone.xmin),
+                    ymin: roi.ymin.max(zone.ymin),
+                    xmax: roi.xmax.min(zone.xmax),
+                    ymax: roi.ymax.min(zone.ymax),
+                };
+            }
+            // Too small to crop, e.g. a box squeezed against the frame edge
+            (roi.width() > 0.01 && roi.height() > 0.01).then_some(roi)
+        }
+
+        fn tiled_candidates(
+            &mut self,
+            frame: &ImageBuffer<Rgb<u8>, Vec<u8>>,
+            tiling: Tiling,
+        ) -> Result<Vec<HandDetails>> {
+            let (frame_width, frame_height) = frame.dimensions();
+
+            // The full frame pass keeps large hands that don't fit inside a single tile
+            let mut candidates = self.candidates(preprocess(frame, &self.preprocess_config))?;
+
+            for tile in tiling.tiles(frame_width, frame_height) {
+                let (x, y, width, height) = tile;
+                let tile_frame = image::imageops::crop_imm(frame, x, y, width, height).to_image();
+                let preprocessed = preprocess(&tile_frame, &self.preprocess_config);
+
+                for mut hand in self.candidates(preprocessed)? {
+                    tile_to_frame(&mut hand, tile, (frame_width, frame_height));
+                    candidates.push(hand);
+                }
+            }
+
+            // Global NMS in select merges duplicates across tile borders
+            Ok(candidates)
+        }
+
+        fn stitched_candidates(
+            &mut self,
+            frame: &ImageBuffer<Rgb<u8>, Vec<u8>>,
+            cameras: u32,
+        ) -> Result<Vec<HandDetails>> {
+            let (frame_width, frame_height) = frame.dimensions();
+            let cameras = cameras.min(frame_width);
+            let camera_width = frame_width / cameras;
+
+            let mut candidates = Vec::new();
+            for camera in 0..cameras {
+                let x = camera * camera_width;
+                // The last camera also gets the pixels left over by the division
+                let width = if camera + 1 == cameras {
+                    frame_width - x
+                } else {
+                    camera_width
+                };
+                let tile = (x, 0, width, frame_height);
+                let camera_frame =
+                    image::imageops::crop_imm(frame, x, 0, width, frame_height).to_image();
+                let mut hands =
+                    self.candidates(preprocess(&camera_frame, &self.preprocess_config))?;
+                for hand in hands.iter_mut() {
+                    tile_to_frame(hand, tile, (frame_width, frame_height));
+                    hand.camera = Some(camera as usize);
+                }
+                // Merge each camera's overlapping candidates first, so only one box per
+                // hand and side is left to match across the seam
+                candidates.extend(apply_nms(hands, self.palm_nms_threshold(), self.nms_mode));
+            }
+
+            for seam in 1..cameras {
+                let seam_x = (seam * camera_width) as f32 / frame_width as f32;
+                candidates = merge_across_seam(candidates, seam as usize, seam_x);
+            }
+
+            Ok(candidates)
+        }
+
+        // Run the model and decode every anchor scoring above the lower (off) threshold
+        fn candidates(&mut self, preprocessed: Preprocessed) -> Result<Vec<HandDetails>> {
+            let Preprocessed { input, letterbox } = preprocessed;
+            let (_, score_off) = self.score_thresholds();
+
+            let input_tensor = Value::from_array(input)?;
+
+            // Run Inference
+            let outputs = {
+                phase_span!("inference");
+                self.session.run(inputs!["image" => input_tensor])?
+            };
+
+            // Output processing
+            phase_span!("postprocess");
+            let (scores, coords) = output_arrays(&outputs)?;
+
+            check_output_shapes(scores.shape(), coords.shape(), self.anchors.len())?;
+            // The outputs borrow the session, so the settings are borrowed field by field
+            let decoding = Decoding {
+                anchors: &self.anchors,
+                offset_scale: self.offset_scale,
+                size_scale: self.size_scale,
+                activation: self.activation,
+                coord_reference: self.coord_reference,
+                score_off,
+            };
+            Ok(decoding.decode(&scores, &coords, letterbox))
+        }
+
+        // Apply the hysteresis and NMS to frame-space candidates and keep the best hands
+        fn select(&mut self, mut candidates: Vec<HandDetails>) -> Option<Vec<HandDetails>> {
+            phase_span!("postprocess");
+
+            // Below score_on only regions that were detected last frame are kept
+            let (score_on, _) = self.score_thresholds();
+            candidates.retain(|hand| {
+                hand.score > score_on
+                    || self
+                        .active_boxes
+                        .iter()
+                        .any(|b| intersection_over_union(b, &hand.bbox) > HYSTERESIS_IOU)
+            });
+
+            if let Some(threshold) = self.min_key_visibility {
+                candidates.retain(|hand| {
+                    [WRIST, INDEX_MCP]
+                        .iter()
+                        .all(|&k| hand.keypoints[k].visibility >= threshold)
+                });
+            }
+
+            if let Some(margin) = self.ignore_border {
+                candidates.retain(|hand| {
+                    let b = &hand.bbox;
+                    b.xmin >= margin
+                        && b.ymin >= margin
+                        && b.xmax <= 1.0 - margin
+                        && b.ymax <= 1.0 - margin
+                });
+            }
+
+            // Get best candidates based on Non-Maximum Suppression
+            let mut filtered_hands =
+                apply_nms(candidates, self.palm_nms_threshold(), self.nms_mode);
+
+            if self.clamp_boxes {
+                for hand in filtered_hands.iter_mut() {
+                    hand.bbox = hand.bbox.clamped();
+                }
+            }
+
+            if let Some(min_visibility) = self.box_fallback {
+                for hand in filtered_hands.iter_mut() {
+                    hand.estimate_from_box(min_visibility);
+                }
+            }
+
+            if let Some(adaptive) = self.adaptive.as_mut() {
+                adaptive.update(filtered_hands.len());
+            }
+
+            self.overflow = filtered_hands.len().saturating_sub(self.max_hands);
+            filtered_hands.truncate(self.max_hands);
+            self.active_boxes = filtered_hands.iter().map(|hand| hand.bbox).collect();
+
+            if filtered_hands.is_empty() {
+                if self.logging {
+                    println!("No hands detected");
+                }
+                None
+            } else {
+                // Return the top hands detected
+                Some(filtered_hands)
+            }
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        // Anchor at the center cell of the 32x32 map, centered at (0.515625, 0.515625)
+        const CENTER_ANCHOR: usize = (16 * 32 + 16) * 2;
+
+        // Raw model coords with `values` (box then keypoints, in input pixels) at one anchor
+        fn coords_at(anchor: usize, values: &[f32]) -> Array3<f32> {
+            let mut coords = Array3::zeros((1, NUM_ANCHORS, 4 + 2 * NUM_KEYPOINTS));
+            for (i, &value) in values.iter().enumerate() {
+                coords[[0, anchor, i]] = value;
+            }
+            coords
+        }
+
+        const MODEL_BYTES: &[u8] = include_bytes!("../models/MediaPipeHandDetector.onnx");
+
+        fn detector() -> HandDetector {
+            HandDetector::new_embedded(MODEL_BYTES).unwrap()
+        }
+
+        // The settings `candidates` decodes with
+        fn decoding(detector: &HandDetector) -> Decoding<'_> {
+            Decoding {
+                anchors: &detector.anchors,
+                offset_scale: detector.offset_scale,
+                size_scale: detector.size_scale,
+                activation: detector.activation,
+                coord_reference: detector.coord_reference,
+                score_off: detector.score_thresholds().1,
+            }
+        }
+
+        fn bbox(xmin: f32, ymin: f32, xmax: f32, ymax: f32) -> Box {
+            Box {
+                xmin,
+                ymin,
+                xmax,
+                ymax,
+            }
+        }
+
+        // A candidate with all palm keypoints at the box center
+        fn hand(score: f32, bbox: Box) -> HandDetails {
+            let (x, y) = bbox.center();
+            let keypoint = Landmark {
+                x,
+                y,
+                z: 0.0,
+                visibility: 1.0,
+                estimated: false,
+            };
+            HandDetails {
+                score,
+                bbox,
+                wrist: keypoint,
+                keypoints: vec![keypoint; NUM_KEYPOINTS],
+                landmarks: None,
+                landmark_indices: None,
+                handedness: None,
+                rotated_bbox: None,
+                camera: None,
+            }
+        }
+
+        fn in_frame(b: &Box) -> bool {
+            [b.xmin, b.ymin, b.xmax, b.ymax]
+                .iter()
+                .all(|v| (0.0..=1.0).contains(v))
+        }
+
+        #[test]
+        fn wrong_coord_scale_gives_out_of_frame_boxes() {
+            let anchors = generate_anchors(NUM_ANCHORS).unwrap();
+            // A 64 px box 16 px right of the anchor, regressed in input pixels
+            let coords = coords_at(CENTER_ANCHOR, &[16.0, 0.0, 64.0, 64.0]);
+
+            let right = get_bbox(
+                CENTER_ANCHOR,
+                &coords.view(),
+                &anchors,
+                INPUT_SIZE,
+                INPUT_SIZE,
+            );
+            assert!(in_frame(&right));
+            assert!((right.width() - 0.25).abs() < 1e-6);
+            assert!((right.center().0 - (0.515625 + 16.0 / INPUT_SIZE)).abs() < 1e-6);
+
+            // Read as already normalized, the same offsets land many frames away
+            let wrong = get_bbox(CENTER_ANCHOR, &coords.view(), &anchors, 1.0, 1.0);
+            assert!(!in_frame(&wrong));
+            assert!(wrong.xmin < -1.0 && wrong.xmax > 2.0);
+        }
+
+        #[test]
+        fn clamp_pulls_regressed_boxes_into_frame() {
+            let anchors = generate_anchors(NUM_ANCHORS).unwrap();
+            // The top left anchor, with its box regressed past the frame corner
+            let coords = coords_at(0, &[-16.0, -16.0, 64.0, 64.0]);
+            let regressed = get_bbox(0, &coords.view(), &anchors, INPUT_SIZE, INPUT_SIZE);
+            assert!(regressed.xmin < 0.0 && regressed.ymin < 0.0);
+
+            let clamped = regressed.clamped();
+            assert!(in_frame(&clamped));
+            assert_eq!((clamped.xmin, clamped.ymin), (0.0, 0.0));
+            assert_eq!(clamped.xmax, regressed.xmax);
+
+            // Swapped corners come out ordered
+            let swapped = bbox(0.6, 1.2, 0.4, 0.8).clamped();
+            assert_eq!(
+                (swapped.xmin, swapped.ymin, swapped.xmax, swapped.ymax),
+                (0.4, 0.8, 0.6, 1.0)
+            );
+        }
+
+        #[test]
+        fn detect_clamps_out_of_frame_candidates() {
+            let candidate = hand(2.0, bbox(-0.1, 0.5, 0.2, 1.15));
+
+            let mut unclamped = detector();
+            let hands = unclamped.select(vec![candidate.clone()]).unwrap();
+            assert!(!in_frame(&hands[0].bbox));
+
+            let mut clamped = detector().with_clamp_boxes(true);
+            let hands = clamped.select(vec![candidate]).unwrap();
+            assert!(in_frame(&hands[0].bbox));
+            assert_eq!(hands[0].bbox.xmin, 0.0);
+            assert_eq!(hands[0].bbox.ymax, 1.0);
+        }
+
+        #[test]
+        fn normalization_applies_mean_and_std() {
+            let config = PreprocessConfig {
+                mean: [0.485, 0.456, 0.406],
+                std: [0.229, 0.224, 0.225],
+                filter: FilterType::Nearest,
+                ..PreprocessConfig::default()
+            };
+            // Letterboxed into rows 64..192, with black bars above and below
+            let frame = ImageBuffer::from_pixel(256, 128, Rgb([200, 100, 50]));
+            let input = preprocess(&frame, &config).input;
+
+            for (c, pixel) in [200.0, 100.0, 50.0].into_iter().enumerate() {
+                let expected = (pixel / 255.0 - config.mean[c]) / config.std[c];
+                assert!((input[[0, c, 128, 10]] - expected).abs() < 1e-6);
+                // Bars are black pixels, normalized like any other
+                let bar = -config.mean[c] / config.std[c];
+                assert!((input[[0, c, 10, 10]] - bar).abs() < 1e-6);
+            }
+        }
+
+        #[test]
+        fn letterbox_round_trips_frame_edges() {
+            for (width, height) in [(640, 480), (480, 640), (256, 256), (1920, 1080), (1000, 3)] {
+                let letterbox = LetterboxTransform::fit(width, height, INPUT_SIZE as u32);
+                let layout = letterbox_layout(width, height, INPUT_SIZE as u32);
+                for (x, y) in [(0.0, 0.0), (1.0, 0.0), (0.0, 1.0), (1.0, 1.0), (0.5, 1.0)] {
+                    let (mx, my) = letterbox.to_model(x, y);
+                    assert!((0.0..=1.0).contains(&mx) && (0.0..=1.0).contains(&my));
+                    let (fx, fy) = letterbox.to_frame(mx, my);
+                    assert!(
+                        (fx - x).abs() < 1e-5 && (fy - y).abs() < 1e-5,
+                        "{}x{}",
+                        width,
+                        height
+                    );
+                }
+
+                // The frame's corners are the corners of the resized image on the canvas
+                let (left, top) = letterbox.to_model(0.0, 0.0);
+                let (right, bottom) = letterbox.to_model(1.0, 1.0);
+                assert_eq!((left * INPUT_SIZE).round() as u32, layout.left);
+                assert_eq!((top * INPUT_SIZE).round() as u32, layout.top);
+                assert_eq!(
+                    (right * INPUT_SIZE).round() as u32,
+                    layout.left + layout.width
+                );
+                assert_eq!(
+                    (bottom * INPUT_SIZE).round() as u32,
+                    layout.top + layout.height
+                );
+            }
+        }
+
+        #[test]
+        fn input_layouts_are_transposes() {
+            // A gradient, so every pixel and channel has its own value
+            let frame = ImageBuffer::from_fn(256, 256, |x, y| {
+                Rgb([x as u8, y as u8, (x as u8).wrapping_add(y as u8)])
+            });
+            let config = |layout| PreprocessConfig {
+                layout,
+                ..PreprocessConfig::default()
+            };
+            let nchw = preprocess(&frame, &config(InputLayout::Nchw)).input;
+            let nhwc = preprocess(&frame, &config(InputLayout::Nhwc)).input;
+
+            assert_eq!(nchw.shape(), &[1, 3, 256, 256]);
+            assert_eq!(nhwc.shape(), &[1, 256, 256, 3]);
+            assert_eq!(nchw.view().permuted_axes([0, 2, 3, 1]), nhwc);
+            assert_eq!(nhwc[[0, 10, 20, 0]], 20.0 / 255.0);
+        }
+
+        #[test]
+        fn empty_frames_are_rejected() {
+            for (width, height) in [(0, 0), (0, 480), (640, 0)] {
+                let frame = ImageBuffer::new(width, height);
+                assert!(check_frame(&frame).is_err());
+            }
+            assert!(check_frame(&ImageBuffer::new(1, 1)).is_ok());
+        }
+
+        #[test]
+        fn detect_fails_on_an_empty_frame() {
+            let mut detector = detector();
+            let frame = ImageBuffer::new(0, 480);
+            assert!(detector.detect(&frame).is_err());
+            assert!(detector.detect_raw(&frame).is_err());
+        }
+
+        // Two palms overlapping with IoU 0.5, e.g. clasped hands
+        fn clasped_hands() -> [HandDetails; 2] {
+            [
+                hand(2.0, bbox(0.2, 0.2, 0.5, 0.6)),
+                hand(1.8, bbox(0.3, 0.2, 0.6, 0.6)),
+            ]
+        }
+
+        fn with_handedness(
+            mut hands: [HandDetails; 2],
+            sides: [Handedness; 2],
+        ) -> Vec<HandDetails> {
+            for (hand, side) in hands.iter_mut().zip(sides) {
+                hand.handedness = Some((side, 0.9));
+            }
+            hands.to_vec()
+        }
+
+        #[test]
+        fn class_aware_nms_keeps_overlapping_left_and_right() {
+            let [a, b] = clasped_hands();
+            assert!((intersection_over_union(&a.bbox, &b.bbox) - 0.5).abs() < 1e-6);
+
+            let left_right =
+                with_handedness(clasped_hands(), [Handedness::Left, Handedness::Right]);
+            let agnostic = apply_nms(
+                left_right.clone(),
+                NMS_IOU_THRESHOLD,
+                NmsMode::ClassAgnostic,
+            );
+            assert_eq!(agnostic.len(), 1);
+            let aware = apply_nms(left_right, NMS_IOU_THRESHOLD, NmsMode::ClassAware);
+            assert_eq!(aware.len(), 2);
+
+            let right_right = with_handedness(clasped_hands(), [Handedness::Right; 2]);
+            let aware = apply_nms(right_right, NMS_IOU_THRESHOLD, NmsMode::ClassAware);
+            assert_eq!(aware.len(), 1);
+            assert_eq!(aware[0].score, 2.0);
+        }
+
+        #[test]
+        fn class_aware_detect_leaves_overlaps_to_the_landmarker() {
+            // Class-agnostic NMS merges the palms before handedness is known
+            let mut agnostic = detector();
+            assert_eq!(agnostic.select(clasped_hands().to_vec()).unwrap().len(), 1);
+            assert_eq!(
+                agnostic
+                    .nms_by_handedness(with_handedness(
+                        clasped_hands(),
+                        [Handedness::Left, Handedness::Right]
+                    ))
+                    .len(),
+                2
+            );
+
+            // Class-aware keeps both palms, then merges by the handedness filled in later
+            let mut aware = detector().with_nms_mode(NmsMode::ClassAware);
+            let palms = aware.select(clasped_hands().to_vec()).unwrap();
+            assert_eq!(palms.len(), 2);
+            let sides = [Handedness::Left, Handedness::Right];
+            let hands = aware.nms_by_handedness(with_handedness(clasped_hands(), sides));
+            assert_eq!(hands.len(), 2);
+            let hands =
+                aware.nms_by_handedness(with_handedness(clasped_hands(), [Handedness::Right; 2]));
+            assert_eq!(hands.len(), 1);
+        }
+
+        #[test]
+        fn deterministic_sessions_give_bit_identical_outputs() {
+            let mut detector =
+                HandDetector::new_embedded_with_config(MODEL_BYTES, SessionConfig::deterministic())
+                    .unwrap();
+            let frame = ImageBuffer::from_fn(320, 240, |x, y| {
+                Rgb([(x % 256) as u8, (y % 256) as u8, ((x * y) % 256) as u8])
+            });
+
+            let first = detector.detect_raw(&frame).unwrap();
+            let second = detector.detect_raw(&frame).unwrap();
+            let bits = |array: &Array3<f32>| array.iter().map(|v| v.to_bits()).collect::<Vec<_>>();
+            assert_eq!(bits(&first.scores), bits(&second.scores));
+            assert_eq!(bits(&first.coords), bits(&second.coords));
+        }
+
+        // Model outputs for a 64 px box 16 px right of the center anchor, as decoded for a
+        // 640x360 frame (letterboxed with 56 px bars above and below)
+        fn decode_center_box(detector: &HandDetector) -> Vec<HandDetailsPx> {
+            let mut scores = Array3::zeros((1, NUM_ANCHORS, 1));
+            scores[[0, CENTER_ANCHOR, 0]] = 5.0;
+            let coords = coords_at(CENTER_ANCHOR, &[16.0, 0.0, 64.0, 64.0]);
+            let hands = decoding(detector).decode(
+                &scores.view(),
+                &coords.view(),
+                LetterboxTransform::fit(640, 360, INPUT_SIZE as u32),
+            );
+            detector.hands_to_pixels(&hands, 640, 360)
+        }
+
+        #[test]
+        fn pixel_coordinates_land_on_the_hand_region() {
+            let hands = decode_center_box(&detector());
+            assert_eq!(hands.len(), 1);
+            let BoxPx {
+                xmin,
+                ymin,
+                xmax,
+                ymax,
+            } = hands[0].bbox;
+            // 0.25 of the 256 px input is 160 px of frame both ways, the bars are removed
+            assert_eq!((xmin, xmax), (290, 450));
+            assert_eq!((ymin, ymax), (110, 270));
+            assert_eq!((hands[0].wrist.x, hands[0].wrist.y), (330, 190));
+        }
+
+        #[test]
+        fn offset_scale_applies_to_offsets_and_size_scale_to_sizes() {
+            let anchors = generate_anchors(NUM_ANCHORS).unwrap();
+            // Offsets regressed for a 128 px input, sizes for 256 px, first keypoint 16 px left
+            let coords = coords_at(CENTER_ANCHOR, &[8.0, -8.0, 64.0, 32.0, -16.0, 0.0]);
+
+            let mixed = get_bbox(CENTER_ANCHOR, &coords.view(), &anchors, 128.0, INPUT_SIZE);
+            assert!((mixed.center().0 - (0.515625 + 0.0625)).abs() < 1e-6);
+            assert!((mixed.center().1 - (0.515625 - 0.0625)).abs() < 1e-6);
+            assert!((mixed.width() - 0.25).abs() < 1e-6);
+            assert!((mixed.height() - 0.125).abs() < 1e-6);
+
+            // The offset scale alone moves the center, not the size
+            let default = get_bbox(
+                CENTER_ANCHOR,
+                &coords.view(),
+                &anchors,
+                INPUT_SIZE,
+                INPUT_SIZE,
+            );
+            assert!((default.center().0 - (0.515625 + 0.03125)).abs() < 1e-6);
+            assert!((default.width() - mixed.width()).abs() < 1e-6);
+            assert!((default.height() - mixed.height()).abs() < 1e-6);
+
+            let keypoint = get_landmark(CENTER_ANCHOR, &coords.view(), &anchors, 4, 5, 128.0);
+            assert!((keypoint.x - (0.515625 - 0.125)).abs() < 1e-6);
+            assert!((keypoint.y - 0.515625).abs() < 1e-6);
+        }
+
+        #[test]
+        fn with_offset_scale_reaches_decoding() {
+            let mut scores = Array3::zeros((1, NUM_ANCHORS, 1));
+            scores[[0, CENTER_ANCHOR, 0]] = 5.0;
+            let coords = coords_at(CENTER_ANCHOR, &[8.0, 0.0, 64.0, 64.0]);
+            let square = LetterboxTransform::fit(256, 256, INPUT_SIZE as u32);
+
+            let detector = detector().with_offset_scale(128.0);
+            let hands = decoding(&detector).decode(&scores.view(), &coords.view(), square);
+            assert_eq!(hands.len(), 1);
+            assert!((hands[0].bbox.center().0 - (0.515625 + 0.0625)).abs() < 1e-6);
+            assert!((hands[0].bbox.width() - 0.25).abs() < 1e-6);
+        }
+
+        #[test]
+        fn ignore_border_drops_boxes_touching_the_frame_edge() {
+            let mut detector = detector().with_ignore_border(0.02);
+            let hands = detector
+                .select(vec![
+                    hand(2.0, bbox(0.0, 0.3, 0.2, 0.6)),
+                    hand(1.5, bbox(0.4, 0.3, 0.6, 0.6)),
+                ])
+                .unwrap();
+            assert_eq!(hands.len(), 1);
+            assert_eq!(hands[0].bbox.xmin, 0.4);
+        }
+
+        #[test]
+        fn ignore_border_uses_frame_edges_with_an_active_zone() {
+            // The middle half of a 640x360 frame, from its top to its bottom edge
+            let zone = bbox(0.25, 0.0, 0.75, 1.0);
+            let mut detector = detector()
+                .with_ignore_border(0.02)
+                .with_active_zone(zone)
+                .unwrap();
+            let crop = zone_crop(zone, 640, 360);
+            assert_eq!(crop, (160, 0, 320, 360));
+
+            // In crop coordinates: one at the crop's left edge, which is well inside the
+            // frame, and one at the crop's top edge, which is the frame's too
+            let candidates = vec![
+                hand(2.0, bbox(0.0, 0.3, 0.3, 0.6)),
+                hand(1.5, bbox(0.5, 0.0, 0.8, 0.2)),
+            ];
+            let hands = detector
+                .select(crop_to_frame(candidates, crop, (640, 360), Some(zone)))
+                .unwrap();
+            assert_eq!(hands.len(), 1);
+            assert_eq!(hands[0].bbox.xmin, 0.25);
+        }
+
+        #[test]
+        fn roi_tracking_follows_active_zone_hands_in_frame_space() {
+            // The right half of a 640x360 frame
+            let zone = bbox(0.5, 0.0, 1.0, 1.0);
+            let mut detector = detector()
+                .with_hysteresis(1.0, 0.5)
+                .unwrap()
+                .with_active_zone(zone)
+                .unwrap()
+                .with_roi_tracking(2.0, 10)
+                .unwrap();
+
+            // Frame 1, a full scan of the zone: the hand is at 0.2 - 0.4 of the crop's width,
+            // 0.6 - 0.7 of the frame's
+            assert!(detector.next_roi().is_none());
+            let crop = zone_crop(zone, 640, 360);
+            let candidates = vec![hand(2.0, bbox(0.2, 0.4, 0.4, 0.6))];
+            let hands = detector.select(crop_to_frame(candidates, crop, (640, 360), Some(zone)));
+            assert!((hands.unwrap()[0].bbox.xmin - 0.6).abs() < 1e-6);
+
+            // Frame 2 is detected around the hand's place in the frame, inside the zone
+            let roi = detector.next_roi().unwrap();
+            assert!(roi.contains(0.65, 0.5));
+            assert!(roi.xmin >= zone.xmin);
+
+            // A weaker detection of the same hand is kept by the hysteresis, one reaching
+            // out of the zone is dropped
+            let crop = zone_crop(roi, 640, 360);
+            let (x, y, width, height) = crop;
+            let to_crop = |fx: f32, fy: f32| {
+                (
+                    (fx * 640.0 - x as f32) / width as f32,
+                    (fy * 360.0 - y as f32) / height as f32,
+                )
+            };
+            let (xmin, ymin) = to_crop(0.61, 0.4);
+            let (xmax, ymax) = to_crop(0.71, 0.6);
+            let (out_xmin, _) = to_crop(0.4, 0.4);
+            let (out_xmax, _) = to_crop(0.52, 0.6);
+            let candidates = vec![
+                hand(0.8, bbox(xmin, ymin, xmax, ymax)),
+                hand(2.0, bbox(out_xmin, ymin, out_xmax, ymax)),
+            ];
+            let zone = detector.active_zone();
+            let hands = detector
+                .select(crop_to_frame(candidates, crop, (640, 360), zone))
+                .unwrap();
+            assert_eq!(hands.len(), 1);
+            assert!((hands[0].bbox.xmin - 0.61).abs() < 1e-3);
+        }
+
+        #[test]
+        fn coord_references_name_frame_and_canvas() {
+            assert_eq!(
+                CoordReference::from_name("frame"),
+                Some(CoordReference::Frame)
+            );
+            assert_eq!(
+                CoordReference::from_name("canvas"),
+                Some(CoordReference::ModelCanvas)
+            );
+            assert_eq!(CoordReference::from_name("model"), None);
+            assert_eq!(CoordReference::default(), CoordReference::Frame);
+        }
+
+        #[test]
+        fn model_canvas_coordinates_keep_the_letterbox() {
+            let mut detector = detector().with_coord_reference(CoordReference::ModelCanvas);
+            // The same outputs as pixel_coordinates_land_on_the_hand_region, on the
+            // 256x256 canvas with its 56 px bars instead of the 640x360 frame
+            let hands = decode_center_box(&detector);
+            assert_eq!(hands.len(), 1);
+            let BoxPx {
+                xmin,
+                ymin,
+                xmax,
+                ymax,
+            } = hands[0].bbox;
+            assert_eq!((xmin, xmax), (116, 180));
+            assert_eq!((ymin, ymax), (100, 164));
+            assert_eq!((hands[0].wrist.x, hands[0].wrist.y), (132, 132));
+
+            // Crops are mapped back through the frame, so they can't give canvas coordinates
+            detector = detector.with_tiles(2, 2, 0.2).unwrap();
+            assert!(detector.check_coord_reference().is_err());
+        }
+
+        #[test]
+        fn swapped_models_must_match_anchors_and_input() {
+            let input = [1, 256, 256, 3];
+            assert!(check_model_matches(2944, Some(2944), Some(&input), Some(&input)).is_ok());
+            // Unknown counts and dynamic dimensions aren't held against the model
+            assert!(
+                check_model_matches(2944, None, Some(&[-1, 256, 256, 3]), Some(&input)).is_ok()
+            );
+            assert!(check_model_matches(2944, Some(2944), None, Some(&input)).is_ok());
+
+            let error = check_model_matches(2944, Some(2016), Some(&input), Some(&input));
+            assert!(error.unwrap_err().to_string().contains("2016 anchors"));
+            // Another input size, layout or rank
+            assert!(
+                check_model_matches(2944, None, Some(&input), Some(&[1, 192, 192, 3])).is_err()
+            );
+            assert!(
+                check_model_matches(2944, None, Some(&input), Some(&[1, 3, 256, 256])).is_err()
+            );
+            assert!(check_model_matches(2944, None, Some(&input), Some(&[256, 256, 3])).is_err());
+        }
+
+        #[test]
+        fn adaptive_threshold_steps_towards_the_detection_range() {
+            let mut adaptive = AdaptiveThreshold {
+                min_detections: 1,
+                max_detections: 2,
+                min: 0.5,
+                max: 0.6,
+                threshold: 0.55,
+            };
+            let mut expect = |detections: usize, threshold: f32| {
+                adaptive.update(detections);
+                assert!(
+                    (adaptive.threshold - threshold).abs() < 1e-6,
+                    "{} detections: threshold {}, expected {}",
+                    detections,
+                    adaptive.threshold,
+                    threshold
+                );
+            };
+            // Too many hands raise it a step at a time, up to max
+            expect(3, 0.6);
+            expect(3, 0.6);
+            // Within the range it stays put
+            expect(1, 0.6);
+            expect(2, 0.6);
+            // Too few lower it, down to min
+            expect(0, 0.55);
+            expect(0, 0.5);
+            expect(0, 0.5);
+        }
+
+        #[test]
+        fn with_activation_converts_adaptive_bounds() {
+            let detector = detector()
+                .with_adaptive_threshold(1, 2, 0.0, 2.0)
+                .unwrap()
+                .with_activation(ActivationMode::Sigmoid);
+            let sigmoid = |x: f32| ActivationMode::Sigmoid.apply(x);
+            let (on, off) = detector.score_thresholds();
+            assert!((on - sigmoid(1.0)).abs() < 1e-6);
+            assert!((off - sigmoid(1.0)).abs() < 1e-6);
+
+            let adaptive = detector.adaptive.unwrap();
+            assert!((adaptive.min - 0.5).abs() < 1e-6);
+            assert!((adaptive.max - sigmoid(2.0)).abs() < 1e-6);
+        }
+
+        // Write `text` to a file of its own under the temp directory
+        fn anchors_file(name: &str, text: &str) -> std::path::PathBuf {
+            let path = std::env::temp_dir().join(format!(
+                "fingers-anchors-{}-{}.csv",
+                std::process::id(),
+                name
+            ));
+            std::fs::write(&path, text).unwrap();
+            path
+        }
+
+        #[test]
+        fn anchors_load_with_a_header_comments_and_whitespace() {
+            let path = anchors_file(
+                "header",
+                "# exported anchors\n\nx_center,y_center,w,h\n0.1,0.2,1,1\n# second\n0.3 0.4\t1 1\n",
+            );
+            let anchors = load_anchors(&path).unwrap();
+            std::fs::remove_file(&path).unwrap();
+            assert_eq!(anchors.len(), 2);
+            assert_eq!((anchors[0].x_center, anchors[0].y_center), (0.1, 0.2));
+            assert_eq!((anchors[1].x_center, anchors[1].y_center), (0.3, 0.4));
+            assert_eq!((anchors[1].w, anchors[1].h), (1.0, 1.0));
+        }
+
+        #[test]
+        fn bad_anchor_lines_report_their_line_number() {
+            // A bad row after the data
+            let path = anchors_file("bad-row", "x,y,w,h\n0.1,0.2,1,1\n0.3,oops,1,1\n");
+            let error = load_anchors(&path).err().unwrap().to_string();
+            std::fs::remove_file(&path).unwrap();
+            assert!(error.contains("line 3"), "{}", error);
+
+            // Only one header line is skipped, a second unparseable line is an error
+            let path = anchors_file(
+                "two-headers",
+                "# anchors\nx,y,w,h\nnot,an,anchor,row\n0.1,0.2,1,1\n",
+            );
+            let error = load_anchors(&path).err().unwrap().to_string();
+            std::fs::remove_file(&path).unwrap();
+            assert!(error.contains("line 3"), "{}", error);
+
+            // Too few values
+            let path = anchors_file("short", "0.1,0.2,1\n");
+            assert!(load_anchors(&path).is_err());
+            std::fs::remove_file(&path).unwrap();
+        }
+
+        #[test]
+        fn anchor_files_must_match_the_model() {
+            let path = anchors_file("count", "0.1,0.2,1,1\n0.3,0.4,1,1\n");
+            let error = detector()
+                .with_anchors_from_file(&path)
+                .err()
+                .unwrap()
+                .to_string();
+            std::fs::remove_file(&path).unwrap();
+            assert!(error.contains("has 2 anchors"), "{}", error);
+
+            let path = anchors_file("empty", "# nothing yet\n");
+            let error = detector()
+                .with_anchors_from_file(&path)
+                .err()
+                .unwrap()
+                .to_string();
+            std::fs::remove_file(&path).unwrap();
+            assert!(error.contains("has no anchors"), "{}", error);
+
+            let anchors: String = generate_anchors(NUM_ANCHORS)
+                .unwrap()
+                .iter()
+                .map(|a| format!("{},{},{},{}\n", a.x_center, a.y_center, a.w, a.h))
+                .collect();
+            let path = anchors_file("generated", &anchors);
+            let detector = detector().with_anchors_from_file(&path);
+            std::fs::remove_file(&path).unwrap();
+            assert_eq!(detector.unwrap().anchors.len(), NUM_ANCHORS);
+        }
+
+        // Decoded candidates of one inference on a square frame with a plain 64 px box at
+        // each (anchor, raw score)
+        fn decoded(detector: &HandDetector, boxes: &[(usize, f32)]) -> Vec<HandDetails> {
+            let mut scores = Array3::from_elem((1, NUM_ANCHORS, 1), -10.0);
+            let mut coords = Array3::zeros((1, NUM_ANCHORS, 4 + 2 * NUM_KEYPOINTS));
+            for &(anchor, score) in boxes {
+                scores[[0, anchor, 0]] = score;
+                coords[[0, anchor, 2]] = 64.0;
+                coords[[0, anchor, 3]] = 64.0;
+            }
+            let square = LetterboxTransform::fit(256, 256, INPUT_SIZE as u32);
+            decoding(detector).decode(&scores.view(), &coords.view(), square)
+        }
+
+        #[test]
+        fn hysteresis_keeps_weak_detections_where_hands_were() {
+            assert!(detector().with_hysteresis(0.5, 1.0).is_err());
+            assert!(detector().with_hysteresis(f32::NAN, 0.5).is_err());
+
+            let mut detector = detector().with_hysteresis(1.0, 0.5).unwrap();
+            // Between the thresholds: decoded, but not enough to start a detection
+            let weak = decoded(&detector, &[(CENTER_ANCHOR, 0.8)]);
+            assert_eq!(weak.len(), 1);
+            assert!(detector.select(weak).is_none());
+            // Below score_off it isn't even decoded
+            assert!(decoded(&detector, &[(CENTER_ANCHOR, 0.4)]).is_empty());
+
+            let strong = decoded(&detector, &[(CENTER_ANCHOR, 2.0)]);
+            assert_eq!(detector.select(strong).unwrap().len(), 1);
+
+            // Next frame, the same weak score at the same place is kept, one elsewhere isn't
+            let weak = decoded(&detector, &[(CENTER_ANCHOR, 0.8), (0, 0.8)]);
+            assert_eq!(weak.len(), 2);
+            let hands = detector.select(weak).unwrap();
+            assert_eq!(hands.len(), 1);
+            assert!((hands[0].bbox.center().0 - 0.515625).abs() < 1e-6);
+        }
+
+        fn three_hands() -> Vec<HandDetails> {
+            vec![
+                hand(2.0, bbox(0.1, 0.1, 0.3, 0.3)),
+                hand(3.0, bbox(0.4, 0.4, 0.6, 0.6)),
+                hand(2.5, bbox(0.7, 0.7, 0.9, 0.9)),
+            ]
+        }
+
+        #[test]
+        fn overflow_policies_handle_too_many_hands() {
+            for policy in [
+                OverflowPolicy::Truncate,
+                OverflowPolicy::Error,
+                OverflowPolicy::Flag,
+            ] {
+                let mut detector = detector().with_max_hands(2).with_overflow_policy(policy);
+                // The best two are kept with every policy
+                let hands = detector.select(three_hands()).unwrap();
+                let scores: Vec<f32> = hands.iter().map(|hand| hand.score).collect();
+                assert_eq!(scores, [3.0, 2.5]);
+
+                let checked = detector.check_overflow();
+                assert_eq!(checked.is_err(), policy == OverflowPolicy::Error);
+                if let Err(e) = checked {
+                    assert!(e.to_string().contains("3 hands"), "{}", e);
+                }
+                assert_eq!(detector.truncated(), policy == OverflowPolicy::Flag);
+
+                // A frame within max_hands clears the overflow
+                detector.select(three_hands()[..1].to_vec()).unwrap();
+                assert!(detector.check_overflow().is_ok());
+                assert!(!detector.truncated());
+            }
+        }
+
+        // A 0.1 wide hand centered at x with this handedness
+        fn hand_at(x: f32, handedness: Option<Handedness>) -> HandDetails {
+            let mut hand = hand(1.0 + x, bbox(x - 0.05, 0.4, x + 0.05, 0.6));
+            hand.handedness = handedness.map(|side| (side, 0.9));
+            hand
+        }
+
+        fn centers(hands: &[HandDetails]) -> Vec<f32> {
+            hands.iter().map(|hand| hand.bbox.center().0).collect()
+        }
+
+        #[test]
+        fn left_to_right_order_sorts_by_box_center() {
+            let mut hands = vec![hand_at(0.7, None), hand_at(0.2, None), hand_at(0.5, None)];
+            OutputOrder::LeftToRight.apply(&mut hands);
+            assert_eq!(centers(&hands), [0.2, 0.5, 0.7]);
+            OutputOrder::Score.apply(&mut hands);
+            assert_eq!(centers(&hands), [0.7, 0.5, 0.2]);
+        }
+
+        #[test]
+        fn handedness_order_puts_left_then_right_then_unknown() {
+            let mut hands = vec![
+                hand_at(0.9, None),
+                hand_at(0.6, Some(Handedness::Right)),
+                hand_at(0.3, None),
+                hand_at(0.8, Some(Handedness::Left)),
+                hand_at(0.1, Some(Handedness::Right)),
+            ];
+            OutputOrder::Handedness.apply(&mut hands);
+            // Hands of the same rank, including unknown ones, go left to right
+            assert_eq!(centers(&hands), [0.8, 0.1, 0.6, 0.3, 0.9]);
+        }
     }
 }
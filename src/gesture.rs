@@ -0,0 +1,780 @@
+pub mod gestures {
+    use crate::detector::hand_detector::{self, HandDetails, Landmark};
+    use crate::landmarker::hand_landmarker::{
+        INDEX_MCP, INDEX_PIP, INDEX_TIP, MIDDLE_PIP, MIDDLE_TIP, PINKY_PIP, PINKY_TIP, RING_PIP,
+        RING_TIP, THUMB_IP, THUMB_MCP, THUMB_TIP, WRIST,
+    };
+    use crate::tracker::hand_tracker::TrackedHand;
+    use std::collections::{HashMap, VecDeque};
+    use std::time::{Duration, Instant};
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum SwipeDirection {
+        Left,
+        Right,
+        Up,
+        Down,
+    }
+
+    // Hand poses recognized from a single frame's landmarks
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum StaticGesture {
+        None,
+        ThumbsUp,
+        ClosedFist,
+        OpenPalm,
+        Pointing,
+        Victory,
+    }
+
+    // Finger touching the thumb in a pinch
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    pub enum Finger {
+        Index,
+        Middle,
+        Ring,
+        Pinky,
+    }
+
+    impl Finger {
+        pub const ALL: [Finger; 4] = [Finger::Index, Finger::Middle, Finger::Ring, Finger::Pinky];
+
+        pub fn tip(self) -> usize {
+            match self {
+                Finger::Index => INDEX_TIP,
+                Finger::Middle => MIDDLE_TIP,
+                Finger::Ring => RING_TIP,
+                Finger::Pinky => PINKY_TIP,
+            }
+        }
+
+        pub fn name(self) -> &'static str {
+            match self {
+                Finger::Index => "index",
+                Finger::Middle => "middle",
+                Finger::Ring => "ring",
+                Finger::Pinky => "pinky",
+            }
+        }
+
+        pub fn from_name(name: &str) -> Option<Self> {
+            Self::ALL.into_iter().find(|finger| finger.name() == name)
+        }
+    }
+
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    pub enum GestureEvent {
+        // Thumb and a fingertip came together, at the given normalized position
+        PinchStart {
+            id: u32,
+            finger: Finger,
+            x: f32,
+            y: f32,
+        },
+        PinchEnd {
+            id: u32,
+            finger: Finger,
+        },
+        // Fast wrist movement in one direction
+        Swipe {
+            id: u32,
+            direction: SwipeDirection,
+        },
+        // Hand moved towards (> 1.0) or away from (< 1.0) the camera while pinching
+        Zoom {
+            id: u32,
+            factor: f32,
+        },
+        // A static pose has been held for the debounce period
+        Static {
+            id: u32,
+            gesture: StaticGesture,
+        },
+        // A hand has been tracked for presence_frames frames, at its wrist position
+        HandEntered {
+            id: u32,
+            x: f32,
+            y: f32,
+        },
+        // An entered hand has been missing for presence_frames frames, at its last wrist position
+        HandLeft {
+            id: u32,
+            x: f32,
+            y: f32,
+        },
+        // The clutch pose turned input control on or off (see GestureConfig::clutch)
+        ControlToggled {
+            id: u32,
+            enabled: bool,
+        },
+    }
+
+    // Names used to refer to gestures outside the code (e.g. in the bindings config)
+    pub const GESTURE_NAMES: &[&str] = &[
+        "pinch_start",
+        "pinch_end",
+        "middle_pinch_start",
+        "middle_pinch_end",
+        "ring_pinch_start",
+        "ring_pinch_end",
+        "pinky_pinch_start",
+        "pinky_pinch_end",
+        "swipe_left",
+        "swipe_right",
+        "swipe_up",
+        "swipe_down",
+        "zoom_in",
+        "zoom_out",
+        "thumbs_up",
+        "closed_fist",
+        "open_palm",
+        "pointing",
+        "victory",
+        "hand_entered",
+        "hand_left",
+        "control_on",
+        "control_off",
+    ];
+
+    impl GestureEvent {
+        // Tracking id of the hand the event is about
+        pub fn id(&self) -> u32 {
+            match *self {
+                GestureEvent::PinchStart { id, .. }
+                | GestureEvent::PinchEnd { id, .. }
+                | GestureEvent::Swipe { id, .. }
+                | GestureEvent::Zoom { id, .. }
+                | GestureEvent::Static { id, .. }
+                | GestureEvent::HandEntered { id, .. }
+                | GestureEvent::HandLeft { id, .. }
+                | GestureEvent::ControlToggled { id, .. } => id,
+            }
+        }
+
+        pub fn name(&self) -> &'static str {
+            match self {
+                GestureEvent::PinchStart { finger, .. } => match finger {
+                    Finger::Index => "pinch_start",
+                    Finger::Middle => "middle_pinch_start",
+                    Finger::Ring => "ring_pinch_start",
+                    Finger::Pinky => "pinky_pinch_start",
+                },
+                GestureEvent::PinchEnd { finger, .. } => match finger {
+                    Finger::Index => "pinch_end",
+                    Finger::Middle => "middle_pinch_end",
+                    Finger::Ring => "ring_pinch_end",
+                    Finger::Pinky => "pinky_pinch_end",
+                },
+                GestureEvent::Swipe { direction, .. } => match direction {
+                    SwipeDirection::Left => "swipe_left",
+                    SwipeDirection::Right => "swipe_right",
+                    SwipeDirection::Up => "swipe_up",
+                    SwipeDirection::Down => "swipe_down",
+                },
+                GestureEvent::Zoom { factor, .. } if *factor >= 1.0 => "zoom_in",
+                GestureEvent::Zoom { .. } => "zoom_out",
+                GestureEvent::Static { gesture, .. } => match gesture {
+                    StaticGesture::None => "none",
+                    StaticGesture::ThumbsUp => "thumbs_up",
+                    StaticGesture::ClosedFist => "closed_fist",
+                    StaticGesture::OpenPalm => "open_palm",
+                    StaticGesture::Pointing => "pointing",
+                    StaticGesture::Victory => "victory",
+                },
+                GestureEvent::HandEntered { .. } => "hand_entered",
+                GestureEvent::HandLeft { .. } => "hand_left",
+                GestureEvent::ControlToggled { enabled: true, .. } => "control_on",
+                GestureEvent::ControlToggled { enabled: false, .. } => "control_off",
+            }
+        }
+    }
+
+    // Consumer of gesture events (e.g. input emulation, logging, networking)
+    pub trait EventSink {
+        fn handle(&mut self, event: &GestureEvent) -> anyhow::Result<()>;
+    }
+
+    #[derive(Debug, Clone, Copy)]
+    pub struct GestureConfig {
+        // Thumb-fingertip distance relative to palm size to start / end a pinch
+        pub pinch_on: f32,
+        pub pinch_off: f32,
+        // Consecutive frames a pinch change must hold before it is reported
+        pub debounce_frames: u32,
+        // Normalized wrist travel within swipe_window that counts as a swipe
+        pub swipe_distance: f32,
+        pub swipe_window: Duration,
+        pub swipe_cooldown: Duration,
+        // Relative palm size change that emits a zoom step
+        pub zoom_step: f32,
+        // Fingertips less visible than this are ignored (avoids false pinches when occluded)
+        pub min_visibility: f32,
+        // Frames a hand must be seen (or missing) in a row before it enters (or leaves),
+        // so one-frame blips don't produce enter/leave pairs
+        pub presence_frames: u32,
+        // Pose that toggles input control on and off ("lifting the pen" to reposition the
+        // hand), None to keep control always on
+        pub clutch: Option<StaticGesture>,
+    }
+
+    impl Default for GestureConfig {
+        fn default() -> Self {
+            Self {
+                pinch_on: 0.35,
+                pinch_off: 0.5,
+                debounce_frames: 2,
+                swipe_distance: 0.25,
+                swipe_window: Duration::from_millis(300),
+                swipe_cooldown: Duration::from_millis(500),
+                zoom_step: 0.1,
+                min_visibility: 0.5,
+                presence_frames: 3,
+                clutch: None,
+            }
+        }
+    }
+
+    struct Debouncer {
+        required: u32,
+        count: u32,
+        active: bool,
+    }
+
+    impl Debouncer {
+        fn new(required: u32) -> Self {
+            Self {
+                required,
+                count: 0,
+                active: false,
+            }
+        }
+
+        // Returns the new state when a change has held for enough frames
+        fn update(&mut self, raw: bool) -> Option<bool> {
+            if raw == self.active {
+                self.count = 0;
+                return None;
+            }
+            self.count += 1;
+            if self.count >= self.required {
+                self.active = raw;
+                self.count = 0;
+                Some(raw)
+            } else {
+                None
+            }
+        }
+    }
+
+    struct PerHandGestures {
+        // Thumb-index pinch, which also drives zoom and blocks swipes
+        pinch: Debouncer,
+        // Thumb-middle, thumb-ring and thumb-pinky pinches
+        other_pinches: [(Finger, Debouncer); 3],
+        // Current stable pose, and a candidate pose with how many frames it has held
+        pose: StaticGesture,
+        pose_candidate: StaticGesture,
+        pose_frames: u32,
+        wrist_history: VecDeque<(Instant, f32, f32)>,
+        swipe_blocked_until: Option<Instant>,
+        zoom_baseline: Option<f32>,
+    }
+
+    // What a hand is doing right now (level state, e.g. "is it pinching?"), as opposed to
+    // the start/end transitions reported as GestureEvents. Debounced like the events.
+    #[derive(Debug, Clone, PartialEq)]
+    pub struct ActiveGestures {
+        pub id: u32,
+        // Fingers currently pinched against the thumb
+        pub pinches: Vec<Finger>,
+        // Current static pose (StaticGesture::None when there is none)
+        pub pose: StaticGesture,
+        // A thumb-index pinch zoom is in progress
+        pub zooming: bool,
+    }
+
+    impl ActiveGestures {
+        pub fn is_pinching(&self, finger: Finger) -> bool {
+            self.pinches.contains(&finger)
+        }
+    }
+
+    // Debounced presence of a tracked hand, for the enter/leave events
+    struct Presence {
+        entered: bool,
+        seen: u32,
+        missing: u32,
+        // Last known wrist position
+        x: f32,
+        y: f32,
+    }
+
+    pub struct HandGestureState {
+        config: GestureConfig,
+        hands: HashMap<u32, PerHandGestures>,
+        presence: HashMap<u32, Presence>,
+        // Whether gestures should drive input, toggled by the clutch pose
+        control_enabled: bool,
+    }
+
+    // Palm size (wrist to middle finger MCP), used to make distances scale invariant
+    fn palm_size(hand: &HandDetails) -> f32 {
+        let wrist = hand.keypoints[hand_detector::WRIST];
+        let middle = hand.keypoints[hand_detector::MIDDLE_MCP];
+        ((middle.x - wrist.x).powi(2) + (middle.y - wrist.y).powi(2)).sqrt()
+    }
+
+    // Thumb-fingertip distance relative to palm size, if both tips are reliably visible
+    fn pinch_ratio(hand: &HandDetails, finger: Finger, min_visibility: f32) -> Option<f32> {
+        let thumb = hand.landmark(THUMB_TIP)?;
+        let tip = hand.landmark(finger.tip())?;
+        if thumb.visibility < min_visibility || tip.visibility < min_visibility {
+            return None;
+        }
+        let distance = ((thumb.x - tip.x).powi(2) + (thumb.y - tip.y).powi(2)).sqrt();
+        let palm = palm_size(hand);
+        (palm > f32::EPSILON).then(|| distance / palm)
+    }
+
+    fn distance(a: Landmark, b: Landmark) -> f32 {
+        ((a.x - b.x).powi(2) + (a.y - b.y).powi(2)).sqrt()
+    }
+
+    // Debounced pinch state change of one finger, with separate on/off distances to avoid chatter
+    fn update_pinch(
+        debouncer: &mut Debouncer,
+        hand: &HandDetails,
+        finger: Finger,
+        config: &GestureConfig,
+    ) -> Option<bool> {
+        let raw = match pinch_ratio(hand, finger, config.min_visibility) {
+            Some(ratio) if debouncer.active => ratio < config.pinch_off,
+            Some(ratio) => ratio < config.pinch_on,
+            None => false,
+        };
+        debouncer.update(raw)
+    }
+
+    // Where a pinch happened: the fingertip, or the box center without landmarks
+    fn pinch_point(hand: &HandDetails, finger: Finger) -> (f32, f32) {
+        hand.landmark(finger.tip())
+            .map(|tip| (tip.x, tip.y))
+            .unwrap_or_else(|| hand.bbox.center())
+    }
+
+    // Where two hands are relative to each other, the basis for two-hand zoom and rotate
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    pub struct PairGeometry {
+        // Between the box centers, in normalized frame units (x and y are scaled by the
+        // frame's width and height, so this isn't a true distance on non-square frames)
+        pub distance: f32,
+        // Direction of the line from the first hand's center to the second's in radians,
+        // -PI to PI: 0 points right and, as y grows downwards, positive angles turn clockwise
+        pub angle: f32,
+        pub midpoint: (f32, f32),
+    }
+
+    pub fn hand_pair_geometry(a: &HandDetails, b: &HandDetails) -> PairGeometry {
+        let (ax, ay) = a.bbox.center();
+        let (bx, by) = b.bbox.center();
+        let (dx, dy) = (bx - ax, by - ay);
+        PairGeometry {
+            distance: (dx * dx + dy * dy).sqrt(),
+            angle: dy.atan2(dx),
+            midpoint: ((ax + bx) / 2.0, (ay + by) / 2.0),
+        }
+    }
+
+    // A finger is extended when its tip is clearly farther from the wrist than its PIP joint
+    fn finger_extended(landmarks: &[Landmark], tip: usize, pip: usize) -> bool {
+        tip_beyond_pip(landmarks[tip], landmarks[pip], landmarks[WRIST])
+    }
+
+    fn tip_beyond_pip(tip: Landmark, pip: Landmark, wrist: Landmark) -> bool {
+        distance(tip, wrist) > distance(pip, wrist) * 1.2
+    }
+
+    // Unit direction the index finger points in, from its knuckle (MCP) to its tip, in
+    // normalized frame coordinates (y grows downwards). Extending the ray from the tip
+    // along it gives what the user points at. None without those landmarks or when the
+    // index isn't extended (the other fingers don't matter).
+    pub fn pointing_direction(hand: &HandDetails) -> Option<(f32, f32)> {
+        let tip = hand.landmark(INDEX_TIP)?;
+        let mcp = hand.landmark(INDEX_MCP)?;
+        if !tip_beyond_pip(tip, hand.landmark(INDEX_PIP)?, hand.landmark(WRIST)?) {
+            return None;
+        }
+        let (dx, dy) = (tip.x - mcp.x, tip.y - mcp.y);
+        let length = (dx * dx + dy * dy).sqrt();
+        (length > f32::EPSILON).then(|| (dx / length, dy / length))
+    }
+
+    // Heuristic pose classifier over the 21 hand landmarks
+    pub fn classify_static(landmarks: &[Landmark]) -> StaticGesture {
+        let index = finger_extended(landmarks, INDEX_TIP, INDEX_PIP);
+        let middle = finger_extended(landmarks, MIDDLE_TIP, MIDDLE_PIP);
+        let ring = finger_extended(landmarks, RING_TIP, RING_PIP);
+        let pinky = finger_extended(landmarks, PINKY_TIP, PINKY_PIP);
+
+        // The thumb is extended when its tip is away from the index knuckle
+        let palm = distance(landmarks[WRIST], landmarks[INDEX_MCP]).max(f32::EPSILON);
+        let thumb = distance(landmarks[THUMB_TIP], landmarks[INDEX_MCP])
+            > distance(landmarks[THUMB_IP], landmarks[INDEX_MCP]) * 1.2
+            && distance(landmarks[THUMB_TIP], landmarks[INDEX_MCP]) > palm * 0.5;
+        // Image y grows downwards, so "up" means a smaller y
+        let thumb_up = landmarks[THUMB_MCP].y - landmarks[THUMB_TIP].y > palm * 0.5;
+
+        match (thumb, index, middle, ring, pinky) {
+            (true, false, false, false, false) if thumb_up => StaticGesture::ThumbsUp,
+            (false, false, false, false, false) => StaticGesture::ClosedFist,
+            (true, true, true, true, true) => StaticGesture::OpenPalm,
+            (_, true, false, false, false) => StaticGesture::Pointing,
+            (_, true, true, false, false) => StaticGesture::Victory,
+            _ => StaticGesture::None,
+        }
+    }
+
+    // Dominant direction of a wrist displacement, if it is long enough to be a swipe
+    fn swipe_direction(dx: f32, dy: f32, min_distance: f32) -> Option<SwipeDirection> {
+        if dx.abs() >= dy.abs() && dx.abs() > min_distance {
+            Some(if dx > 0.0 {
+                SwipeDirection::Right
+            } else {
+                SwipeDirection::Left
+            })
+        } else if dy.abs() > dx.abs() && dy.abs() > min_distance {
+            Some(if dy > 0.0 {
+                SwipeDirection::Down
+            } else {
+                SwipeDirection::Up
+            })
+        } else {
+            None
+        }
+    }
+
+    impl Default for HandGestureState {
+        fn default() -> Self {
+            Self::new(GestureConfig::default())
+        }
+    }
+
+    impl HandGestureState {
+        pub fn new(config: GestureConfig) -> Self {
+            Self {
+                config,
+                hands: HashMap::new(),
+                presence: HashMap::new(),
+                control_enabled: true,
+            }
+        }
+
+        // False while the clutch is released: detection and events go on, but gestures
+        // shouldn't move the cursor, click or trigger actions
+        pub fn control_enabled(&self) -> bool {
+            self.control_enabled
+        }
+
+        // The gestures each hand is holding as of the last update, by tracking id
+        pub fn gesture_state(&self) -> Vec<ActiveGestures> {
+            let mut active: Vec<ActiveGestures> = self
+                .hands
+                .iter()
+                .map(|(&id, state)| {
+                    let mut pinches = Vec::new();
+                    if state.pinch.active {
+                        pinches.push(Finger::Index);
+                    }
+                    pinches.extend(
+                        state
+                            .other_pinches
+                            .iter()
+                            .filter(|(_, pinch)| pinch.active)
+                            .map(|(finger, _)| *finger),
+                    );
+                    ActiveGestures {
+                        id,
+                        pinches,
+                        pose: state.pose,
+                        zooming: state.zoom_baseline.is_some(),
+                    }
+                })
+                .collect();
+            active.sort_by_key(|gestures| gestures.id);
+            active
+        }
+
+        // Forget every hand's gesture state machines (pinches, swipes, static poses) and
+        // presence, and turn control back on. No events are sent for the dropped state.
+        pub fn reset(&mut self) {
+            self.hands.clear();
+            self.presence.clear();
+            self.control_enabled = true;
+        }
+
+        // Enter/leave events, once a hand has been seen (or missing) for presence_frames
+        fn update_presence(&mut self, tracked: &[TrackedHand], events: &mut Vec<GestureEvent>) {
+            let required = self.config.presence_frames.max(1);
+
+            for hand in tracked {
+                let wrist = hand.details.wrist;
+                let presence = self.presence.entry(hand.id).or_insert(Presence {
+                    entered: false,
+                    seen: 0,
+                    missing: 0,
+                    x: wrist.x,
+                    y: wrist.y,
+                });
+                presence.seen += 1;
+                presence.missing = 0;
+                (presence.x, presence.y) = (wrist.x, wrist.y);
+                if !presence.entered && presence.seen >= required {
+                    presence.entered = true;
+                    events.push(GestureEvent::HandEntered {
+                        id: hand.id,
+                        x: wrist.x,
+                        y: wrist.y,
+                    });
+                }
+            }
+
+            self.presence.retain(|id, presence| {
+                if tracked.iter().any(|hand| hand.id == *id) {
+                    return true;
+                }
+                presence.seen = 0;
+                presence.missing += 1;
+                if presence.missing < required {
+                    return true;
+                }
+                if presence.entered {
+                    events.push(GestureEvent::HandLeft {
+                        id: *id,
+                        x: presence.x,
+                        y: presence.y,
+                    });
+                }
+                false
+            });
+        }
+
+        // Feed this frame's tracked hands, returning the gesture events they produced
+        pub fn update(&mut self, tracked: &[TrackedHand], now: Instant) -> Vec<GestureEvent> {
+            let mut events = Vec::new();
+            self.update_presence(tracked, &mut events);
+            let config = self.config;
+
+            // Drop state for hands that are no longer tracked, ending any held pinch
+            self.hands.retain(|id, state| {
+                let alive = tracked.iter().any(|hand| hand.id == *id);
+                if !alive {
+                    let held = std::iter::once((Finger::Index, &state.pinch))
+                        .chain(state.other_pinches.iter().map(|(f, d)| (*f, d)));
+                    for (finger, pinch) in held {
+                        if pinch.active {
+                            events.push(GestureEvent::PinchEnd { id: *id, finger });
+                        }
+                    }
+                }
+                alive
+            });
+
+            for hand in tracked {
+                let id = hand.id;
+                let state = self.hands.entry(id).or_insert_with(|| PerHandGestures {
+                    pinch: Debouncer::new(config.debounce_frames),
+                    other_pinches: [Finger::Middle, Finger::Ring, Finger::Pinky]
+                        .map(|finger| (finger, Debouncer::new(config.debounce_frames))),
+                    pose: StaticGesture::None,
+                    pose_candidate: StaticGesture::None,
+                    pose_frames: 0,
+                    wrist_history: VecDeque::new(),
+                    swipe_blocked_until: None,
+                    zoom_baseline: None,
+                });
+                let details = &hand.details;
+
+                // Pinches, each finger debounced on its own
+                let finger = Finger::Index;
+                match update_pinch(&mut state.pinch, details, finger, &config) {
+                    Some(true) => {
+                        let (x, y) = pinch_point(details, finger);
+                        events.push(GestureEvent::PinchStart { id, finger, x, y });
+                        state.zoom_baseline = Some(palm_size(details));
+                    }
+                    Some(false) => {
+                        events.push(GestureEvent::PinchEnd { id, finger });
+                        state.zoom_baseline = None;
+                    }
+                    None => {}
+                }
+                for (finger, pinch) in state.other_pinches.iter_mut() {
+                    let finger = *finger;
+                    match update_pinch(pinch, details, finger, &config) {
+                        Some(true) => {
+                            let (x, y) = pinch_point(details, finger);
+                            events.push(GestureEvent::PinchStart { id, finger, x, y });
+                        }
+                        Some(false) => events.push(GestureEvent::PinchEnd { id, finger }),
+                        None => {}
+                    }
+                }
+
+                // Zoom while pinching, relative to the palm size at pinch start
+                if let Some(baseline) = state.zoom_baseline {
+                    let size = palm_size(details);
+                    let factor = size / baseline;
+                    if baseline > f32::EPSILON && (factor - 1.0).abs() >= config.zoom_step {
+                        events.push(GestureEvent::Zoom { id, factor });
+                        state.zoom_baseline = Some(size);
+                    }
+                }
+
+                // Static poses, reported once when they have held for the debounce period
+                let pose = details
+                    .full_landmarks()
+                    .map(classify_static)
+                    .unwrap_or(StaticGesture::None);
+                if pose == state.pose_candidate {
+                    state.pose_frames += 1;
+                } else {
+                    state.pose_candidate = pose;
+                    state.pose_frames = 1;
+                }
+                if state.pose_frames >= config.debounce_frames && state.pose != pose {
+                    state.pose = pose;
+                    if pose != StaticGesture::None {
+                        events.push(GestureEvent::Static { id, gesture: pose });
+                    }
+                    if pose != StaticGesture::None && config.clutch == Some(pose) {
+                        self.control_enabled = !self.control_enabled;
+                        events.push(GestureEvent::ControlToggled {
+                            id,
+                            enabled: self.control_enabled,
+                        });
+                    }
+                }
+
+                // Swipe (only while not pinching, so drags don't register as swipes)
+                let wrist = details.wrist;
+                state.wrist_history.push_back((now, wrist.x, wrist.y));
+                while let Some(&(t, _, _)) = state.wrist_history.front() {
+                    if now.duration_since(t) > config.swipe_window {
+                        state.wrist_history.pop_front();
+                    } else {
+                        break;
+                    }
+                }
+
+                let blocked = state.swipe_blocked_until.is_some_and(|until| now < until);
+                if !state.pinch.active
+                    && !blocked
+                    && let Some(&(_, x0, y0)) = state.wrist_history.front()
+                    && let Some(direction) =
+                        swipe_direction(wrist.x - x0, wrist.y - y0, config.swipe_distance)
+                {
+                    events.push(GestureEvent::Swipe { id, direction });
+                    state.wrist_history.clear();
+                    state.swipe_blocked_until = Some(now + config.swipe_cooldown);
+                }
+            }
+
+            events
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        use crate::detector::hand_detector::Box;
+        use crate::sensor::webcam::{FrameSource, SyntheticSource};
+        use crate::tracker::hand_tracker::HandTracker;
+        use image::{ImageBuffer, Rgb};
+
+        const BLOB: Rgb<u8> = Rgb([224, 172, 138]);
+
+        // Stand-in for the detector: the box around the blob's pixels, wrist at its center
+        fn find_blob(frame: &ImageBuffer<Rgb<u8>, Vec<u8>>) -> Option<HandDetails> {
+            let (width, height) = (frame.width() as f32, frame.height() as f32);
+            let mut bbox: Option<Box> = None;
+            for (x, y, _) in frame
+                .enumerate_pixels()
+                .filter(|(_, _, pixel)| **pixel == BLOB)
+            {
+                let (x, y) = (x as f32 / width, y as f32 / height);
+                let b = bbox.get_or_insert(Box {
+                    xmin: x,
+                    ymin: y,
+                    xmax: x,
+                    ymax: y,
+                });
+                (b.xmin, b.ymin) = (b.xmin.min(x), b.ymin.min(y));
+                (b.xmax, b.ymax) = (b.xmax.max(x), b.ymax.max(y));
+            }
+            let bbox = bbox?;
+            let (x, y) = bbox.center();
+            let wrist = Landmark {
+                x,
+                y,
+                z: 0.0,
+                visibility: 1.0,
+                estimated: false,
+            };
+            Some(HandDetails {
+                score: 1.0,
+                bbox,
+                wrist,
+                keypoints: vec![wrist; 7],
+                landmarks: None,
+                landmark_indices: None,
+                handedness: None,
+                rotated_bbox: None,
+                camera: None,
+            })
+        }
+
+        #[test]
+        fn synthetic_sweep_is_tracked_and_swipes_right() {
+            // Left to right across the frame in 10 frames, at 30 fps
+            let mut source = SyntheticSource::linear(320, 240, (0.2, 0.5), (0.8, 0.5), 10)
+                .unwrap()
+                .with_color(BLOB)
+                .with_frame_count(10);
+            let mut tracker = HandTracker::new();
+            let mut gestures = HandGestureState::new(GestureConfig::default());
+            let start = Instant::now();
+
+            let mut frame_number = 0;
+            let mut ids = Vec::new();
+            let mut events = Vec::new();
+            loop {
+                let frame = match source.next_frame() {
+                    Ok(frame) => frame,
+                    Err(e) if e.is_end_of_stream() => break,
+                    Err(e) => panic!("{}", e),
+                };
+                let hand = find_blob(&frame).expect("blob in frame");
+                let (x, y) = source.position(frame_number);
+                let (cx, cy) = hand.bbox.center();
+                assert!((cx - x).abs() < 0.01 && (cy - y).abs() < 0.01);
+
+                let tracked = tracker.update(&[hand]);
+                ids.extend(tracked.iter().map(|hand| hand.id));
+                let now = start + Duration::from_millis(33 * frame_number);
+                events.extend(gestures.update(&tracked, now));
+                frame_number += 1;
+            }
+
+            assert_eq!(frame_number, 10);
+            // One hand, followed across every frame
+            assert_eq!(ids.len(), 10);
+            assert!(ids.iter().all(|&id| id == ids[0]));
+            let names: Vec<&str> = events.iter().map(GestureEvent::name).collect();
+            assert!(names.contains(&"hand_entered"));
+            assert_eq!(
+                names.iter().filter(|&&name| name == "swipe_right").count(),
+                1
+            );
+        }
+    }
+}
@@ -0,0 +1,113 @@
+pub mod occupancy {
+    use crate::detector::hand_detector::HandDetails;
+    use anyhow::{Result, anyhow};
+    use image::{ImageBuffer, Rgb};
+    use std::io::Write;
+    use std::path::Path;
+
+    // Default grid: 16 x 9 cells, square on a 16:9 frame
+    pub const DEFAULT_GRID: (usize, usize) = (16, 9);
+
+    // Heatmaps are exported as CSV or PNG, picked by the file extension
+    pub fn check_path(path: &Path) -> Result<()> {
+        match path.extension().and_then(|e| e.to_str()) {
+            Some("csv" | "png") => Ok(()),
+            _ => Err(anyhow!(
+                "Unsupported heatmap format for {} (use .csv or .png)",
+                path.display()
+            )),
+        }
+    }
+
+    // Counts of detected hand centroids binned into a cols x rows grid over the frame
+    pub struct OccupancyGrid {
+        cols: usize,
+        rows: usize,
+        counts: Vec<u64>,
+    }
+
+    // Black -> red -> yellow -> white ramp for 0.0 - 1.0
+    fn heat_color(t: f32) -> Rgb<u8> {
+        let t = t.clamp(0.0, 1.0) * 3.0;
+        let r = t.min(1.0);
+        let g = (t - 1.0).clamp(0.0, 1.0);
+        let b = (t - 2.0).clamp(0.0, 1.0);
+        Rgb([(r * 255.0) as u8, (g * 255.0) as u8, (b * 255.0) as u8])
+    }
+
+    impl OccupancyGrid {
+        pub fn new(cols: usize, rows: usize) -> Self {
+            let (cols, rows) = (cols.max(1), rows.max(1));
+            Self {
+                cols,
+                rows,
+                counts: vec![0; cols * rows],
+            }
+        }
+
+        pub fn add(&mut self, hand: &HandDetails) {
+            let (x, y) = hand.bbox.center();
+            if !(0.0..=1.0).contains(&x) || !(0.0..=1.0).contains(&y) {
+                return;
+            }
+            let col = ((x * self.cols as f32) as usize).min(self.cols - 1);
+            let row = ((y * self.rows as f32) as usize).min(self.rows - 1);
+            self.counts[row * self.cols + col] += 1;
+        }
+
+        pub fn add_hands(&mut self, hands: &[HandDetails]) {
+            for hand in hands {
+                self.add(hand);
+            }
+        }
+
+        pub fn count(&self, col: usize, row: usize) -> u64 {
+            self.counts[row * self.cols + col]
+        }
+
+        pub fn total(&self) -> u64 {
+            self.counts.iter().sum()
+        }
+
+        pub fn reset(&mut self) {
+            self.counts.fill(0);
+        }
+
+        // One line per grid row, comma-separated counts
+        pub fn write_csv<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+            let mut file = std::io::BufWriter::new(std::fs::File::create(path)?);
+            for row in self.counts.chunks(self.cols) {
+                let line: Vec<String> = row.iter().map(|c| c.to_string()).collect();
+                writeln!(file, "{}", line.join(","))?;
+            }
+            Ok(())
+        }
+
+        // Heatmap image with each cell drawn as a cell_size x cell_size square
+        pub fn write_png<P: AsRef<Path>>(&self, path: P, cell_size: u32) -> Result<()> {
+            let max = self.counts.iter().copied().max().unwrap_or(0).max(1) as f32;
+            let cell_size = cell_size.max(1);
+
+            let image = ImageBuffer::from_fn(
+                self.cols as u32 * cell_size,
+                self.rows as u32 * cell_size,
+                |x, y| {
+                    let count = self.count((x / cell_size) as usize, (y / cell_size) as usize);
+                    heat_color(count as f32 / max)
+                },
+            );
+            image.save(path)?;
+            Ok(())
+        }
+
+        // Export based on the file extension (.csv or .png)
+        pub fn save<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+            let path = path.as_ref();
+            check_path(path)?;
+            match path.extension().and_then(|e| e.to_str()) {
+                Some("csv") => self.write_csv(path),
+                _ => self.write_png(path, 32),
+            }
+        }
+    }
+}
@@ -0,0 +1,308 @@
+pub mod hand_landmarker {
+    use crate::detector::hand_detector::{
+        self, HandDetails, Handedness, Landmark, RotatedBox, SessionConfig,
+    };
+    use anyhow::{Result, anyhow};
+    use image::{ImageBuffer, Rgb};
+    use ndarray::Array4;
+    use ort::{inputs, session::Session, value::Value};
+    use std::path::Path;
+
+    const INPUT_SIZE: usize = 224;
+
+    // Landmarks of one hand and its handedness (with confidence)
+    type ModelOutput = (Vec<Landmark>, Option<(Handedness, f32)>);
+    pub const NUM_LANDMARKS: usize = 21;
+
+    // MediaPipe crops the palm region, shifted towards the fingers and enlarged to fit the whole hand
+    const CROP_SHIFT: f32 = 0.5;
+    const CROP_SCALE: f32 = 2.6;
+
+    // Hand landmark indices (MediaPipe 21-point topology)
+    pub const WRIST: usize = 0;
+    pub const THUMB_CMC: usize = 1;
+    pub const THUMB_MCP: usize = 2;
+    pub const THUMB_IP: usize = 3;
+    pub const THUMB_TIP: usize = 4;
+    pub const INDEX_MCP: usize = 5;
+    pub const INDEX_PIP: usize = 6;
+    pub const INDEX_DIP: usize = 7;
+    pub const INDEX_TIP: usize = 8;
+    pub const MIDDLE_MCP: usize = 9;
+    pub const MIDDLE_PIP: usize = 10;
+    pub const MIDDLE_DIP: usize = 11;
+    pub const MIDDLE_TIP: usize = 12;
+    pub const RING_MCP: usize = 13;
+    pub const RING_PIP: usize = 14;
+    pub const RING_DIP: usize = 15;
+    pub const RING_TIP: usize = 16;
+    pub const PINKY_MCP: usize = 17;
+    pub const PINKY_PIP: usize = 18;
+    pub const PINKY_DIP: usize = 19;
+    pub const PINKY_TIP: usize = 20;
+
+    pub struct HandLandmarker {
+        session: Session,
+        presence_threshold: f32,
+        // Only these landmarks are extracted (None: all 21)
+        indices: Option<Vec<usize>>,
+    }
+
+    // Rotated square crop around a hand, in frame pixels
+    struct HandCrop {
+        center_x: f32,
+        center_y: f32,
+        side: f32,
+        // Unit vector pointing from the wrist towards the fingers ("up" in the crop)
+        up: (f32, f32),
+    }
+
+    impl HandCrop {
+        fn from_palm(hand: &HandDetails, frame_width: f32, frame_height: f32) -> Self {
+            let (cx, cy) = hand.bbox.center();
+            let palm_size =
+                (hand.bbox.width() * frame_width).max(hand.bbox.height() * frame_height);
+
+            // Rotate the crop so the wrist -> middle finger direction points up
+            let wrist = hand.keypoints[hand_detector::WRIST];
+            let middle = hand.keypoints[hand_detector::MIDDLE_MCP];
+            let dx = (middle.x - wrist.x) * frame_width;
+            let dy = (middle.y - wrist.y) * frame_height;
+            let length = (dx * dx + dy * dy).sqrt();
+            let up = if length > f32::EPSILON {
+                (dx / length, dy / length)
+            } else {
+                (0.0, -1.0)
+            };
+
+            Self {
+                center_x: cx * frame_width + up.0 * CROP_SHIFT * palm_size,
+                center_y: cy * frame_height + up.1 * CROP_SHIFT * palm_size,
+                side: palm_size * CROP_SCALE,
+                up,
+            }
+        }
+
+        // Map crop-local coordinates (0.0 to 1.0, y pointing down) to frame pixels
+        fn to_frame(&self, u: f32, v: f32) -> (f32, f32) {
+            let right = (-self.up.1, self.up.0);
+            let lx = (u - 0.5) * self.side;
+            let ly = (v - 0.5) * self.side;
+            (
+                self.center_x + lx * right.0 - ly * self.up.0,
+                self.center_y + lx * right.1 - ly * self.up.1,
+            )
+        }
+    }
+
+    // Box around the landmarks, oriented wrist -> middle finger MCP (from the landmarks if
+    // they include both, the palm keypoints otherwise)
+    fn rotated_box(hand: &HandDetails, frame_width: f32, frame_height: f32) -> Option<RotatedBox> {
+        let landmarks = hand.landmarks.as_ref()?;
+        let (wrist, middle) = match (hand.landmark(WRIST), hand.landmark(MIDDLE_MCP)) {
+            (Some(wrist), Some(middle)) => (wrist, middle),
+            _ => (
+                hand.keypoints[hand_detector::WRIST],
+                hand.keypoints[hand_detector::MIDDLE_MCP],
+            ),
+        };
+        let up = (
+            (middle.x - wrist.x) * frame_width,
+            (middle.y - wrist.y) * frame_height,
+        );
+        RotatedBox::fit(landmarks, up, frame_width, frame_height)
+    }
+
+    fn sample_bilinear(frame: &ImageBuffer<Rgb<u8>, Vec<u8>>, x: f32, y: f32) -> [f32; 3] {
+        let (width, height) = frame.dimensions();
+        let x0 = x.floor();
+        let y0 = y.floor();
+        let fx = x - x0;
+        let fy = y - y0;
+
+        // Pixels outside the frame are treated as black
+        let pixel = |px: f32, py: f32| -> [f32; 3] {
+            if px < 0.0 || py < 0.0 || px >= width as f32 || py >= height as f32 {
+                [0.0; 3]
+            } else {
+                let rgb = frame.get_pixel(px as u32, py as u32);
+                [rgb[0] as f32, rgb[1] as f32, rgb[2] as f32]
+            }
+        };
+
+        let p00 = pixel(x0, y0);
+        let p10 = pixel(x0 + 1.0, y0);
+        let p01 = pixel(x0, y0 + 1.0);
+        let p11 = pixel(x0 + 1.0, y0 + 1.0);
+
+        let mut out = [0.0; 3];
+        for c in 0..3 {
+            let top = p00[c] * (1.0 - fx) + p10[c] * fx;
+            let bottom = p01[c] * (1.0 - fx) + p11[c] * fx;
+            out[c] = top * (1.0 - fy) + bottom * fy;
+        }
+        out
+    }
+
+    impl HandLandmarker {
+        pub fn new<P: AsRef<Path>>(model_path: P) -> Result<Self> {
+            // Create new session for model
+            let session = SessionConfig::default()
+                .builder()?
+                .commit_from_file(model_path)?;
+
+            Ok(Self {
+                session,
+                presence_threshold: 0.5,
+                indices: None,
+            })
+        }
+
+        pub fn new_embedded(model_bytes: &[u8]) -> Result<Self> {
+            Self::new_embedded_with_config(model_bytes, SessionConfig::default())
+        }
+
+        pub fn new_embedded_with_config(model_bytes: &[u8], config: SessionConfig) -> Result<Self> {
+            // Create new session for model
+            let session = config.builder()?.commit_from_memory(model_bytes)?;
+
+            Ok(Self {
+                session,
+                presence_threshold: 0.5,
+                indices: None,
+            })
+        }
+
+        // Only extract these landmarks (e.g. just the five fingertips), in this order.
+        // HandDetails then carries just those, see `HandDetails::landmark`. Static poses
+        // and the skeleton need all 21, so they are skipped for hands with a subset.
+        pub fn with_landmark_indices(mut self, indices: &[usize]) -> Result<Self> {
+            if let Some(&index) = indices.iter().find(|&&i| i >= NUM_LANDMARKS) {
+                return Err(anyhow!(
+                    "Landmark index {} is out of range (0 to {})",
+                    index,
+                    NUM_LANDMARKS - 1
+                ));
+            }
+            self.indices = Some(indices.to_vec());
+            Ok(self)
+        }
+
+        pub fn landmark_indices(&self) -> Option<&[usize]> {
+            self.indices.as_deref()
+        }
+
+        // Run the landmark model on a palm detection, returning the 21 landmarks (or the
+        // requested subset) normalized to the frame (0.0 to 1.0), or None if the model sees
+        // no hand in the crop
+        pub fn landmarks(
+            &mut self,
+            frame: &ImageBuffer<Rgb<u8>, Vec<u8>>,
+            hand: &HandDetails,
+        ) -> Result<Option<Vec<Landmark>>> {
+            Ok(self.run(frame, hand)?.map(|(landmarks, _)| landmarks))
+        }
+
+        // Landmarks plus the handedness, if the model outputs it
+        fn run(
+            &mut self,
+            frame: &ImageBuffer<Rgb<u8>, Vec<u8>>,
+            hand: &HandDetails,
+        ) -> Result<Option<ModelOutput>> {
+            let (frame_width, frame_height) = frame.dimensions();
+            let (frame_width, frame_height) = (frame_width as f32, frame_height as f32);
+            let crop = HandCrop::from_palm(hand, frame_width, frame_height);
+
+            // Convert the rotated crop to a tensor [1, 224, 224, 3] (NHWC) in 0.0-1.0
+            let mut input = Array4::<f32>::zeros((1, INPUT_SIZE, INPUT_SIZE, 3));
+            for v in 0..INPUT_SIZE {
+                for u in 0..INPUT_SIZE {
+                    let (x, y) = crop.to_frame(
+                        (u as f32 + 0.5) / INPUT_SIZE as f32,
+                        (v as f32 + 0.5) / INPUT_SIZE as f32,
+                    );
+                    let rgb = sample_bilinear(frame, x, y);
+                    for c in 0..3 {
+                        input[[0, v, u, c]] = rgb[c] / 255.0;
+                    }
+                }
+            }
+
+            let input_tensor = Value::from_array(input)?;
+
+            // Run Inference
+            let outputs = self.session.run(inputs!["input_1" => input_tensor])?;
+
+            // Output processing
+            // Identity: 21 x (x, y, z) in crop pixels, Identity_1: hand presence score,
+            // Identity_2: probability of a right hand
+            let (_, landmarks_data) = outputs["Identity"].try_extract_tensor::<f32>()?;
+            let (_, presence_data) = outputs["Identity_1"].try_extract_tensor::<f32>()?;
+
+            if presence_data[0] < self.presence_threshold {
+                return Ok(None);
+            }
+
+            let handedness = match outputs.get("Identity_2") {
+                Some(output) => {
+                    let (_, handedness_data) = output.try_extract_tensor::<f32>()?;
+                    handedness_data.first().map(|&right| {
+                        if right >= 0.5 {
+                            (Handedness::Right, right)
+                        } else {
+                            (Handedness::Left, 1.0 - right)
+                        }
+                    })
+                }
+                None => None,
+            };
+
+            // Landmarks outside the frame can't be seen, the rest get the hand presence score
+            let presence = presence_data[0];
+            let all: Vec<usize>;
+            let indices = match &self.indices {
+                Some(indices) => indices.as_slice(),
+                None => {
+                    all = (0..NUM_LANDMARKS).collect();
+                    &all
+                }
+            };
+            let landmarks = indices
+                .iter()
+                .map(|&i| {
+                    let u = landmarks_data[i * 3] / INPUT_SIZE as f32;
+                    let v = landmarks_data[i * 3 + 1] / INPUT_SIZE as f32;
+                    let (x, y) = crop.to_frame(u, v);
+                    let (x, y) = (x / frame_width, y / frame_height);
+                    let in_frame = (0.0..=1.0).contains(&x) && (0.0..=1.0).contains(&y);
+                    Landmark {
+                        x,
+                        y,
+                        // Depth is in crop pixels, scale it like x (relative to frame width)
+                        z: landmarks_data[i * 3 + 2] / INPUT_SIZE as f32 * crop.side / frame_width,
+                        visibility: if in_frame { presence } else { 0.0 },
+                        estimated: false,
+                    }
+                })
+                .collect();
+
+            Ok(Some((landmarks, handedness)))
+        }
+
+        // Fill in the landmarks of every detected hand
+        pub fn refine(
+            &mut self,
+            frame: &ImageBuffer<Rgb<u8>, Vec<u8>>,
+            hands: &mut [HandDetails],
+        ) -> Result<()> {
+            for hand in hands.iter_mut() {
+                let (landmarks, handedness) = self.run(frame, hand)?.unzip();
+                hand.landmarks = landmarks;
+                hand.landmark_indices = self.indices.clone();
+                hand.handedness = handedness.flatten();
+                hand.rotated_bbox = rotated_box(hand, frame.width() as f32, frame.height() as f32);
+            }
+            Ok(())
+        }
+    }
+}
@@ -0,0 +1,115 @@
+pub mod detection_log {
+    use crate::detector::hand_detector::HandDetails;
+    use anyhow::Result;
+    use serde::Serialize;
+    use std::fs::File;
+    use std::io::{BufWriter, Write};
+    use std::path::Path;
+    use std::time::{SystemTime, UNIX_EPOCH};
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+    pub enum LogMode {
+        // Every field of every hand (boxes, keypoints, landmarks, ...)
+        #[default]
+        Full,
+        // Only each hand's score and box center, a few dozen bytes per hand
+        Minimal,
+    }
+
+    #[derive(Serialize)]
+    struct MinimalHand {
+        score: f32,
+        x: f32,
+        y: f32,
+    }
+
+    #[derive(Serialize)]
+    #[serde(untagged)]
+    enum LoggedHands<'a> {
+        Full(&'a [HandDetails]),
+        Minimal(Vec<MinimalHand>),
+    }
+
+    #[derive(Serialize)]
+    struct LogLine<'a> {
+        frame: u64,
+        // When the frame was captured, in ms since the Unix epoch
+        timestamp_ms: u128,
+        hands: LoggedHands<'a>,
+    }
+
+    // Writes detections to a JSON Lines file, one frame per line:
+    //   {"frame":12,"timestamp_ms":1760400000000,"hands":[...]}
+    // For long sessions, `with_every` only logs every Nth frame and LogMode::Minimal
+    // shrinks each hand to {"score","x","y"} (box center, normalized).
+    pub struct DetectionLogger {
+        writer: BufWriter<File>,
+        every: u64,
+        mode: LogMode,
+    }
+
+    impl DetectionLogger {
+        pub fn create<P: AsRef<Path>>(path: P) -> Result<Self> {
+            Ok(Self {
+                writer: BufWriter::new(File::create(path)?),
+                every: 1,
+                mode: LogMode::Full,
+            })
+        }
+
+        // Only log frames whose number is a multiple of `every` (1 logs all of them)
+        pub fn with_every(mut self, every: u64) -> Self {
+            self.every = every.max(1);
+            self
+        }
+
+        pub fn with_mode(mut self, mode: LogMode) -> Self {
+            self.mode = mode;
+            self
+        }
+
+        // Log a frame's hands, unless decimation skips it. Returns whether it was written.
+        pub fn log(
+            &mut self,
+            frame: u64,
+            captured: SystemTime,
+            hands: &[HandDetails],
+        ) -> Result<bool> {
+            if !frame.is_multiple_of(self.every) {
+                return Ok(false);
+            }
+            let hands = match self.mode {
+                LogMode::Full => LoggedHands::Full(hands),
+                LogMode::Minimal => LoggedHands::Minimal(
+                    hands
+                        .iter()
+                        .map(|hand| {
+                            let (x, y) = hand.bbox.center();
+                            MinimalHand {
+                                score: hand.score,
+                                x,
+                                y,
+                            }
+                        })
+                        .collect(),
+                ),
+            };
+            let line = LogLine {
+                frame,
+                timestamp_ms: captured
+                    .duration_since(UNIX_EPOCH)
+                    .map(|d| d.as_millis())
+                    .unwrap_or(0),
+                hands,
+            };
+            serde_json::to_writer(&mut self.writer, &line)?;
+            self.writer.write_all(b"\n")?;
+            Ok(true)
+        }
+
+        pub fn flush(&mut self) -> Result<()> {
+            self.writer.flush()?;
+            Ok(())
+        }
+    }
+}
@@ -2,64 +2,504 @@
 
 use enigo::Mouse;
 use minifb::{Key, Window, WindowOptions};
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
+mod cli;
+use cli::args;
 mod sensor;
 use sensor::webcam;
 mod detector;
 use detector::hand_detector;
 mod controller;
 use controller::input_device;
+mod landmarker;
+use landmarker::hand_landmarker;
+mod tracker;
+use tracker::hand_tracker;
+mod gesture;
+use gesture::gestures::{self, EventSink};
+mod bindings;
+use bindings::gesture_bindings;
+mod pipeline;
+use pipeline::frame_pipeline;
+mod heatmap;
+use heatmap::occupancy;
+mod snapshot;
+use snapshot::frame_snapshot;
+mod smoothing;
+use smoothing::filters;
+mod selftest;
+use selftest::health_check;
+mod metrics;
+use metrics::frame_metrics;
+mod viz;
+use viz::overlay;
+mod motion;
+use motion::motion_gate;
+mod recorder;
+use recorder::gesture_recorder;
+mod changes;
+use changes::detection_changes;
+#[cfg(feature = "json")]
+mod mediapipe;
+#[cfg(feature = "json")]
+use mediapipe::mediapipe_json;
+#[cfg(feature = "json")]
+mod logger;
+#[cfg(feature = "json")]
+use logger::detection_log;
+#[cfg(feature = "screen")]
+mod screen;
+#[cfg(feature = "screen")]
+use screen::screen_capture;
+#[cfg(feature = "http")]
+mod server;
+#[cfg(feature = "http")]
+use server::detection_server;
+#[cfg(feature = "clipboard")]
+mod clipboard;
+#[cfg(feature = "clipboard")]
+use clipboard::detection_clipboard;
+#[cfg(feature = "tui")]
+mod dashboard;
+#[cfg(feature = "tui")]
+use dashboard::tui_dashboard;
 
 const MODEL_BYTES: &[u8] = include_bytes!("../models/MediaPipeHandDetector.onnx");
+const LANDMARK_MODEL_BYTES: &[u8] =
+    include_bytes!("../models/gesture_model/hand_landmarks_detector.onnx");
 const RED: u32 = 0xFF0000;
 const GREEN: u32 = 0x00FF00;
 const BLUE: u32 = 0x0000FF;
+const WHITE: u32 = 0xFFFFFF;
+const MAX_RECONNECT_ATTEMPTS: u32 = 5;
+// Failed window updates in a row before the window is recreated, and before giving up
+const WINDOW_RECREATE_AFTER: u32 = 3;
+const MAX_WINDOW_FAILURES: u32 = 10;
+
+fn create_window(title: &str, width: usize, height: usize, fps: u32) -> anyhow::Result<Window> {
+    let mut window = Window::new(title, width, height, WindowOptions::default())?;
+
+    // Limit fps to reduce CPU usage and potential instability
+    let duration_per_frame = Duration::from_micros(1000000 / fps as u64);
+    window.limit_update_rate(Some(duration_per_frame));
+    Ok(window)
+}
 
 fn main() -> anyhow::Result<()> {
-    // Start camera
-    let mut camera = webcam::setup()?;
-    camera.open_stream()?;
+    let options = args::parse()?;
+
+    // List camera formats and exit
+    if let Some(index) = options.list_formats {
+        for format in webcam::supported_formats(index)? {
+            println!("{}", format);
+        }
+        return Ok(());
+    }
+
+    // Print a model's signature and exit
+    if let Some(path) = &options.describe_model {
+        print!("{}", hand_detector::ModelSignature::from_file(path)?);
+        return Ok(());
+    }
+
+    // Check every subsystem and exit
+    if options.selftest {
+        let results = health_check::run(MODEL_BYTES);
+        health_check::print_summary(&results);
+        if results.iter().any(|r| !r.passed) {
+            return Err(anyhow::anyhow!("Self-test failed"));
+        }
+        return Ok(());
+    }
+
+    // Time each resize filter on a fixture image and exit
+    if let Some(path) = &options.compare_filters {
+        let frame = image::open(path)?.to_rgb8();
+        health_check::print_filter_comparison(&health_check::compare_filters(
+            MODEL_BYTES,
+            &frame,
+            20,
+        )?);
+        return Ok(());
+    }
+
+    // Time batch detection on a directory of frames, inline and on the preprocessing pool,
+    // and exit
+    let load_frames = |dir: &std::path::Path| -> anyhow::Result<Vec<_>> {
+        let replay = webcam::FileSource::open(dir)?;
+        let count = replay.len();
+        let mut replay: Box<dyn webcam::FrameSource> = Box::new(replay);
+        Ok((0..count)
+            .map(|_| replay.next_frame())
+            .collect::<Result<Vec<_>, _>>()?)
+    };
+    if let Some(dir) = &options.bench_batch {
+        let frames = load_frames(dir)?;
+        health_check::print_batch_comparison(
+            frames.len(),
+            &health_check::compare_batch(MODEL_BYTES, &frames, &[1, 2])?,
+        );
+        return Ok(());
+    }
+
+    // Time detection on a directory of frames with full scans and with ROI tracking (with
+    // the --tiles, --roi-tracking and --rescan-every settings), and exit
+    if let Some(dir) = &options.compare_roi {
+        let frames = load_frames(dir)?;
+        let tiles = options
+            .tiles
+            .map(|(cols, rows)| (cols, rows, options.tile_overlap.unwrap_or(0.2)));
+        health_check::print_batch_comparison(
+            frames.len(),
+            &health_check::compare_roi(
+                MODEL_BYTES,
+                &frames,
+                tiles,
+                options.roi_tracking.unwrap_or(2.0),
+                options.rescan_every.unwrap_or(10),
+            )?,
+        );
+        return Ok(());
+    }
+
+    // Start camera (or read frames from a pipe, or generate them)
+    let pipe = options.pipe.clone().zip(options.pipe_size);
+    let pipe_stride = options.pipe_stride;
+    let synthetic = options.synthetic;
+    let synthetic_sprite = options.synthetic_sprite.clone();
+    let replay = options.replay.clone();
+    let replay_from = options.replay_from.unwrap_or(0);
+    let camera_settings = webcam::CameraSettings {
+        auto_exposure: options.auto_exposure,
+        controls: options.camera_controls.clone(),
+    };
+    #[cfg(feature = "screen")]
+    let (screen, screen_region, screen_fps) = (
+        options.screen,
+        options.screen_region,
+        options.screen_fps.unwrap_or(24),
+    );
+    let open_source = move || -> anyhow::Result<Box<dyn webcam::FrameSource>> {
+        #[cfg(feature = "screen")]
+        if let Some(index) = screen {
+            let mut source = screen_capture::ScreenSource::open(index, screen_fps)?;
+            if let Some((x, y, width, height)) = screen_region {
+                source = source.with_region(screen_capture::ScreenRegion {
+                    x,
+                    y,
+                    width,
+                    height,
+                });
+            }
+            return Ok(Box::new(source));
+        }
+        if let Some(dir) = &replay {
+            let mut replay = webcam::FileSource::open(dir)?;
+            replay.seek(replay_from);
+            return Ok(Box::new(replay));
+        }
+        Ok(match pipe {
+            Some((path, (width, height))) => {
+                Box::new(webcam::PipeSource::open(&path, width, height, pipe_stride)?)
+            }
+            _ if synthetic => {
+                let mut synthetic =
+                    webcam::SyntheticSource::linear(640, 480, (0.2, 0.5), (0.8, 0.5), 90)?;
+                if let Some(path) = &synthetic_sprite {
+                    synthetic = synthetic.with_sprite(image::open(path)?.to_rgb8());
+                }
+                Box::new(synthetic)
+            }
+            _ => {
+                let mut camera = webcam::setup()?;
+                webcam::open_stream(&mut camera)?;
+                // Tune the camera for the lighting (unsupported controls are skipped), again
+                // after each reconnect
+                Box::new(webcam::TunedCamera::new(camera, camera_settings))
+            }
+        })
+    };
+    // Optionally capture on its own thread, so a slow detector doesn't hold up the camera
+    let mut source: Box<dyn webcam::FrameSource> = match options.capture_queue {
+        Some(queue) => Box::new(frame_pipeline::CaptureThread::spawn(
+            open_source,
+            queue,
+            MAX_RECONNECT_ATTEMPTS,
+        )?),
+        None => open_source()?,
+    };
 
     // Setup window
     let window_width = 960;
     let window_height = 540;
 
-    let mut window = Window::new(
-        "fingers v0.1.0",
-        window_width,
-        window_height,
-        WindowOptions::default(),
-    )?;
+    // No window in headless or terminal dashboard mode (the frame is still drawn into the buffer)
+    let window_title = options.window_title.as_deref().unwrap_or("fingers v0.1.0");
+    let window_fps = options.window_fps.unwrap_or(24);
+    let mut window = if options.no_window || options.tui {
+        None
+    } else {
+        Some(create_window(
+            window_title,
+            window_width,
+            window_height,
+            window_fps,
+        )?)
+    };
+    let mut window_failures = 0;
 
     // Pre-allocate the pixel buffer to avoid allocating a new vector every frame (Performance)
     let mut window_buffer = vec![0u32; window_width * window_height];
+    let pixel_order = options.pixel_order.unwrap_or_default();
 
-    // Limit fps to reduce CPU usage and potential instability
-    let fps = 24;
-    let duration_per_frame = Duration::from_micros(1000000 / fps as u64);
-    window.limit_update_rate(Some(duration_per_frame));
+    // Stdout belongs to the dashboard in tui mode
+    let verbose = !options.tui && !options.mediapipe_json && options.changes_json.is_none();
+    let mut metrics = frame_metrics::Metrics::new().with_average_window(
+        options
+            .perf_window
+            .unwrap_or(frame_metrics::DEFAULT_AVERAGE_WINDOW),
+    );
+    #[cfg(feature = "tui")]
+    let dashboard = options.tui.then(tui_dashboard::Dashboard::spawn);
+
+    // Latest detections over HTTP for other programs to poll
+    #[cfg(feature = "http")]
+    let http_server = options
+        .http
+        .as_deref()
+        .map(detection_server::DetectionServer::spawn)
+        .transpose()?;
+
+    // Setup Input Device (only needed when gestures are bound to actions)
+    let mut action_sink = match &options.bindings {
+        Some(path) => {
+            let table = gesture_bindings::DispatchTable::load(path)?;
+            Some(gesture_bindings::ActionSink::new(
+                input_device::create()?,
+                table,
+            ))
+        }
+        None => None,
+    };
 
-    // Setup Input Device
-    // let mut input_controller = input_device::create()?;
+    // Pinch to click
+    let mut pinch_clicker = if options.pinch_click {
+        let mut clicker = input_device::PinchClicker::new();
+        if let Some(buttons) = options.click_buttons.clone() {
+            clicker = clicker.with_buttons(buttons);
+        }
+        if let Some(millis) = options.click_cooldown_ms {
+            clicker = clicker.with_click_cooldown(Duration::from_millis(millis));
+        }
+        Some((input_device::create()?, clicker))
+    } else {
+        None
+    };
+
+    // Hold still to click
+    let mut dwell_clicker = match options.dwell_click_ms {
+        Some(millis) => {
+            let mut clicker = input_device::DwellClicker::new(Duration::from_millis(millis));
+            if let Some(radius) = options.dwell_radius {
+                clicker = clicker.with_radius(radius);
+            }
+            if let Some(stability) = options.dwell_min_stability {
+                clicker = clicker.with_min_stability(stability);
+            }
+            Some((input_device::create()?, clicker))
+        }
+        None => None,
+    };
+
+    // Hold the hand high or low to scroll
+    let mut positional_scroller = match options.positional_scroll {
+        Some(speed) => {
+            let mut scroller = input_device::PositionalScroller::new(speed);
+            if let Some((up, down)) = options.scroll_zones {
+                scroller = scroller.with_zones(up, down)?;
+            }
+            Some((input_device::create()?, scroller))
+        }
+        None => None,
+    };
 
     // Load detector model
-    let mut detector = hand_detector::HandDetector::new_embedded(MODEL_BYTES)?;
+    let session_config = if options.deterministic {
+        hand_detector::SessionConfig::deterministic()
+    } else {
+        hand_detector::SessionConfig::default()
+    };
+    let mut detector =
+        hand_detector::HandDetector::new_embedded_with_config(MODEL_BYTES, session_config)?;
+    if let Some(expansion) = options.roi_tracking {
+        detector = detector.with_roi_tracking(expansion, options.rescan_every.unwrap_or(10))?;
+    }
+    if let Some(cameras) = options.stitched_cameras {
+        detector = detector.with_stitched_cameras(cameras)?;
+    }
+    if let Some((cols, rows)) = options.tiles {
+        detector = detector.with_tiles(cols, rows, options.tile_overlap.unwrap_or(0.2))?;
+    }
+    if let Some(path) = &options.anchors {
+        detector = detector.with_anchors_from_file(path)?;
+    }
+    if let Some(max_hands) = options.max_hands {
+        detector = detector.with_max_hands(max_hands);
+    }
+    if let Some(mode) = options.nms_mode {
+        detector = detector.with_nms_mode(mode);
+    }
+    if options.warn_overflow {
+        detector = detector.with_overflow_policy(hand_detector::OverflowPolicy::Flag);
+    }
+    if let Some(threshold) = options.min_key_visibility {
+        detector = detector.with_min_key_visibility(threshold);
+    }
+    if let Some(margin) = options.ignore_border {
+        detector = detector.with_ignore_border(margin);
+    }
+    if let Some(threshold) = options.box_fallback {
+        detector = detector.with_box_fallback(threshold);
+    }
+    if let Some(order) = options.hand_order {
+        detector = detector.with_output_order(order);
+    }
+    if let Some(filter) = options.resize_filter {
+        detector = detector.with_resize_filter(filter);
+    }
+    if let Some(activation) = options.score_activation {
+        detector = detector.with_activation(activation);
+    }
+    if let Some(zone) = options.active_zone {
+        detector = detector.with_active_zone(zone)?;
+    }
+    detector = detector.with_logging(verbose);
+
+    // Pay ORT's first-run cost now instead of stuttering on the first frame
+    let warmup = detector.warmup()?;
+    if verbose {
+        println!(
+            "Detector warmup took {:.1} ms",
+            warmup.as_secs_f64() * 1000.0
+        );
+    }
+    let mut landmarker = hand_landmarker::HandLandmarker::new_embedded_with_config(
+        LANDMARK_MODEL_BYTES,
+        session_config,
+    )?;
+
+    // Setup hand tracking and gesture state
+    let mut tracker = hand_tracker::HandTracker::new();
+    let mut gesture_state = gestures::HandGestureState::new(gestures::GestureConfig {
+        clutch: options
+            .clutch
+            .then_some(gestures::StaticGesture::ClosedFist),
+        ..gestures::GestureConfig::default()
+    });
+
+    // Only let one hand drive input
+    let mut hand_lock = options.lock_hand.map(|selection| {
+        hand_tracker::PrimaryHandLock::new(
+            selection,
+            Duration::from_millis(options.lock_timeout_ms.unwrap_or(1000)),
+        )
+    });
 
-    // Define closure to convert normalised coordinates to pixel coordinates in window
-    let in_window_px = |l: f32, window_dim_size: usize| {
-        ((l * window_dim_size as f32) as i32).clamp(0, window_dim_size as i32 - 1)
+    // Smooth the drawn boxes so they don't wobble (display only)
+    let mut box_smoother = match options.box_median {
+        Some(frames) => filters::BoxSmoother::median(frames),
+        None => filters::BoxSmoother::new(0.4),
     };
+    let mut landmark_smoother = options.smooth_landmarks.map(filters::LandmarkSmoother::new);
+
+    // Reuse the last results on static frames
+    let mut motion_gate = options
+        .motion_threshold
+        .map(|threshold| motion_gate::MotionGate::new(threshold, options.max_skip.unwrap_or(10)));
+    let mut last_hands = Vec::new();
+    // Cap the inference rate separately from the window rate (e.g. display 60, detect 15)
+    let mut inference_limiter = options.inference_fps.map(motion_gate::RateLimiter::new);
+
+    // Only what changed since the last frame, as JSON lines
+    #[cfg(feature = "json")]
+    let mut change_differ = options
+        .changes_json
+        .map(detection_changes::ChangeDiffer::new);
+
+    // JSON Lines log of the detections
+    #[cfg(feature = "json")]
+    let mut detection_logger = match &options.log_jsonl {
+        Some(path) => Some(
+            detection_log::DetectionLogger::create(path)?
+                .with_every(options.log_every.unwrap_or(1))
+                .with_mode(if options.log_minimal {
+                    detection_log::LogMode::Minimal
+                } else {
+                    detection_log::LogMode::Full
+                }),
+        ),
+        None => None,
+    };
+
+    // Where in the frame hands appear over the session
+    let (heatmap_cols, heatmap_rows) = options
+        .heatmap_grid
+        .map_or(occupancy::DEFAULT_GRID, |(cols, rows)| {
+            (cols as usize, rows as usize)
+        });
+    let mut occupancy_grid = occupancy::OccupancyGrid::new(heatmap_cols, heatmap_rows);
+
+    // Labeled landmark sequences for training, recorded with the R key
+    let mut recorder = options.record_dir.as_ref().map(|dir| {
+        gesture_recorder::GestureRecorder::new(
+            dir,
+            options.record_label.as_deref().unwrap_or("unlabeled"),
+            options.record_frames.unwrap_or(30),
+        )
+    });
+
+    // Whether the last detected frame had more hands than max_hands (--warn-overflow)
+    let mut overflowing = false;
+
+    // The last frames, saved with the D key to debug detection failures after the fact
+    let mut recent_frames = options.recent_frames.map(|frames| {
+        frame_snapshot::FrameRing::new(frames, options.recent_max_mb.unwrap_or(512) << 20)
+    });
 
     // THE WINDOW UPDATE LOOP
-    while window.is_open() && !window.is_key_down(Key::Escape) {
-        let decoded_frame = match webcam::capture_and_decode_frame(&mut camera) {
+    loop {
+        if let Some(window) = &window
+            && (!window.is_open() || window.is_key_down(Key::Escape))
+        {
+            break;
+        }
+
+        let decoded_frame = match source.next_frame() {
             Ok(frame) => frame,
-            Err(e) => {
+            Err(webcam::CaptureError::Transient(e)) => {
                 eprintln!("Failed to capture or decode frame: {}", e);
-                continue; // Skip this frame
+                continue; // Skip this frame and retry immediately
+            }
+            Err(webcam::CaptureError::Fatal(e)) => {
+                eprintln!("Camera failure: {}", e);
+                // Try to reopen the device, exit if it stays unavailable
+                source.reconnect(MAX_RECONNECT_ATTEMPTS)?;
+                continue;
+            }
+            // A pipe or replay ran out of frames: finish normally (the heatmap is still saved)
+            Err(webcam::CaptureError::EndOfStream) => {
+                if verbose {
+                    println!("No more frames from {}", source.describe());
+                }
+                break;
             }
         };
+        #[cfg(any(feature = "http", feature = "json"))]
+        let captured_at = std::time::SystemTime::now();
+        if let Some(ring) = recent_frames.as_mut() {
+            ring.push(&decoded_frame);
+        }
 
         let resized_frame = image::imageops::resize(
             &decoded_frame,
@@ -72,7 +512,7 @@ fn main() -> anyhow::Result<()> {
 
         // Pixel Conversion //
         // The camera gives us a long list of u8 bytes: [R, G, B, R, G, B...]
-        // The window wants u32 integers: [00RGB, 00RGB...]
+        // The window wants u32 integers: [00RGB, 00RGB...] (or 00BGR with --pixel-order abgr)
         // We must map them.
 
         // Sanity check buffer size matches window dimensions
@@ -87,70 +527,409 @@ fn main() -> anyhow::Result<()> {
 
         // Efficiently update the pre-allocated buffer
         for (i, chunk) in resized_frame_raw.chunks_exact(3).enumerate() {
-            let r = chunk[0] as u32;
-            let g = chunk[1] as u32;
-            let b = chunk[2] as u32;
-            window_buffer[i] = (r << 16) | (g << 8) | b;
-        }
-
-        // Pass the frame through the detector and get detector results
-        if let Ok(Some(hands)) = detector.detect(&resized_frame) {
-            for details in hands {
-                // Hand Tracking //
-                println!(
-                    "Hand detected >> score: {} | bbox: ({} {}) ({} {}) | wrist: ({} {})",
-                    details.score,
-                    details.bbox.xmin,
-                    details.bbox.ymin,
-                    details.bbox.xmax,
-                    details.bbox.ymax,
-                    details.wrist.x,
-                    details.wrist.y
+            window_buffer[i] = overlay::pack_pixel(chunk[0], chunk[1], chunk[2], pixel_order);
+        }
+
+        // Pass the full resolution frame through the detector and get detector results
+        // (the window-sized frame is only for display, results are normalized either way)
+        let hands = if inference_limiter
+            .as_mut()
+            .is_none_or(|limiter| limiter.should_run(Instant::now()))
+            && motion_gate
+                .as_mut()
+                .is_none_or(|gate| gate.should_run(&decoded_frame))
+        {
+            let inference_start = Instant::now();
+            let mut hands = detector
+                .detect(&decoded_frame)
+                .ok()
+                .flatten()
+                .unwrap_or_default();
+            if let Err(e) = landmarker.refine(&decoded_frame, &mut hands) {
+                eprintln!("Failed to run hand landmarker: {}", e);
+            }
+            // Handedness is only known after the landmarker
+            let mut hands = detector.nms_by_handedness(hands);
+            detector.output_order().apply(&mut hands);
+            // Warn once each time too many hands come into view
+            if detector.truncated() != overflowing {
+                overflowing = detector.truncated();
+                if overflowing {
+                    eprintln!(
+                        "More than {} hands in view, only the best scoring are used",
+                        options.max_hands.unwrap_or(2)
+                    );
+                }
+            }
+            metrics.inference(inference_start.elapsed());
+            last_hands = hands.clone();
+            hands
+        } else {
+            metrics.skipped_frame();
+            last_hands.clone()
+        };
+
+        occupancy_grid.add_hands(&hands);
+        metrics.frame(Instant::now(), hands.len());
+
+        // Assign tracking ids and update gestures
+        let mut tracked = tracker.update(&hands);
+        let now = Instant::now();
+        if let Some(smoother) = landmark_smoother.as_mut() {
+            smoother.retain(|id| tracked.iter().any(|hand| hand.id == id));
+            for hand in tracked.iter_mut().filter(|hand| hand.missed == 0) {
+                let details = &mut hand.details;
+                if let Some(landmarks) = details.landmarks.as_mut() {
+                    smoother.smooth(hand.id, landmarks, details.landmark_indices.as_deref(), now);
+                }
+            }
+        }
+        let events = gesture_state.update(&tracked, now);
+        if let Some(recorder) = recorder.as_mut() {
+            match recorder.record(&tracked) {
+                Ok(Some(path)) if verbose => {
+                    println!("Recorded gesture sample to {}", path.display())
+                }
+                Ok(_) => {}
+                Err(e) => eprintln!("Failed to record gesture sample: {}", e),
+            }
+        }
+        // While the clutch is off, gestures are still detected and drawn but drive no input
+        let control_enabled = gesture_state.control_enabled();
+        // With a hand lock, only the primary hand drives input
+        if let Some(lock) = hand_lock.as_mut() {
+            lock.update(&tracked, now);
+        }
+        let controls = |id: u32| hand_lock.as_ref().is_none_or(|lock| lock.is_primary(id));
+        let control_tracked: Vec<hand_tracker::TrackedHand> = tracked
+            .iter()
+            .filter(|hand| controls(hand.id))
+            .cloned()
+            .collect();
+        let control_events: Vec<gestures::GestureEvent> = events
+            .iter()
+            .filter(|event| controls(event.id()))
+            .cloned()
+            .collect();
+        if control_enabled
+            && let Some((enigo, clicker)) = pinch_clicker.as_mut()
+            && let Err(e) = clicker.update(enigo, &control_events, &control_tracked, now)
+        {
+            eprintln!("Failed to click: {}", e);
+        }
+        if control_enabled
+            && let Some((enigo, clicker)) = dwell_clicker.as_mut()
+            && let Err(e) = clicker.update(enigo, &control_tracked, now)
+        {
+            eprintln!("Failed to dwell click: {}", e);
+        }
+        if control_enabled
+            && let Some((enigo, scroller)) = positional_scroller.as_mut()
+            && let Err(e) = scroller.update(enigo, &control_tracked, now)
+        {
+            eprintln!("Failed to scroll: {}", e);
+        }
+
+        let mut snapshot_requested = false;
+        let mut dump_requested = false;
+        for event in &events {
+            metrics.gesture(event.name());
+            if verbose {
+                println!("Gesture >> {:?}", event);
+            }
+            if let gestures::GestureEvent::Static {
+                gesture: gestures::StaticGesture::ThumbsUp,
+                ..
+            } = event
+            {
+                snapshot_requested = true;
+            }
+            if options.recent_trigger == Some(event.name()) {
+                dump_requested = true;
+            }
+            let toggle = matches!(event, gestures::GestureEvent::ControlToggled { .. });
+            if (control_enabled || toggle)
+                && controls(event.id())
+                && let Some(sink) = action_sink.as_mut()
+                && let Err(e) = sink.handle(event)
+            {
+                eprintln!("Failed to run action for {}: {}", event.name(), e);
+            }
+        }
+        // Carry on with a macro waiting between steps (turning control off stops it)
+        if let Some(sink) = action_sink.as_mut() {
+            if !control_enabled {
+                sink.cancel_macro();
+            }
+            if let Err(e) = sink.tick(Instant::now()) {
+                eprintln!("Failed to play macro: {}", e);
+                sink.cancel_macro();
+            }
+        }
+
+        #[cfg(feature = "tui")]
+        if let Some(dashboard) = &dashboard {
+            let update = tui_dashboard::DashboardUpdate {
+                metrics: metrics.snapshot(),
+                wrists: tracked
+                    .iter()
+                    .map(|hand| (hand.details.wrist.x, hand.details.wrist.y))
+                    .collect(),
+            };
+            // Quit when the dashboard is closed
+            if !dashboard.send(update) {
+                break;
+            }
+        }
+
+        #[cfg(feature = "http")]
+        if let Some(server) = &http_server {
+            server
+                .latest()
+                .publish(metrics.frames(), captured_at, &hands);
+        }
+
+        #[cfg(feature = "json")]
+        if let Some(differ) = change_differ.as_mut() {
+            for change in differ.update(&tracked, &events) {
+                match serde_json::to_string(&change) {
+                    Ok(json) => println!("{}", json),
+                    Err(e) => eprintln!("Failed to serialize change: {}", e),
+                }
+            }
+        }
+
+        #[cfg(feature = "json")]
+        if let Some(logger) = detection_logger.as_mut()
+            && let Err(e) = logger.log(metrics.frames(), captured_at, &hands)
+        {
+            eprintln!("Failed to log detections: {}", e);
+        }
+
+        // One line of MediaPipe HandLandmarkerResult JSON per frame
+        #[cfg(feature = "json")]
+        if options.mediapipe_json {
+            match mediapipe_json::to_json(&hands) {
+                Ok(json) => println!("{}", json),
+                Err(e) => eprintln!("Failed to serialize detections: {}", e),
+            }
+        }
+
+        for details in hands.iter().filter(|_| verbose) {
+            // Hand Tracking //
+            println!(
+                "Hand detected >> score: {} | bbox: ({} {}) ({} {}) | wrist: ({} {})",
+                details.score,
+                details.bbox.xmin,
+                details.bbox.ymin,
+                details.bbox.xmax,
+                details.bbox.ymax,
+                details.wrist.x,
+                details.wrist.y
+            );
+        }
+
+        // A red border while the clutch has control turned off
+        if !control_enabled {
+            let frame = hand_detector::Box {
+                xmin: 0.0,
+                ymin: 0.0,
+                xmax: 1.0,
+                ymax: 1.0,
+            };
+            overlay::Canvas::new(&mut window_buffer, window_width, window_height)
+                .with_pixel_order(pixel_order)
+                .draw_box(&frame, RED);
+        }
+
+        // Outline the active zone so users can see where hands are picked up
+        if let Some(zone) = detector.active_zone() {
+            overlay::Canvas::new(&mut window_buffer, window_width, window_height)
+                .with_pixel_order(pixel_order)
+                .draw_box(&zone, overlay::ZONE_COLOR);
+        }
+
+        // Keep drawing the last known box of hands that are briefly missing to avoid flicker
+        let hand_states = tracker.hand_states();
+        box_smoother.retain(|id| hand_states.iter().any(|s| s.id == id));
+
+        for state in hand_states.into_iter().filter(|s| s.present) {
+            let details = state.details;
+            let bbox = if state.missed == 0 {
+                box_smoother.smooth(state.id, &details.bbox)
+            } else {
+                box_smoother.get(state.id).unwrap_or(details.bbox)
+            };
+
+            let mut canvas = overlay::Canvas::new(&mut window_buffer, window_width, window_height)
+                .with_pixel_order(pixel_order);
+
+            // --- Draw the Bounding Box and skeleton (one color per tracked hand) ---
+            canvas.draw_hand(
+                state.id,
+                &bbox,
+                &details,
+                overlay::MEDIAPIPE_HAND_CONNECTIONS,
+                overlay::palette_color,
+            );
+            if options.rotated_boxes
+                && let Some(rbox) = &details.rotated_bbox
+            {
+                canvas.draw_rotated_box(rbox, overlay::palette_color(state.id));
+            }
+            if options.show_scores {
+                let label = format!("{:.2}", details.score);
+                canvas.draw_box_label(&bbox, &label, 2, overlay::palette_color(state.id));
+            }
+
+            // --- Draw the Wrist Point (Blue) Dot) ---
+            canvas.draw_dot(details.wrist.x, details.wrist.y, 3, BLUE);
+        }
+
+        // Smoothed FPS and inference time in the top left corner
+        if options.show_fps {
+            let perf = metrics.perf();
+            let text = format!("{:.1} FPS  {:.1} MS", perf.fps, perf.inference_ms);
+            overlay::Canvas::new(&mut window_buffer, window_width, window_height)
+                .with_pixel_order(pixel_order)
+                .draw_text(0.01, 0.01, &text, 3, WHITE);
+        }
+
+        // Dwell progress ring around the pointer
+        if control_enabled
+            && let Some((_, clicker)) = &dwell_clicker
+            && let Some((x, y, fraction)) = clicker.progress(Instant::now())
+        {
+            overlay::Canvas::new(&mut window_buffer, window_width, window_height)
+                .with_pixel_order(pixel_order)
+                .draw_progress(x, y, 12, fraction, GREEN);
+        }
+
+        // Hands-free camera shutter
+        if snapshot_requested && let Some(dir) = &options.snapshot_dir {
+            let saved = if options.snapshot_overlays {
+                let image = frame_snapshot::buffer_to_image(
+                    &window_buffer,
+                    window_width,
+                    window_height,
+                    pixel_order,
                 );
+                frame_snapshot::save_frame_as(&image, dir, options.snapshot_format)
+            } else {
+                frame_snapshot::save_frame_as(&decoded_frame, dir, options.snapshot_format)
+            };
+            match saved {
+                Ok(path) if verbose => println!("Saved snapshot to {}", path.display()),
+                Ok(_) => {}
+                Err(e) => eprintln!("Failed to save snapshot: {}", e),
+            }
+        }
 
-                // Convert normalized coordinates to pixel coordinates
-                let [p_xmin, p_xmax, p_wrist_x] =
-                    [details.bbox.xmin, details.bbox.xmax, details.wrist.x]
-                        .map(|x| in_window_px(x, window_width));
-                let [p_ymin, p_ymax, p_wrist_y] =
-                    [details.bbox.ymin, details.bbox.ymax, details.wrist.y]
-                        .map(|x| in_window_px(x, window_height));
-
-                // --- Draw the Bounding Box (Green: 0x00FF00) ---
-                let box_color = GREEN;
-
-                // Horizontal lines (top and bottom)
-                for x in p_xmin..=p_xmax {
-                    window_buffer[(p_ymin as usize * window_width) + x as usize] = box_color;
-                    window_buffer[(p_ymax as usize * window_width) + x as usize] = box_color;
+        // Save the frames leading up to now
+        if let Some(ring) = &recent_frames
+            && (dump_requested
+                || window
+                    .as_ref()
+                    .is_some_and(|w| w.is_key_pressed(Key::D, minifb::KeyRepeat::No)))
+        {
+            let dir = options
+                .recent_dir
+                .as_deref()
+                .unwrap_or(std::path::Path::new("recent"));
+            match ring.dump_recent(dir, options.snapshot_format) {
+                Ok(path) if verbose => {
+                    println!("Saved {} recent frames to {}", ring.len(), path.display())
                 }
-                // Vertical lines (left and right)
-                for y in p_ymin..=p_ymax {
-                    window_buffer[(y as usize * window_width) + p_xmin as usize] = box_color;
-                    window_buffer[(y as usize * window_width) + p_xmax as usize] = box_color;
+                Ok(_) => {}
+                Err(e) => eprintln!("Failed to save recent frames: {}", e),
+            }
+        }
+
+        // Copy the current detections for bug reports
+        #[cfg(feature = "clipboard")]
+        if let Some(window) = &window
+            && window.is_key_pressed(Key::C, minifb::KeyRepeat::No)
+        {
+            let report = detection_clipboard::DetectionReport {
+                version: env!("CARGO_PKG_VERSION"),
+                model: detector.active_model(),
+                source: &source.describe(),
+                frame_width: decoded_frame.width(),
+                frame_height: decoded_frame.height(),
+                hands: &hands,
+            };
+            match detection_clipboard::copy(&report) {
+                Ok(()) if verbose => {
+                    println!("Copied {} detection(s) to the clipboard", hands.len())
                 }
+                Ok(()) => {}
+                Err(e) => eprintln!("Failed to copy detections: {}", e),
+            }
+        }
 
-                // --- Draw the Wrist Point (Blue) Dot) ---
-                let dot_color = BLUE;
-                let radius = 3;
-                for dy in -radius..=radius {
-                    for dx in -radius..=radius {
-                        let rx = p_wrist_x + dx;
-                        let ry = p_wrist_y + dy;
-                        if rx >= 0
-                            && rx < window_width as i32
-                            && ry >= 0
-                            && ry < window_height as i32
-                        {
-                            window_buffer[(ry as usize * window_width) + rx as usize] = dot_color;
+        // New user: start tracking, smoothing and gestures from scratch (the models stay loaded)
+        if let Some(window) = &window
+            && window.is_key_pressed(Key::N, minifb::KeyRepeat::No)
+        {
+            tracker.reset();
+            gesture_state.reset();
+            if let Some(lock) = hand_lock.as_mut() {
+                lock.reset();
+            }
+            box_smoother.reset();
+            if let Some(smoother) = landmark_smoother.as_mut() {
+                smoother.reset();
+            }
+            if verbose {
+                println!("Reset tracking state");
+            }
+        }
+
+        if let Some(recorder) = recorder.as_mut()
+            && let Some(window) = &window
+            && window.is_key_pressed(Key::R, minifb::KeyRepeat::No)
+        {
+            recorder.start();
+            if verbose {
+                println!("Recording gesture sample...");
+            }
+        }
+
+        // Draw to Window //
+        // A failed update only costs this frame; keep going unless the window stays broken
+        if let Some(current) = window.as_mut() {
+            match current.update_with_buffer(&window_buffer, window_width, window_height) {
+                Ok(()) => window_failures = 0,
+                Err(e) => {
+                    window_failures += 1;
+                    eprintln!(
+                        "Failed to update window ({}/{}): {}",
+                        window_failures, MAX_WINDOW_FAILURES, e
+                    );
+                    if window_failures >= MAX_WINDOW_FAILURES {
+                        return Err(anyhow::anyhow!(
+                            "Window failed to update {} times in a row: {}",
+                            window_failures,
+                            e
+                        ));
+                    }
+                    if window_failures % WINDOW_RECREATE_AFTER == 0 {
+                        eprintln!("Recreating window...");
+                        match create_window(window_title, window_width, window_height, window_fps) {
+                            Ok(recreated) => window = Some(recreated),
+                            Err(e) => eprintln!("Failed to recreate window: {}", e),
                         }
                     }
                 }
             }
         }
+    }
 
-        // Draw to Window //
-        window.update_with_buffer(&window_buffer, window_width, window_height)?;
+    if let Some(path) = &options.heatmap {
+        occupancy_grid.save(path)?;
+        if verbose {
+            println!("Saved hand occupancy heatmap to {}", path.display());
+        }
     }
 
     Ok(())
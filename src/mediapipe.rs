@@ -0,0 +1,170 @@
+pub mod mediapipe_json {
+    use crate::detector::hand_detector::{HandDetails, Handedness};
+    use anyhow::Result;
+    use serde::{Deserialize, Serialize};
+
+    // Detections in the JSON layout of MediaPipe's HandLandmarkerResult, so tools built for
+    // MediaPipe output can consume `fingers` directly:
+    //   {"handedness": [[{"index": 0, "score": 0.97, "categoryName": "Right",
+    //                     "displayName": "Right"}], ...],
+    //    "landmarks": [[{"x": 0.51, "y": 0.62, "z": -0.01}, ... 21 per hand], ...]}
+    // Both lists have one entry per hand, in the same order. Only hands with all 21
+    // landmarks are included. A hand's handedness list is empty if it isn't known.
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    #[serde(rename_all = "camelCase")]
+    pub struct HandLandmarkerResult {
+        pub handedness: Vec<Vec<Category>>,
+        pub landmarks: Vec<Vec<NormalizedLandmark>>,
+    }
+
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    #[serde(rename_all = "camelCase")]
+    pub struct Category {
+        pub index: i32,
+        pub score: f32,
+        pub category_name: String,
+        pub display_name: String,
+    }
+
+    // Normalized to the frame (0.0 to 1.0), z relative to the wrist in about the same scale as x
+    #[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+    pub struct NormalizedLandmark {
+        pub x: f32,
+        pub y: f32,
+        pub z: f32,
+    }
+
+    // MediaPipe's handedness categories: index 0 is Left, 1 is Right
+    impl From<(Handedness, f32)> for Category {
+        fn from((handedness, score): (Handedness, f32)) -> Self {
+            Self {
+                index: match handedness {
+                    Handedness::Left => 0,
+                    Handedness::Right => 1,
+                },
+                score,
+                category_name: handedness.name().to_string(),
+                display_name: handedness.name().to_string(),
+            }
+        }
+    }
+
+    impl HandLandmarkerResult {
+        pub fn from_hands(hands: &[HandDetails]) -> Self {
+            let mut result = Self {
+                handedness: Vec::new(),
+                landmarks: Vec::new(),
+            };
+            for hand in hands {
+                let Some(landmarks) = hand.full_landmarks() else {
+                    continue;
+                };
+                result.landmarks.push(
+                    landmarks
+                        .iter()
+                        .map(|l| NormalizedLandmark {
+                            x: l.x,
+                            y: l.y,
+                            z: l.z,
+                        })
+                        .collect(),
+                );
+                result
+                    .handedness
+                    .push(hand.handedness.map(Category::from).into_iter().collect());
+            }
+            result
+        }
+    }
+
+    pub fn to_json(hands: &[HandDetails]) -> Result<String> {
+        Ok(serde_json::to_string(&HandLandmarkerResult::from_hands(
+            hands,
+        ))?)
+    }
+
+    pub fn from_json(json: &str) -> Result<HandLandmarkerResult> {
+        Ok(serde_json::from_str(json)?)
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        use crate::detector::hand_detector::{Box, Landmark};
+
+        // A HandLandmarkerResult as MediaPipe's JS tasks API prints it (one right hand),
+        // including worldLandmarks, which aren't part of the schema here
+        const FIXTURE: &str = include_str!("../tests/fixtures/hand_landmarker_result.json");
+
+        fn hand(handedness: Option<(Handedness, f32)>, landmarks: Option<usize>) -> HandDetails {
+            let landmark = |i: usize| Landmark {
+                x: 0.3 + 0.01 * i as f32,
+                y: 0.6 - 0.01 * i as f32,
+                z: -0.001 * i as f32,
+                visibility: 1.0,
+                estimated: false,
+            };
+            HandDetails {
+                score: 0.9,
+                bbox: Box {
+                    xmin: 0.25,
+                    ymin: 0.35,
+                    xmax: 0.6,
+                    ymax: 0.65,
+                },
+                wrist: landmark(0),
+                keypoints: (0..7).map(landmark).collect(),
+                landmarks: landmarks.map(|count| (0..count).map(landmark).collect()),
+                landmark_indices: None,
+                handedness,
+                rotated_bbox: None,
+                camera: None,
+            }
+        }
+
+        #[test]
+        fn parses_mediapipe_output() {
+            let result = from_json(FIXTURE).unwrap();
+            assert_eq!(result.landmarks.len(), 1);
+            assert_eq!(result.handedness.len(), 1);
+            assert_eq!(result.landmarks[0].len(), 21);
+            assert_eq!(
+                result.landmarks[0][0],
+                NormalizedLandmark {
+                    x: 0.52,
+                    y: 0.81,
+                    z: 0.0
+                }
+            );
+            assert_eq!(
+                result.handedness[0],
+                vec![Category::from((Handedness::Right, 0.9731))]
+            );
+        }
+
+        #[test]
+        fn json_round_trips() {
+            let hands = [
+                hand(Some((Handedness::Left, 0.8)), Some(21)),
+                // Skipped: landmarker not run
+                hand(Some((Handedness::Right, 0.9)), None),
+                hand(None, Some(21)),
+            ];
+            let json = to_json(&hands).unwrap();
+            assert!(json.contains("\"categoryName\":\"Left\""));
+
+            let result = from_json(&json).unwrap();
+            assert_eq!(result, HandLandmarkerResult::from_hands(&hands));
+            assert_eq!(result.landmarks.len(), 2);
+            assert_eq!(result.handedness[0][0].index, 0);
+            assert!(result.handedness[1].is_empty());
+
+            // And back out in the same layout as the fixture
+            let fixture = from_json(FIXTURE).unwrap();
+            assert_eq!(
+                from_json(&serde_json::to_string(&fixture).unwrap()).unwrap(),
+                fixture
+            );
+        }
+    }
+}
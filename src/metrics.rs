@@ -0,0 +1,172 @@
+pub mod frame_metrics {
+    use std::collections::VecDeque;
+    use std::time::{Duration, Instant};
+
+    // Frames the displayed FPS and inference time are averaged over
+    pub const DEFAULT_AVERAGE_WINDOW: usize = 30;
+
+    // Mean of the last `window` values pushed
+    #[derive(Debug, Clone)]
+    pub struct MovingAverage {
+        window: usize,
+        values: VecDeque<f32>,
+    }
+
+    impl MovingAverage {
+        pub fn new(window: usize) -> Self {
+            let window = window.max(1);
+            Self {
+                window,
+                values: VecDeque::with_capacity(window),
+            }
+        }
+
+        pub fn push(&mut self, value: f32) {
+            if self.values.len() == self.window {
+                self.values.pop_front();
+            }
+            self.values.push_back(value);
+        }
+
+        // 0.0 until a value has been pushed
+        pub fn mean(&self) -> f32 {
+            if self.values.is_empty() {
+                return 0.0;
+            }
+            self.values.iter().sum::<f32>() / self.values.len() as f32
+        }
+    }
+
+    // Smoothed performance of the detect loop
+    #[derive(Debug, Clone, Copy, Default, PartialEq)]
+    pub struct PerfStats {
+        // Processed frames per second
+        pub fps: f32,
+        // Time spent in detection and landmarking per inference, in ms
+        pub inference_ms: f32,
+    }
+
+    // Running stats of the detect loop
+    pub struct Metrics {
+        last_frame: Option<Instant>,
+        // Seconds between processed frames
+        frame_intervals: MovingAverage,
+        inference_ms: MovingAverage,
+        frames: u64,
+        detections: usize,
+        total_detections: u64,
+        // Frames where inference was skipped and the previous results reused
+        skipped_frames: u64,
+        last_gesture: Option<String>,
+    }
+
+    // Copy of the current stats, cheap to send to another thread
+    #[derive(Debug, Clone, Default)]
+    pub struct MetricsSnapshot {
+        pub fps: f32,
+        pub inference_ms: f32,
+        pub frames: u64,
+        pub detections: usize,
+        pub total_detections: u64,
+        pub skipped_frames: u64,
+        pub last_gesture: Option<String>,
+    }
+
+    impl Metrics {
+        pub fn new() -> Self {
+            Self {
+                last_frame: None,
+                frame_intervals: MovingAverage::new(DEFAULT_AVERAGE_WINDOW),
+                inference_ms: MovingAverage::new(DEFAULT_AVERAGE_WINDOW),
+                frames: 0,
+                detections: 0,
+                total_detections: 0,
+                skipped_frames: 0,
+                last_gesture: None,
+            }
+        }
+
+        // Average the FPS and inference time over this many frames instead. A longer window
+        // is steadier but slower to show a change.
+        pub fn with_average_window(mut self, frames: usize) -> Self {
+            self.frame_intervals = MovingAverage::new(frames);
+            self.inference_ms = MovingAverage::new(frames);
+            self
+        }
+
+        // Call once per processed frame with the number of hands detected in it
+        pub fn frame(&mut self, now: Instant, detections: usize) {
+            if let Some(last) = self.last_frame {
+                let elapsed = now.duration_since(last).as_secs_f32();
+                if elapsed > 0.0 {
+                    self.frame_intervals.push(elapsed);
+                }
+            }
+            self.last_frame = Some(now);
+            self.frames += 1;
+            self.detections = detections;
+            self.total_detections += detections as u64;
+        }
+
+        // Call with the time each inference (detection plus landmarking) took
+        pub fn inference(&mut self, elapsed: Duration) {
+            self.inference_ms.push(elapsed.as_secs_f32() * 1000.0);
+        }
+
+        // Call for frames that reused the previous frame's results
+        pub fn skipped_frame(&mut self) {
+            self.skipped_frames += 1;
+        }
+
+        pub fn gesture(&mut self, name: &str) {
+            self.last_gesture = Some(name.to_string());
+        }
+
+        // Averaged over the last frames (see with_average_window)
+        pub fn fps(&self) -> f32 {
+            let interval = self.frame_intervals.mean();
+            if interval > 0.0 { 1.0 / interval } else { 0.0 }
+        }
+
+        pub fn perf(&self) -> PerfStats {
+            PerfStats {
+                fps: self.fps(),
+                inference_ms: self.inference_ms.mean(),
+            }
+        }
+
+        pub fn frames(&self) -> u64 {
+            self.frames
+        }
+
+        pub fn detections(&self) -> usize {
+            self.detections
+        }
+
+        pub fn skipped_frames(&self) -> u64 {
+            self.skipped_frames
+        }
+
+        pub fn last_gesture(&self) -> Option<&str> {
+            self.last_gesture.as_deref()
+        }
+
+        pub fn snapshot(&self) -> MetricsSnapshot {
+            MetricsSnapshot {
+                fps: self.fps(),
+                inference_ms: self.inference_ms.mean(),
+                frames: self.frames,
+                detections: self.detections,
+                total_detections: self.total_detections,
+                skipped_frames: self.skipped_frames,
+                last_gesture: self.last_gesture.clone(),
+            }
+        }
+    }
+
+    impl Default for Metrics {
+        fn default() -> Self {
+            Self::new()
+        }
+    }
+}
@@ -0,0 +1,97 @@
+pub mod motion_gate {
+    use image::{ImageBuffer, Rgb};
+    use std::time::{Duration, Instant};
+
+    // Frames are compared on a coarse grey grid, which is cheap and ignores sensor noise
+    const GRID_WIDTH: u32 = 64;
+    const GRID_HEIGHT: u32 = 36;
+
+    // Skips inference on frames that barely differ from the previous one (static scenes),
+    // to save power. After `max_skip` skipped frames in a row inference runs anyway,
+    // so the reused results can't go stale indefinitely.
+    pub struct MotionGate {
+        // Mean absolute difference (0.0 to 1.0) below which a frame counts as static
+        threshold: f32,
+        max_skip: u32,
+        previous: Option<Vec<u8>>,
+        skipped_in_a_row: u32,
+    }
+
+    fn grey_grid(frame: &ImageBuffer<Rgb<u8>, Vec<u8>>) -> Vec<u8> {
+        let (width, height) = frame.dimensions();
+        let mut grid = Vec::with_capacity((GRID_WIDTH * GRID_HEIGHT) as usize);
+        for gy in 0..GRID_HEIGHT {
+            for gx in 0..GRID_WIDTH {
+                let x = (gx * width / GRID_WIDTH).min(width.saturating_sub(1));
+                let y = (gy * height / GRID_HEIGHT).min(height.saturating_sub(1));
+                let rgb = frame.get_pixel(x, y);
+                grid.push(((rgb[0] as u32 + rgb[1] as u32 + rgb[2] as u32) / 3) as u8);
+            }
+        }
+        grid
+    }
+
+    impl MotionGate {
+        pub fn new(threshold: f32, max_skip: u32) -> Self {
+            Self {
+                threshold,
+                max_skip,
+                previous: None,
+                skipped_in_a_row: 0,
+            }
+        }
+
+        // Whether to run inference on this frame (false means reuse the last results)
+        pub fn should_run(&mut self, frame: &ImageBuffer<Rgb<u8>, Vec<u8>>) -> bool {
+            let grid = grey_grid(frame);
+            let difference = match &self.previous {
+                Some(previous) if previous.len() == grid.len() => {
+                    let total: u32 = previous
+                        .iter()
+                        .zip(&grid)
+                        .map(|(&a, &b)| a.abs_diff(b) as u32)
+                        .sum();
+                    total as f32 / (grid.len() as f32 * 255.0)
+                }
+                _ => f32::INFINITY,
+            };
+            self.previous = Some(grid);
+
+            if difference < self.threshold && self.skipped_in_a_row < self.max_skip {
+                self.skipped_in_a_row += 1;
+                false
+            } else {
+                self.skipped_in_a_row = 0;
+                true
+            }
+        }
+    }
+
+    // Runs inference at most `max_per_second` times a second, independent of the capture
+    // and display rate; frames in between reuse the last results.
+    pub struct RateLimiter {
+        interval: Duration,
+        last_run: Option<Instant>,
+    }
+
+    impl RateLimiter {
+        pub fn new(max_per_second: f32) -> Self {
+            Self {
+                interval: Duration::from_secs_f32(1.0 / max_per_second.max(0.01)),
+                last_run: None,
+            }
+        }
+
+        // Whether to run inference now (false means reuse the last results)
+        pub fn should_run(&mut self, now: Instant) -> bool {
+            if self
+                .last_run
+                .is_some_and(|last| now.duration_since(last) < self.interval)
+            {
+                return false;
+            }
+            self.last_run = Some(now);
+            true
+        }
+    }
+}
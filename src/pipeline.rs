@@ -0,0 +1,399 @@
+pub mod frame_pipeline {
+    use crate::detector::hand_detector::{PreprocessConfig, Preprocessed, preprocess};
+    use crate::sensor::webcam::{CaptureError, FrameSource};
+    use image::{ImageBuffer, Rgb};
+    use nokhwa::NokhwaError;
+    use std::collections::VecDeque;
+    use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+    use std::sync::mpsc::{Receiver, SyncSender, TrySendError, sync_channel};
+    use std::sync::{Arc, Condvar, Mutex};
+    use std::thread::JoinHandle;
+
+    type Frame = ImageBuffer<Rgb<u8>, Vec<u8>>;
+
+    struct Worker {
+        jobs: SyncSender<Frame>,
+        results: Receiver<Preprocessed>,
+        handle: Option<JoinHandle<()>>,
+    }
+
+    // Preprocesses frames on a small pool of threads so preparing the next frame
+    // overlaps with inference on the current one (useful when processing recorded
+    // video as fast as possible). Frames are handed out round-robin and results
+    // come back in submission order. Each worker holds at most `queue_depth`
+    // pending frames; once they're all taken `submit` hands the frame back instead of
+    // blocking, so one thread can both submit and recv (see health_check::compare_batch).
+    // Pass the detector's `preprocess_config()` so tensors match what `detect` would build.
+    pub struct PreprocessPool {
+        workers: Vec<Worker>,
+        queue_depth: usize,
+        next_submit: usize,
+        next_receive: usize,
+        pending: usize,
+    }
+
+    impl PreprocessPool {
+        pub fn new(num_threads: usize, queue_depth: usize, config: PreprocessConfig) -> Self {
+            let queue_depth = queue_depth.max(1);
+            let workers = (0..num_threads.max(1))
+                .map(|_| {
+                    let (job_tx, job_rx) = sync_channel::<Frame>(queue_depth);
+                    let (result_tx, result_rx) = sync_channel::<Preprocessed>(queue_depth);
+                    let config = config.clone();
+
+                    let handle = std::thread::spawn(move || {
+                        for frame in job_rx {
+                            if result_tx.send(preprocess(&frame, &config)).is_err() {
+                                break;
+                            }
+                        }
+                    });
+
+                    Worker {
+                        jobs: job_tx,
+                        results: result_rx,
+                        handle: Some(handle),
+                    }
+                })
+                .collect();
+
+            Self {
+                workers,
+                queue_depth,
+                next_submit: 0,
+                next_receive: 0,
+                pending: 0,
+            }
+        }
+
+        // Queue a frame for preprocessing without blocking. The frame is handed back when
+        // the pool is full (recv a result first) or the worker has stopped.
+        pub fn submit(&mut self, frame: Frame) -> Result<(), Frame> {
+            if self.is_full() {
+                return Err(frame);
+            }
+            // Results are drained round-robin too, so no worker holds more than queue_depth
+            // frames and neither its job nor its result queue can fill up
+            let worker = &self.workers[self.next_submit];
+            worker.jobs.try_send(frame).map_err(|e| match e {
+                TrySendError::Full(frame) | TrySendError::Disconnected(frame) => frame,
+            })?;
+
+            self.next_submit = (self.next_submit + 1) % self.workers.len();
+            self.pending += 1;
+            Ok(())
+        }
+
+        // Next preprocessed frame in submission order, or None if nothing is pending
+        pub fn recv(&mut self) -> Option<Preprocessed> {
+            if self.pending == 0 {
+                return None;
+            }
+            let result = self.workers[self.next_receive].results.recv().ok()?;
+            self.next_receive = (self.next_receive + 1) % self.workers.len();
+            self.pending -= 1;
+            Some(result)
+        }
+
+        pub fn pending(&self) -> usize {
+            self.pending
+        }
+
+        // Whether `submit` would hand the frame back: queue_depth frames per worker pending
+        pub fn is_full(&self) -> bool {
+            self.pending >= self.workers.len() * self.queue_depth
+        }
+    }
+
+    impl Drop for PreprocessPool {
+        fn drop(&mut self) {
+            // Closing both queues makes workers exit, even if blocked on a full result queue
+            for worker in self.workers.drain(..) {
+                let Worker {
+                    jobs,
+                    results,
+                    handle,
+                } = worker;
+                drop(jobs);
+                drop(results);
+                if let Some(handle) = handle {
+                    let _ = handle.join();
+                }
+            }
+        }
+    }
+
+    // What the capture thread does when the queue to the detect loop is full.
+    // - DropOldest: discard the oldest queued frame. The detect loop always gets the freshest
+    //   frame, so latency stays at most `capacity` frames; frames are lost when detection is
+    //   slower than the camera. Best for live control.
+    // - DropNewest: discard the frame just captured. Queued frames keep their order, but the
+    //   ones waiting are stale by the time they're detected, so latency grows to `capacity`
+    //   frames. Useful when frame order matters more than recency.
+    // - Block: capture waits for room, so no frame is lost and throughput equals the detect
+    //   rate. A camera keeps producing frames meanwhile, so the driver may drop (or buffer)
+    //   them instead. Best for recorded input where every frame should be processed.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+    pub enum DropPolicy {
+        #[default]
+        DropOldest,
+        DropNewest,
+        Block,
+    }
+
+    impl DropPolicy {
+        pub fn from_name(name: &str) -> Option<Self> {
+            match name {
+                "drop-oldest" => Some(DropPolicy::DropOldest),
+                "drop-newest" => Some(DropPolicy::DropNewest),
+                "block" => Some(DropPolicy::Block),
+                _ => None,
+            }
+        }
+    }
+
+    // Bounded queue between the capture thread and the detect loop. A deeper queue smooths
+    // over detection hiccups (with Block or DropNewest) at the cost of latency; the default,
+    // one frame with DropOldest, gives the lowest latency.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct QueueConfig {
+        pub capacity: usize,
+        pub policy: DropPolicy,
+    }
+
+    impl Default for QueueConfig {
+        fn default() -> Self {
+            Self {
+                capacity: 1,
+                policy: DropPolicy::DropOldest,
+            }
+        }
+    }
+
+    struct FrameQueue {
+        frames: Mutex<QueueState>,
+        // Signalled when a frame is pushed or popped, or the queue is closed
+        changed: Condvar,
+        dropped: AtomicU64,
+        stop: AtomicBool,
+    }
+
+    struct QueueState {
+        frames: VecDeque<Frame>,
+        // Set once capture has stopped for good (the error is handed out once)
+        closed: Option<Option<CaptureError>>,
+    }
+
+    // Captures frames on a background thread so a slow detector doesn't stall the camera.
+    // The source is opened on the capture thread (camera handles can't move between
+    // threads), retried there on fatal errors, and frames reach `next_frame` through a
+    // bounded queue configured by `QueueConfig`. Implements FrameSource, so the detect
+    // loop reads from it like from any other source.
+    pub struct CaptureThread {
+        queue: Arc<FrameQueue>,
+        description: String,
+        handle: Option<JoinHandle<()>>,
+    }
+
+    impl CaptureThread {
+        pub fn spawn<F>(
+            open: F,
+            config: QueueConfig,
+            max_reconnect_attempts: u32,
+        ) -> anyhow::Result<Self>
+        where
+            F: FnOnce() -> anyhow::Result<std::boxed::Box<dyn FrameSource>> + Send + 'static,
+        {
+            let config = QueueConfig {
+                capacity: config.capacity.max(1),
+                ..config
+            };
+            let queue = Arc::new(FrameQueue {
+                frames: Mutex::new(QueueState {
+                    frames: VecDeque::with_capacity(config.capacity),
+                    closed: None,
+                }),
+                changed: Condvar::new(),
+                dropped: AtomicU64::new(0),
+                stop: AtomicBool::new(false),
+            });
+            let (opened_tx, opened_rx) = sync_channel::<anyhow::Result<String>>(1);
+
+            let thread_queue = Arc::clone(&queue);
+            let handle = std::thread::spawn(move || {
+                let mut source = match open() {
+                    Ok(source) => {
+                        let _ = opened_tx.send(Ok(source.describe()));
+                        source
+                    }
+                    Err(e) => {
+                        let _ = opened_tx.send(Err(e));
+                        return;
+                    }
+                };
+                let error = capture(&mut *source, &thread_queue, config, max_reconnect_attempts);
+                thread_queue.close(error);
+            });
+
+            let description = opened_rx.recv().map_err(|_| {
+                anyhow::anyhow!("Capture thread stopped before opening the source")
+            })??;
+
+            Ok(Self {
+                queue,
+                description,
+                handle: Some(handle),
+            })
+        }
+
+        // Frames discarded by the drop policy so far
+        pub fn dropped(&self) -> u64 {
+            self.queue.dropped.load(Ordering::Relaxed)
+        }
+    }
+
+    // Capture until the consumer goes away, the source runs out or fails for good
+    fn capture(
+        source: &mut dyn FrameSource,
+        queue: &FrameQueue,
+        config: QueueConfig,
+        max_reconnect_attempts: u32,
+    ) -> Option<CaptureError> {
+        while !queue.stop.load(Ordering::Relaxed) {
+            match source.next_frame() {
+                Ok(frame) => queue.push(frame, config),
+                Err(CaptureError::Transient(e)) => {
+                    eprintln!("Failed to capture or decode frame: {}", e);
+                }
+                Err(CaptureError::Fatal(e)) => {
+                    eprintln!("Camera failure: {}", e);
+                    if source.reconnect(max_reconnect_attempts).is_err() {
+                        return Some(CaptureError::Fatal(e));
+                    }
+                }
+                Err(CaptureError::EndOfStream) => return Some(CaptureError::EndOfStream),
+            }
+        }
+        None
+    }
+
+    impl FrameQueue {
+        fn push(&self, frame: Frame, config: QueueConfig) {
+            let Ok(mut state) = self.frames.lock() else {
+                return;
+            };
+            if state.frames.len() >= config.capacity {
+                match config.policy {
+                    DropPolicy::DropOldest => {
+                        state.frames.pop_front();
+                        self.dropped.fetch_add(1, Ordering::Relaxed);
+                    }
+                    DropPolicy::DropNewest => {
+                        self.dropped.fetch_add(1, Ordering::Relaxed);
+                        return;
+                    }
+                    DropPolicy::Block => {
+                        while state.frames.len() >= config.capacity
+                            && !self.stop.load(Ordering::Relaxed)
+                        {
+                            state = match self.changed.wait(state) {
+                                Ok(state) => state,
+                                Err(_) => return,
+                            };
+                        }
+                    }
+                }
+            }
+            state.frames.push_back(frame);
+            self.changed.notify_all();
+        }
+
+        fn close(&self, error: Option<CaptureError>) {
+            if let Ok(mut state) = self.frames.lock() {
+                state.closed = Some(error);
+            }
+            self.changed.notify_all();
+        }
+    }
+
+    impl FrameSource for CaptureThread {
+        // Waits for the next queued frame. Once capture has stopped, remaining frames are
+        // still returned, then the error that stopped it (EndOfStream for a finite source
+        // that ran out of frames).
+        fn next_frame(&mut self) -> Result<Frame, CaptureError> {
+            let mut state = self
+                .queue
+                .frames
+                .lock()
+                .map_err(|_| shutdown("capture thread panicked"))?;
+            loop {
+                if let Some(frame) = state.frames.pop_front() {
+                    self.queue.changed.notify_all();
+                    return Ok(frame);
+                }
+                if let Some(error) = state.closed.as_mut() {
+                    return Err(error.take().unwrap_or_else(|| shutdown("capture stopped")));
+                }
+                state = self
+                    .queue
+                    .changed
+                    .wait(state)
+                    .map_err(|_| shutdown("capture thread panicked"))?;
+            }
+        }
+
+        fn describe(&self) -> String {
+            format!("{} (capture thread)", self.description)
+        }
+    }
+
+    fn shutdown(reason: &str) -> CaptureError {
+        CaptureError::Fatal(NokhwaError::StreamShutdownError(reason.into()))
+    }
+
+    impl Drop for CaptureThread {
+        fn drop(&mut self) {
+            // Wake a capture blocked on a full queue so it sees the stop flag
+            self.queue.stop.store(true, Ordering::Relaxed);
+            {
+                let _state = self.queue.frames.lock();
+                self.queue.changed.notify_all();
+            }
+            if let Some(handle) = self.handle.take() {
+                let _ = handle.join();
+            }
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        #[test]
+        fn pool_hands_frames_back_when_full() {
+            let mut pool = PreprocessPool::new(2, 1, PreprocessConfig::default());
+            // Frames of different heights, to tell the results apart by their letterbox
+            let heights = [300, 200, 100];
+            let frame = |height| Frame::new(400, height);
+
+            assert!(pool.submit(frame(heights[0])).is_ok());
+            assert!(pool.submit(frame(heights[1])).is_ok());
+            assert!(pool.is_full());
+            // Would block (and never return) with a blocking submit on a single thread
+            let rejected = pool.submit(frame(heights[2])).unwrap_err();
+            assert_eq!(rejected.height(), heights[2]);
+
+            let first = pool.recv().unwrap();
+            assert!(pool.submit(rejected).is_ok());
+            let rest = [pool.recv().unwrap(), pool.recv().unwrap()];
+            assert!(pool.recv().is_none());
+
+            // Results come back in submission order: flatter frames get smaller scales
+            let scales: Vec<f32> = std::iter::once(&first)
+                .chain(&rest)
+                .map(|preprocessed| preprocessed.letterbox.scale_y)
+                .collect();
+            assert!(scales[0] > scales[1] && scales[1] > scales[2]);
+        }
+    }
+}
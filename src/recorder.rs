@@ -0,0 +1,100 @@
+pub mod gesture_recorder {
+    use crate::detector::hand_detector::Landmark;
+    use crate::landmarker::hand_landmarker::NUM_LANDMARKS;
+    use crate::tracker::hand_tracker::TrackedHand;
+    use anyhow::Result;
+    use std::fs::OpenOptions;
+    use std::io::Write;
+    use std::path::{Path, PathBuf};
+    use std::time::{SystemTime, UNIX_EPOCH};
+
+    // Records labeled sequences of landmark frames for training a gesture classifier.
+    // `start` captures the next `frames` frames of one hand (the first tracked hand with all
+    // 21 landmarks) and appends them as one sample to `<dir>/gestures.csv`:
+    //   sample,label,frame,x0,y0,z0,...,x20,y20,z20
+    // where `sample` is the recording's start time in ms, and coordinates are normalized
+    // to the frame. Frames without a usable hand are skipped rather than padded.
+    pub struct GestureRecorder {
+        dir: PathBuf,
+        label: String,
+        frames: usize,
+        // Landmark frames of the recording in progress
+        recording: Option<(u128, Vec<Vec<Landmark>>)>,
+    }
+
+    impl GestureRecorder {
+        pub fn new<P: AsRef<Path>>(dir: P, label: &str, frames: usize) -> Self {
+            Self {
+                dir: dir.as_ref().to_path_buf(),
+                label: label.to_string(),
+                frames: frames.max(1),
+                recording: None,
+            }
+        }
+
+        pub fn set_label(&mut self, label: &str) {
+            self.label = label.to_string();
+        }
+
+        pub fn is_recording(&self) -> bool {
+            self.recording.is_some()
+        }
+
+        // Start a new sample (restarting one already in progress)
+        pub fn start(&mut self) {
+            let millis = SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|d| d.as_millis())
+                .unwrap_or(0);
+            self.recording = Some((millis, Vec::with_capacity(self.frames)));
+        }
+
+        fn path(&self) -> PathBuf {
+            self.dir.join("gestures.csv")
+        }
+
+        // Feed this frame's tracked hands. Returns the dataset path once a sample is complete.
+        pub fn record(&mut self, tracked: &[TrackedHand]) -> Result<Option<PathBuf>> {
+            let Some((_, frames)) = self.recording.as_mut() else {
+                return Ok(None);
+            };
+            if let Some(landmarks) = tracked
+                .iter()
+                .find_map(|hand| hand.details.full_landmarks())
+            {
+                frames.push(landmarks.to_vec());
+            }
+            if frames.len() < self.frames {
+                return Ok(None);
+            }
+
+            let (sample, frames) = self.recording.take().unwrap_or_default();
+            self.write(sample, &frames).map(Some)
+        }
+
+        fn write(&self, sample: u128, frames: &[Vec<Landmark>]) -> Result<PathBuf> {
+            std::fs::create_dir_all(&self.dir)?;
+            let path = self.path();
+            let new_file = !path.exists();
+            let mut file = OpenOptions::new().create(true).append(true).open(&path)?;
+
+            if new_file {
+                let mut header = String::from("sample,label,frame");
+                for i in 0..NUM_LANDMARKS {
+                    header.push_str(&format!(",x{i},y{i},z{i}"));
+                }
+                writeln!(file, "{}", header)?;
+            }
+            // Commas would break the columns
+            let label = self.label.replace(',', "_");
+            for (frame, landmarks) in frames.iter().enumerate() {
+                let mut row = format!("{},{},{}", sample, label, frame);
+                for landmark in landmarks {
+                    row.push_str(&format!(",{},{},{}", landmark.x, landmark.y, landmark.z));
+                }
+                writeln!(file, "{}", row)?;
+            }
+            Ok(path)
+        }
+    }
+}
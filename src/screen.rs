@@ -0,0 +1,107 @@
+pub mod screen_capture {
+    use crate::sensor::webcam::{CaptureError, FrameSource};
+    use image::{GenericImageView, ImageBuffer, Rgb};
+    use nokhwa::NokhwaError;
+    use std::time::{Duration, Instant};
+    use xcap::Monitor;
+
+    // Part of a monitor to capture, in screen pixels from its top left corner
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct ScreenRegion {
+        pub x: u32,
+        pub y: u32,
+        pub width: u32,
+        pub height: u32,
+    }
+
+    // Frames grabbed from a monitor (or a region of it) with `xcap`, e.g. to detect hands
+    // in a video playing on screen. Captures are paced to at most `fps` frames a second.
+    // Supported where xcap is: Windows, macOS (needs the screen recording permission) and
+    // Linux on X11 or Wayland (through the desktop portal, which may ask for confirmation).
+    pub struct ScreenSource {
+        monitor: Monitor,
+        index: usize,
+        region: Option<ScreenRegion>,
+        interval: Duration,
+        last_capture: Option<Instant>,
+    }
+
+    impl ScreenSource {
+        // Capture the monitor at this index (0 is usually the primary one)
+        pub fn open(index: usize, fps: u32) -> Result<Self, NokhwaError> {
+            let open_error = |message: String| {
+                NokhwaError::OpenDeviceError(format!("screen {}", index), message)
+            };
+            let monitors = Monitor::all().map_err(|e| open_error(e.to_string()))?;
+            let count = monitors.len();
+            let monitor = monitors
+                .into_iter()
+                .nth(index)
+                .ok_or_else(|| open_error(format!("only {} monitors found", count)))?;
+
+            Ok(Self {
+                monitor,
+                index,
+                region: None,
+                interval: Duration::from_secs_f64(1.0 / fps.max(1) as f64),
+                last_capture: None,
+            })
+        }
+
+        // Only capture this part of the monitor (clipped to the screen)
+        pub fn with_region(mut self, region: ScreenRegion) -> Self {
+            self.region = Some(region);
+            self
+        }
+    }
+
+    impl FrameSource for ScreenSource {
+        fn describe(&self) -> String {
+            match self.region {
+                Some(r) => format!(
+                    "screen {} (region {}x{} at {},{})",
+                    self.index, r.width, r.height, r.x, r.y
+                ),
+                None => format!("screen {}", self.index),
+            }
+        }
+
+        fn next_frame(&mut self) -> Result<ImageBuffer<Rgb<u8>, Vec<u8>>, CaptureError> {
+            if let Some(last) = self.last_capture {
+                let elapsed = last.elapsed();
+                if elapsed < self.interval {
+                    std::thread::sleep(self.interval - elapsed);
+                }
+            }
+            self.last_capture = Some(Instant::now());
+
+            let screen = self
+                .monitor
+                .capture_image()
+                .map_err(|e| CaptureError::Transient(NokhwaError::ReadFrameError(e.to_string())))?;
+
+            let (width, height) = screen.dimensions();
+            let (x, y, w, h) = match self.region {
+                Some(r) => {
+                    let x = r.x.min(width);
+                    let y = r.y.min(height);
+                    (x, y, r.width.min(width - x), r.height.min(height - y))
+                }
+                None => (0, 0, width, height),
+            };
+            if w == 0 || h == 0 {
+                return Err(CaptureError::Fatal(NokhwaError::GeneralError(format!(
+                    "Screen region is outside the {}x{} screen",
+                    width, height
+                ))));
+            }
+
+            // Drop the alpha channel
+            let region = image::imageops::crop_imm(&screen, x, y, w, h);
+            Ok(ImageBuffer::from_fn(w, h, |px, py| {
+                let [r, g, b, _] = region.get_pixel(px, py).0;
+                Rgb([r, g, b])
+            }))
+        }
+    }
+}
@@ -0,0 +1,312 @@
+pub mod health_check {
+    use crate::controller::input_device;
+    use crate::detector::hand_detector::{HandDetector, RESIZE_FILTERS};
+    use crate::pipeline::frame_pipeline::PreprocessPool;
+    use crate::sensor::webcam;
+    use anyhow::{Result, anyhow};
+    use enigo::{Coordinate, Mouse};
+    use image::{ImageBuffer, Rgb};
+    use std::time::Instant;
+
+    type Frame = ImageBuffer<Rgb<u8>, Vec<u8>>;
+
+    pub struct CheckResult {
+        pub name: &'static str,
+        pub passed: bool,
+        pub detail: String,
+    }
+
+    impl CheckResult {
+        fn from_result(name: &'static str, result: Result<String>) -> Self {
+            match result {
+                Ok(detail) => Self {
+                    name,
+                    passed: true,
+                    detail,
+                },
+                Err(e) => Self {
+                    name,
+                    passed: false,
+                    detail: e.to_string(),
+                },
+            }
+        }
+    }
+
+    // Grey gradient, so the detector sees something other than a constant image
+    fn synthetic_frame() -> Frame {
+        ImageBuffer::from_fn(640, 480, |x, y| {
+            let v = ((x + y) % 256) as u8;
+            Rgb([v, v, v])
+        })
+    }
+
+    // Time one detector pass and report the raw output shapes
+    fn run_detector(detector: &mut HandDetector, frame: &Frame) -> Result<String> {
+        let start = Instant::now();
+        let hands = detector.detect(frame)?;
+        let elapsed = start.elapsed();
+
+        let shapes = detector
+            .output_shapes(frame)?
+            .into_iter()
+            .map(|(name, shape)| format!("{} {:?}", name, shape))
+            .collect::<Vec<String>>()
+            .join(", ");
+
+        Ok(format!(
+            "{:.1} ms, {} hand(s), outputs: {}",
+            elapsed.as_secs_f64() * 1000.0,
+            hands.map_or(0, |h| h.len()),
+            shapes
+        ))
+    }
+
+    fn check_camera() -> Result<(String, Frame)> {
+        let mut camera = webcam::setup()?;
+        webcam::open_stream(&mut camera)?;
+        let frame = webcam::capture_and_decode_frame(&mut camera)?;
+        let _ = webcam::stop_stream(&mut camera);
+
+        let format = camera.camera_format();
+        let (width, height) = frame.dimensions();
+        Ok((
+            format!(
+                "{} reports {}, captured {}x{}",
+                camera.info().human_name(),
+                format,
+                width,
+                height
+            ),
+            frame,
+        ))
+    }
+
+    fn check_input() -> Result<String> {
+        let mut enigo = input_device::create()?;
+        let (x, y) = enigo.location()?;
+
+        // Nudge the cursor by a pixel and put it back
+        enigo.move_mouse(1, 0, Coordinate::Rel)?;
+        enigo.move_mouse(x, y, Coordinate::Abs)?;
+
+        let (width, height) = enigo.main_display()?;
+        Ok(format!(
+            "cursor at ({}, {}), main display {}x{}",
+            x, y, width, height
+        ))
+    }
+
+    // Run every check, continuing past failures so the summary covers all subsystems
+    pub fn run(model_bytes: &[u8]) -> Vec<CheckResult> {
+        let mut results = Vec::new();
+
+        let captured = match check_camera() {
+            Ok((detail, frame)) => {
+                results.push(CheckResult::from_result("camera", Ok(detail)));
+                Some(frame)
+            }
+            Err(e) => {
+                results.push(CheckResult::from_result("camera", Err(e)));
+                None
+            }
+        };
+
+        match HandDetector::new_embedded(model_bytes) {
+            Ok(mut detector) => {
+                results.push(CheckResult::from_result(
+                    "detector (synthetic frame)",
+                    run_detector(&mut detector, &synthetic_frame()),
+                ));
+                let captured_result = match &captured {
+                    Some(frame) => run_detector(&mut detector, frame),
+                    None => Err(anyhow::anyhow!("skipped, no camera frame")),
+                };
+                results.push(CheckResult::from_result(
+                    "detector (camera frame)",
+                    captured_result,
+                ));
+            }
+            Err(e) => results.push(CheckResult::from_result("detector", Err(e))),
+        }
+
+        results.push(CheckResult::from_result("input controller", check_input()));
+
+        // Return
+        results
+    }
+
+    pub fn print_summary(results: &[CheckResult]) {
+        println!("Self-test summary:");
+        for result in results {
+            let status = if result.passed { "PASS" } else { "FAIL" };
+            println!("  [{}] {}: {}", status, result.name, result.detail);
+        }
+    }
+
+    // Detection latency and results of one resize filter on a fixture frame
+    pub struct FilterReport {
+        pub filter: &'static str,
+        pub mean_ms: f64,
+        pub hands: usize,
+        pub best_score: Option<f32>,
+    }
+
+    // Run the detector `runs` times on the frame with each resize filter, for choosing
+    // between detection quality and speed
+    pub fn compare_filters(
+        model_bytes: &[u8],
+        frame: &Frame,
+        runs: u32,
+    ) -> Result<Vec<FilterReport>> {
+        let runs = runs.max(1);
+        let mut reports = Vec::new();
+        for (name, filter) in RESIZE_FILTERS {
+            let mut detector = HandDetector::new_embedded(model_bytes)?
+                .with_resize_filter(filter)
+                .with_logging(false);
+            detector.warmup()?;
+
+            let start = Instant::now();
+            let mut hands = Vec::new();
+            for _ in 0..runs {
+                hands = detector.detect(frame)?.unwrap_or_default();
+            }
+            reports.push(FilterReport {
+                filter: name,
+                mean_ms: start.elapsed().as_secs_f64() * 1000.0 / runs as f64,
+                hands: hands.len(),
+                best_score: hands.iter().map(|hand| hand.score).reduce(f32::max),
+            });
+        }
+        Ok(reports)
+    }
+
+    pub fn print_filter_comparison(reports: &[FilterReport]) {
+        println!("Resize filter comparison:");
+        for report in reports {
+            let score = report
+                .best_score
+                .map_or("-".to_string(), |score| format!("{:.3}", score));
+            println!(
+                "  {:<12} {:>7.2} ms  {} hand(s)  best score {}",
+                report.filter, report.mean_ms, report.hands, score
+            );
+        }
+    }
+
+    // Detection throughput over a batch of frames with one way of preprocessing them
+    pub struct BatchReport {
+        pub mode: String,
+        pub frames_per_second: f64,
+        pub hands: usize,
+    }
+
+    // Detect on every frame of the batch in turn, preprocessing inline (as `detect` does)
+    fn detect_inline(
+        detector: &mut HandDetector,
+        frames: &[Frame],
+        mode: &str,
+    ) -> Result<BatchReport> {
+        let start = Instant::now();
+        let mut hands = 0;
+        for frame in frames {
+            hands += detector.detect(frame)?.map_or(0, |hands| hands.len());
+        }
+        Ok(BatchReport {
+            mode: mode.to_string(),
+            frames_per_second: frames.len() as f64 / start.elapsed().as_secs_f64(),
+            hands,
+        })
+    }
+
+    // Frames preprocessed ahead per pool worker in compare_batch
+    const BATCH_QUEUE_DEPTH: usize = 2;
+
+    // Detect on every frame of the batch with preprocessing inline (as `detect` does), then
+    // overlapped with inference on a PreprocessPool of each size in `pool_threads`
+    pub fn compare_batch(
+        model_bytes: &[u8],
+        frames: &[Frame],
+        pool_threads: &[usize],
+    ) -> Result<Vec<BatchReport>> {
+        let mut detector = HandDetector::new_embedded(model_bytes)?.with_logging(false);
+        detector.warmup()?;
+        let fps = |start: Instant| frames.len() as f64 / start.elapsed().as_secs_f64();
+
+        let mut reports = vec![detect_inline(&mut detector, frames, "inline")?];
+
+        for &threads in pool_threads {
+            let mut pool =
+                PreprocessPool::new(threads, BATCH_QUEUE_DEPTH, detector.preprocess_config());
+            let start = Instant::now();
+            let mut hands = 0;
+            let mut next = frames.iter();
+            loop {
+                // Keep the workers busy while the detector runs on the oldest frame
+                while !pool.is_full() {
+                    let Some(frame) = next.next() else {
+                        break;
+                    };
+                    pool.submit(frame.clone())
+                        .map_err(|_| anyhow!("Preprocessing worker stopped"))?;
+                }
+                let Some(preprocessed) = pool.recv() else {
+                    break;
+                };
+                hands += detector
+                    .detect_preprocessed(preprocessed)?
+                    .map_or(0, |hands| hands.len());
+            }
+            reports.push(BatchReport {
+                mode: format!(
+                    "pool ({} thread{})",
+                    threads,
+                    if threads == 1 { "" } else { "s" }
+                ),
+                frames_per_second: fps(start),
+                hands,
+            });
+        }
+        Ok(reports)
+    }
+
+    pub fn print_batch_comparison(frames: usize, reports: &[BatchReport]) {
+        println!("Batch detection on {} frames:", frames);
+        for report in reports {
+            println!(
+                "  {:<16} {:>7.1} frames/s  {} hand(s)",
+                report.mode, report.frames_per_second, report.hands
+            );
+        }
+    }
+
+    // Detect on the batch with a full scan of every frame, then with ROI tracking, both on
+    // the same tiling grid (COLS, ROWS, overlap) if any, since skipping the tile passes is
+    // where ROI tracking saves the most
+    pub fn compare_roi(
+        model_bytes: &[u8],
+        frames: &[Frame],
+        tiles: Option<(u32, u32, f32)>,
+        expansion: f32,
+        rescan_every: u32,
+    ) -> Result<Vec<BatchReport>> {
+        let new_detector = || -> Result<HandDetector> {
+            let mut detector = HandDetector::new_embedded(model_bytes)?.with_logging(false);
+            if let Some((cols, rows, overlap)) = tiles {
+                detector = detector.with_tiles(cols, rows, overlap)?;
+            }
+            detector.warmup()?;
+            Ok(detector)
+        };
+
+        let full_scan = detect_inline(&mut new_detector()?, frames, "full scan")?;
+        let mut tracking = new_detector()?.with_roi_tracking(expansion, rescan_every)?;
+        let roi = detect_inline(
+            &mut tracking,
+            frames,
+            &format!("ROI x{} / {}", expansion, rescan_every),
+        )?;
+        Ok(vec![full_scan, roi])
+    }
+}
@@ -2,28 +2,946 @@ pub mod webcam {
 
     use image::{ImageBuffer, Rgb};
     use nokhwa::pixel_format::RgbFormat;
-    use nokhwa::utils::{CameraIndex, RequestedFormat, RequestedFormatType};
-    use nokhwa::{Camera, NokhwaError};
+    use nokhwa::utils::{
+        CameraFormat, CameraIndex, ControlValueDescription, ControlValueSetter, FrameFormat,
+        KnownCameraControl, RequestedFormat, RequestedFormatType,
+    };
+    use nokhwa::{Buffer, Camera, NokhwaError};
+    use std::fmt;
+    use std::fs::File;
+    use std::io::{ErrorKind, Read};
+    use std::path::{Path, PathBuf};
+    use std::time::Duration;
 
-    pub fn setup() -> Result<Camera, nokhwa::NokhwaError> {
+    // Capture errors, split by whether the same camera handle can be used again.
+    //
+    // Mapping from `NokhwaError`:
+    // - Transient: `ReadFrameError` (dropped frame / read timeout),
+    //   `ProcessFrameError` (a corrupt frame that failed to decode) and
+    //   `GeneralError` (backend hiccups that usually clear on the next frame).
+    // - Fatal: everything else (`OpenDeviceError`, `OpenStreamError`,
+    //   `StreamShutdownError`, `UnitializedError`, property/structure errors,
+    //   unsupported or unimplemented backend operations). These mean the device
+    //   is gone or the stream is closed, so the camera must be reopened.
+    // - EndOfStream: not from nokhwa, but from finite sources (a closed pipe, the end of a
+    //   replay) that have no more frames to give. Nothing failed, so stop reading.
+    #[derive(Debug)]
+    pub enum CaptureError {
+        Transient(NokhwaError),
+        Fatal(NokhwaError),
+        EndOfStream,
+    }
+
+    impl CaptureError {
+        pub fn classify(error: NokhwaError) -> Self {
+            match error {
+                NokhwaError::ReadFrameError(_)
+                | NokhwaError::ProcessFrameError { .. }
+                | NokhwaError::GeneralError(_) => CaptureError::Transient(error),
+                _ => CaptureError::Fatal(error),
+            }
+        }
+
+        pub fn is_fatal(&self) -> bool {
+            matches!(self, CaptureError::Fatal(_))
+        }
+
+        pub fn is_end_of_stream(&self) -> bool {
+            matches!(self, CaptureError::EndOfStream)
+        }
+    }
+
+    impl fmt::Display for CaptureError {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            match self {
+                CaptureError::Transient(e) => write!(f, "transient capture error: {}", e),
+                CaptureError::Fatal(e) => write!(f, "fatal capture error: {}", e),
+                CaptureError::EndOfStream => write!(f, "end of stream"),
+            }
+        }
+    }
+
+    impl std::error::Error for CaptureError {}
+
+    // Why a camera could not be opened, so the user can be told what to do about it.
+    //
+    // nokhwa reports all of these as `OpenDeviceError`/`OpenStreamError` with a
+    // backend-specific message, so they are told apart by the message text
+    // (V4L2 errno strings, AVFoundation/Media Foundation messages) and, on macOS,
+    // by the camera authorization status.
+    #[derive(Debug)]
+    pub enum CameraOpenError {
+        NoDevice(NokhwaError),
+        PermissionDenied(NokhwaError),
+        Busy(NokhwaError),
+        Other(NokhwaError),
+    }
+
+    impl CameraOpenError {
+        pub fn classify(error: NokhwaError) -> Self {
+            // Only macOS has an explicit camera authorization, elsewhere this is always true
+            if !nokhwa::nokhwa_check() {
+                return CameraOpenError::PermissionDenied(error);
+            }
+
+            let message = error.to_string().to_lowercase();
+            let mentions = |patterns: &[&str]| patterns.iter().any(|p| message.contains(p));
+
+            if mentions(&[
+                "permission denied",
+                "not authorized",
+                "access denied",
+                "access is denied",
+            ]) {
+                CameraOpenError::PermissionDenied(error)
+            } else if mentions(&["busy", "in use", "being used"]) {
+                CameraOpenError::Busy(error)
+            } else if mentions(&[
+                "no such",
+                "not found",
+                "no device",
+                "no camera",
+                "out of range",
+            ]) || no_cameras_connected()
+            {
+                CameraOpenError::NoDevice(error)
+            } else {
+                CameraOpenError::Other(error)
+            }
+        }
+
+        // What the user can do about it
+        pub fn guidance(&self) -> &'static str {
+            match self {
+                CameraOpenError::NoDevice(_) => {
+                    "No camera found. Check that one is connected and not disabled."
+                }
+                CameraOpenError::PermissionDenied(_) => {
+                    "Camera access was denied. Grant camera permission to this terminal/app \
+                     (macOS: System Settings > Privacy & Security > Camera, \
+                     Linux: add your user to the 'video' group)."
+                }
+                CameraOpenError::Busy(_) => {
+                    "The camera is in use by another application. Close it and try again."
+                }
+                CameraOpenError::Other(_) => "The camera could not be opened.",
+            }
+        }
+    }
+
+    impl fmt::Display for CameraOpenError {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            match self {
+                CameraOpenError::NoDevice(e)
+                | CameraOpenError::PermissionDenied(e)
+                | CameraOpenError::Busy(e)
+                | CameraOpenError::Other(e) => write!(f, "{} ({})", self.guidance(), e),
+            }
+        }
+    }
+
+    impl std::error::Error for CameraOpenError {}
+
+    fn no_cameras_connected() -> bool {
+        nokhwa::native_api_backend()
+            .and_then(|backend| nokhwa::query(backend).ok())
+            .is_some_and(|cameras| cameras.is_empty())
+    }
+
+    // Exponential backoff between reconnection attempts (base, 2*base, 4*base, ... up to max)
+    pub struct Backoff {
+        base: Duration,
+        max: Duration,
+        attempt: u32,
+    }
+
+    impl Backoff {
+        pub fn new(base: Duration, max: Duration) -> Self {
+            Self {
+                base,
+                max,
+                attempt: 0,
+            }
+        }
+
+        pub fn next_delay(&mut self) -> Duration {
+            let factor = 1u32 << self.attempt.min(16);
+            self.attempt += 1;
+            (self.base * factor).min(self.max)
+        }
+
+        pub fn reset(&mut self) {
+            self.attempt = 0;
+        }
+    }
+
+    pub fn setup() -> Result<Camera, CameraOpenError> {
         // Setup Camera //
         let index = CameraIndex::Index(0);
         let requested =
             RequestedFormat::new::<RgbFormat>(RequestedFormatType::AbsoluteHighestFrameRate);
-        println!("Opening camera...");
+        eprintln!("Opening camera...");
+
+        // Return
+        Camera::new(index, requested).map_err(CameraOpenError::classify)
+    }
+
+    pub fn setup_with_format(index: u32, format: CameraFormat) -> Result<Camera, CameraOpenError> {
+        // Setup Camera with an exact format (see supported_formats) //
+        let requested = RequestedFormat::new::<RgbFormat>(RequestedFormatType::Exact(format));
+        eprintln!("Opening camera {} with format {}...", index, format);
+
+        // Return
+        Camera::new(CameraIndex::Index(index), requested).map_err(CameraOpenError::classify)
+    }
+
+    pub fn supported_formats(index: u32) -> Result<Vec<CameraFormat>, CameraOpenError> {
+        // Open the camera without negotiating a format, just to query it
+        let requested = RequestedFormat::new::<RgbFormat>(RequestedFormatType::None);
+        let mut camera =
+            Camera::new(CameraIndex::Index(index), requested).map_err(CameraOpenError::classify)?;
+
+        let mut formats = camera
+            .compatible_camera_formats()
+            .map_err(CameraOpenError::classify)?;
+        formats.sort_by_key(|f| (f.width(), f.height(), f.frame_rate()));
 
         // Return
-        Camera::new(index, requested)
+        Ok(formats)
+    }
+
+    // Camera::open_stream, with the error classified like setup's, logging the format the
+    // camera settled on. Does nothing if the stream is already open.
+    pub fn open_stream(camera: &mut Camera) -> Result<(), CameraOpenError> {
+        if camera.is_stream_open() {
+            return Ok(());
+        }
+        camera.open_stream().map_err(CameraOpenError::classify)?;
+        eprintln!("Camera stream open at {}", camera.camera_format());
+        Ok(())
+    }
+
+    // Release the camera's stream. Does nothing if it isn't open.
+    pub fn stop_stream(camera: &mut Camera) -> Result<(), NokhwaError> {
+        if !camera.is_stream_open() {
+            return Ok(());
+        }
+        camera.stop_stream()
+    }
+
+    // Image controls of a camera. Which ones a camera has, and their ranges, depend on the
+    // device and the platform backend (most cameras on Linux's V4L2 expose brightness,
+    // contrast, saturation, gain and exposure; other backends often fewer).
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum ControlKind {
+        Brightness,
+        Contrast,
+        Saturation,
+        Sharpness,
+        Gamma,
+        Gain,
+        Exposure,
+        WhiteBalance,
+        BacklightComp,
+    }
+
+    impl ControlKind {
+        pub fn from_name(name: &str) -> Option<Self> {
+            match name {
+                "brightness" => Some(ControlKind::Brightness),
+                "contrast" => Some(ControlKind::Contrast),
+                "saturation" => Some(ControlKind::Saturation),
+                "sharpness" => Some(ControlKind::Sharpness),
+                "gamma" => Some(ControlKind::Gamma),
+                "gain" => Some(ControlKind::Gain),
+                "exposure" => Some(ControlKind::Exposure),
+                "white_balance" => Some(ControlKind::WhiteBalance),
+                "backlight" => Some(ControlKind::BacklightComp),
+                _ => None,
+            }
+        }
+
+        fn known(self) -> KnownCameraControl {
+            match self {
+                ControlKind::Brightness => KnownCameraControl::Brightness,
+                ControlKind::Contrast => KnownCameraControl::Contrast,
+                ControlKind::Saturation => KnownCameraControl::Saturation,
+                ControlKind::Sharpness => KnownCameraControl::Sharpness,
+                ControlKind::Gamma => KnownCameraControl::Gamma,
+                ControlKind::Gain => KnownCameraControl::Gain,
+                ControlKind::Exposure => KnownCameraControl::Exposure,
+                ControlKind::WhiteBalance => KnownCameraControl::WhiteBalance,
+                ControlKind::BacklightComp => KnownCameraControl::BacklightComp,
+            }
+        }
+    }
+
+    fn control_error(property: String, value: String, error: String) -> NokhwaError {
+        NokhwaError::SetPropertyError {
+            property,
+            value,
+            error,
+        }
     }
 
+    // Set an integer camera control, failing if the camera doesn't have it or the value is
+    // outside the range it reports. Settings may not survive a reconnect, use TunedCamera to
+    // keep them.
+    pub fn set_control(
+        camera: &mut Camera,
+        kind: ControlKind,
+        value: i64,
+    ) -> Result<(), NokhwaError> {
+        let control = kind.known();
+        let current = camera.camera_control(control).map_err(|e| {
+            control_error(
+                control.to_string(),
+                value.to_string(),
+                format!("not supported by this camera ({})", e),
+            )
+        })?;
+        if let ControlValueDescription::IntegerRange { min, max, .. } = *current.description()
+            && !(min..=max).contains(&value)
+        {
+            return Err(control_error(
+                control.to_string(),
+                value.to_string(),
+                format!("outside the camera's range {} to {}", min, max),
+            ));
+        }
+        camera.set_camera_control(control, ControlValueSetter::Integer(value))
+    }
+
+    pub fn set_brightness(camera: &mut Camera, value: i64) -> Result<(), NokhwaError> {
+        set_control(camera, ControlKind::Brightness, value)
+    }
+
+    // Turn the camera's automatic exposure on or off (off keeps the exposure set with
+    // ControlKind::Exposure, so it doesn't drift with what's in view). nokhwa has no
+    // cross-platform control for it, so this is only supported on Linux (V4L2).
+    pub fn auto_exposure(camera: &mut Camera, on: bool) -> Result<(), NokhwaError> {
+        #[cfg(target_os = "linux")]
+        {
+            // V4L2_CID_EXPOSURE_AUTO: 1 is manual, 3 is aperture priority (auto exposure)
+            let control = KnownCameraControl::Other(0x009a_0901);
+            let mode = if on { 3 } else { 1 };
+            camera
+                .set_camera_control(control, ControlValueSetter::Integer(mode))
+                .map_err(|e| {
+                    control_error(
+                        "auto exposure".to_string(),
+                        on.to_string(),
+                        format!("not supported by this camera ({})", e),
+                    )
+                })
+        }
+        #[cfg(not(target_os = "linux"))]
+        {
+            let _ = camera;
+            Err(control_error(
+                "auto exposure".to_string(),
+                on.to_string(),
+                "only supported on Linux".to_string(),
+            ))
+        }
+    }
+
+    // Image controls requested for a camera, applied when it's opened and again after each
+    // reconnect (see TunedCamera)
+    #[derive(Debug, Clone, Default)]
+    pub struct CameraSettings {
+        pub auto_exposure: Option<bool>,
+        pub controls: Vec<(ControlKind, i64)>,
+    }
+
+    impl CameraSettings {
+        // Apply every setting, logging the ones the camera refuses and carrying on
+        pub fn apply(&self, camera: &mut Camera) {
+            if let Some(on) = self.auto_exposure
+                && let Err(e) = auto_exposure(camera, on)
+            {
+                eprintln!("Failed to set auto exposure: {}", e);
+            }
+            for &(kind, value) in &self.controls {
+                if let Err(e) = set_control(camera, kind, value) {
+                    eprintln!("Failed to set camera control: {}", e);
+                }
+            }
+        }
+    }
+
+    // A camera that applies its CameraSettings when created and after every reconnect, which
+    // otherwise opens a fresh camera with the driver's defaults
+    pub struct TunedCamera {
+        camera: Camera,
+        settings: CameraSettings,
+    }
+
+    impl TunedCamera {
+        pub fn new(mut camera: Camera, settings: CameraSettings) -> Self {
+            settings.apply(&mut camera);
+            Self { camera, settings }
+        }
+    }
+
+    impl FrameSource for TunedCamera {
+        fn next_frame(&mut self) -> Result<ImageBuffer<Rgb<u8>, Vec<u8>>, CaptureError> {
+            self.camera.next_frame()
+        }
+
+        fn describe(&self) -> String {
+            self.camera.describe()
+        }
+
+        fn reconnect(&mut self, max_attempts: u32) -> Result<(), CameraOpenError> {
+            reconnect(&mut self.camera, max_attempts)?;
+            self.settings.apply(&mut self.camera);
+            Ok(())
+        }
+    }
+
+    pub fn reconnect(camera: &mut Camera, max_attempts: u32) -> Result<(), CameraOpenError> {
+        // Release the old stream, ignoring errors since the device may already be gone
+        let _ = stop_stream(camera);
+
+        let mut backoff = Backoff::new(Duration::from_millis(250), Duration::from_secs(4));
+        let mut last_error = CameraOpenError::Other(NokhwaError::GeneralError(
+            "no reconnection attempts made".into(),
+        ));
+
+        for attempt in 1..=max_attempts {
+            let delay = backoff.next_delay();
+            eprintln!(
+                "Reconnecting camera (attempt {}/{}) in {:?}...",
+                attempt, max_attempts, delay
+            );
+            std::thread::sleep(delay);
+
+            match setup().and_then(|mut new_camera| {
+                open_stream(&mut new_camera)?;
+                Ok(new_camera)
+            }) {
+                Ok(new_camera) => {
+                    *camera = new_camera;
+                    return Ok(());
+                }
+                Err(e) => last_error = e,
+            }
+        }
+
+        // Return
+        Err(last_error)
+    }
+
+    // Capture a frame and decode it to RGB, checking it has the camera's resolution so
+    // a malformed buffer never reaches the detector (a mismatch is a transient error).
     pub fn capture_and_decode_frame(
         camera: &mut Camera,
-    ) -> Result<ImageBuffer<Rgb<u8>, Vec<u8>>, NokhwaError> {
+    ) -> Result<ImageBuffer<Rgb<u8>, Vec<u8>>, CaptureError> {
         // Capture frame
-        let frame = camera.frame()?;
+        let frame = camera.frame().map_err(CaptureError::classify)?;
         // Decode frame as image
-        let decoded = frame.decode_image::<RgbFormat>()?;
+        let decoded = decode_buffer(&frame)?;
+        // Some cameras hand out empty frames while starting up, skip them
+        if decoded.width() == 0 || decoded.height() == 0 {
+            return Err(CaptureError::Transient(NokhwaError::ReadFrameError(
+                format!("empty {}x{} frame", decoded.width(), decoded.height()),
+            )));
+        }
+        let expected = camera.resolution();
+        if decoded.dimensions() != (expected.width(), expected.height()) {
+            return Err(malformed_frame(
+                frame.source_frame_format(),
+                format!(
+                    "decoded a {}x{} frame but the camera reports {}",
+                    decoded.width(),
+                    decoded.height(),
+                    expected
+                ),
+            ));
+        }
         // Return
         Ok(decoded)
     }
+
+    fn malformed_frame(format: FrameFormat, error: String) -> CaptureError {
+        CaptureError::Transient(NokhwaError::ProcessFrameError {
+            src: format,
+            destination: "RGB888".to_string(),
+            error,
+        })
+    }
+
+    // Decode a captured buffer to RGB after checking it holds a whole frame. nokhwa sizes
+    // the image by the resolution it negotiated, so a buffer of another size would decode
+    // into a garbled or misaligned image: uncompressed formats need exactly the bytes of
+    // that resolution, and MJPEG frames are decoded here so the size comes from the JPEG
+    // header, after checking they weren't cut short (e.g. on a USB bandwidth hiccup).
+    pub fn decode_buffer(frame: &Buffer) -> Result<ImageBuffer<Rgb<u8>, Vec<u8>>, CaptureError> {
+        let format = frame.source_frame_format();
+        let data = frame.buffer();
+        let resolution = frame.resolution();
+        let pixels = resolution.width() as usize * resolution.height() as usize;
+
+        let expected_len = match format {
+            FrameFormat::MJPEG => {
+                // Some cameras pad the frame with zeros after the end of image marker
+                let end = data.iter().rposition(|&b| b != 0).map_or(0, |i| i + 1);
+                let jpeg = &data[..end];
+                if !jpeg.starts_with(&[0xFF, 0xD8]) || !jpeg.ends_with(&[0xFF, 0xD9]) {
+                    return Err(malformed_frame(
+                        format,
+                        format!("truncated MJPEG frame ({} bytes)", data.len()),
+                    ));
+                }
+                return image::load_from_memory_with_format(jpeg, image::ImageFormat::Jpeg)
+                    .map(|image| image.to_rgb8())
+                    .map_err(|e| malformed_frame(format, e.to_string()));
+            }
+            FrameFormat::YUYV => pixels * 2,
+            FrameFormat::NV12 => pixels * 3 / 2,
+            FrameFormat::GRAY => pixels,
+            FrameFormat::RAWRGB | FrameFormat::RAWBGR => pixels * 3,
+        };
+        if data.len() != expected_len {
+            return Err(malformed_frame(
+                format,
+                format!(
+                    "{} buffer of {} bytes, expected {} for {}",
+                    format,
+                    data.len(),
+                    expected_len,
+                    resolution
+                ),
+            ));
+        }
+        frame
+            .decode_image::<RgbFormat>()
+            .map_err(CaptureError::classify)
+    }
+
+    // Anything that produces decoded RGB frames (a camera, a pipe, ...)
+    pub trait FrameSource {
+        fn next_frame(&mut self) -> Result<ImageBuffer<Rgb<u8>, Vec<u8>>, CaptureError>;
+
+        // Human readable description (device name, format) for logs and bug reports
+        fn describe(&self) -> String;
+
+        // Try to recover after a fatal error. Sources that can't be reopened just fail.
+        fn reconnect(&mut self, _max_attempts: u32) -> Result<(), CameraOpenError> {
+            Err(CameraOpenError::Other(NokhwaError::GeneralError(
+                "this frame source can't be reopened".into(),
+            )))
+        }
+    }
+
+    // So a source can be lent out (e.g. to HandDetector::stream) and used again afterwards
+    impl<S: FrameSource + ?Sized> FrameSource for &mut S {
+        fn next_frame(&mut self) -> Result<ImageBuffer<Rgb<u8>, Vec<u8>>, CaptureError> {
+            (**self).next_frame()
+        }
+
+        fn describe(&self) -> String {
+            (**self).describe()
+        }
+
+        fn reconnect(&mut self, max_attempts: u32) -> Result<(), CameraOpenError> {
+            (**self).reconnect(max_attempts)
+        }
+    }
+
+    impl FrameSource for Camera {
+        fn next_frame(&mut self) -> Result<ImageBuffer<Rgb<u8>, Vec<u8>>, CaptureError> {
+            capture_and_decode_frame(self)
+        }
+
+        fn describe(&self) -> String {
+            format!("{} ({})", self.info().human_name(), self.camera_format())
+        }
+
+        fn reconnect(&mut self, max_attempts: u32) -> Result<(), CameraOpenError> {
+            reconnect(self, max_attempts)
+        }
+    }
+
+    // Raw RGB frames read from a pipe, FIFO, file descriptor (/dev/fd/N) or stdin.
+    //
+    // Framing: there are no headers or delimiters, each frame is exactly
+    // `height * stride` bytes and frames follow each other back to back.
+    // Each row is `width * 3` bytes of packed R, G, B (8 bits per channel,
+    // top row first, left to right), followed by `stride - width * 3` padding
+    // bytes that are skipped. With no padding the stride is just `width * 3`.
+    //
+    // For example, from GStreamer:
+    // gst-launch-1.0 -q <source> ! videoconvert ! video/x-raw,format=RGB,width=640,height=480 ! fdsink
+    // (GStreamer pads RGB rows to a multiple of 4 bytes, which 640 * 3 already is)
+    pub struct PipeSource {
+        reader: Box<dyn Read>,
+        width: u32,
+        height: u32,
+        stride: usize,
+        buffer: Vec<u8>,
+    }
+
+    impl PipeSource {
+        pub fn new(
+            reader: Box<dyn Read>,
+            width: u32,
+            height: u32,
+            stride: Option<usize>,
+        ) -> Result<Self, NokhwaError> {
+            let row_bytes = width as usize * 3;
+            let stride = stride.unwrap_or(row_bytes);
+            if width == 0 || height == 0 || stride < row_bytes {
+                return Err(NokhwaError::GeneralError(format!(
+                    "Invalid pipe frame layout: {}x{} with stride {} (needs at least {})",
+                    width, height, stride, row_bytes
+                )));
+            }
+
+            Ok(Self {
+                reader,
+                width,
+                height,
+                stride,
+                buffer: vec![0; stride * height as usize],
+            })
+        }
+
+        // Read frames from a path, or from stdin when the path is "-"
+        pub fn open(
+            path: &Path,
+            width: u32,
+            height: u32,
+            stride: Option<usize>,
+        ) -> Result<Self, NokhwaError> {
+            let reader: Box<dyn Read> = if path == Path::new("-") {
+                Box::new(std::io::stdin())
+            } else {
+                let file = File::open(path).map_err(|e| {
+                    NokhwaError::OpenDeviceError(path.display().to_string(), e.to_string())
+                })?;
+                Box::new(file)
+            };
+            eprintln!(
+                "Reading {}x{} RGB frames from {}...",
+                width,
+                height,
+                path.display()
+            );
+
+            // Return
+            Self::new(reader, width, height, stride)
+        }
+    }
+
+    impl FrameSource for PipeSource {
+        fn describe(&self) -> String {
+            format!(
+                "pipe ({}x{} RGB, stride {})",
+                self.width, self.height, self.stride
+            )
+        }
+
+        fn next_frame(&mut self) -> Result<ImageBuffer<Rgb<u8>, Vec<u8>>, CaptureError> {
+            // A closed pipe ends the stream (dropping a partial last frame), other read
+            // errors may clear up
+            self.reader
+                .read_exact(&mut self.buffer)
+                .map_err(|e| match e.kind() {
+                    ErrorKind::UnexpectedEof => CaptureError::EndOfStream,
+                    _ => CaptureError::Transient(NokhwaError::ReadFrameError(e.to_string())),
+                })?;
+
+            // Drop the row padding
+            let row_bytes = self.width as usize * 3;
+            let pixels = self
+                .buffer
+                .chunks_exact(self.stride)
+                .flat_map(|row| &row[..row_bytes])
+                .copied()
+                .collect();
+
+            // Return
+            ImageBuffer::from_raw(self.width, self.height, pixels).ok_or_else(|| {
+                CaptureError::Transient(NokhwaError::ProcessFrameError {
+                    src: nokhwa::utils::FrameFormat::RAWRGB,
+                    destination: "RGB888".into(),
+                    error: "frame buffer size mismatch".into(),
+                })
+            })
+        }
+    }
+
+    // Normalized (x, y) position of the synthetic hand at a frame number
+    pub type Trajectory = dyn Fn(u64) -> (f32, f32);
+
+    // Generated frames for running the pipeline without a camera (CI, tests, new
+    // contributors): a colored blob, or a pasted hand image (`with_sprite`) so the detector
+    // actually finds a hand, centered on `trajectory(frame)`. Frames are deterministic, so
+    // expected detections can be compared against `position`.
+    pub struct SyntheticSource {
+        width: u32,
+        height: u32,
+        trajectory: Box<Trajectory>,
+        frame: u64,
+        // The stream ends (CaptureError::EndOfStream) after this many frames
+        frame_count: Option<u64>,
+        // Blob radius as a fraction of the frame height
+        radius: f32,
+        color: Rgb<u8>,
+        background: Rgb<u8>,
+        sprite: Option<ImageBuffer<Rgb<u8>, Vec<u8>>>,
+    }
+
+    impl SyntheticSource {
+        pub fn new(
+            width: u32,
+            height: u32,
+            trajectory: impl Fn(u64) -> (f32, f32) + 'static,
+        ) -> Result<Self, NokhwaError> {
+            if width == 0 || height == 0 {
+                return Err(NokhwaError::GeneralError(format!(
+                    "Invalid synthetic frame size {}x{}",
+                    width, height
+                )));
+            }
+
+            Ok(Self {
+                width,
+                height,
+                trajectory: Box::new(trajectory),
+                frame: 0,
+                frame_count: None,
+                radius: 0.1,
+                color: Rgb([224, 172, 138]),
+                background: Rgb([32, 32, 32]),
+                sprite: None,
+            })
+        }
+
+        // Back and forth between two normalized points, one way every `frames` frames
+        pub fn linear(
+            width: u32,
+            height: u32,
+            from: (f32, f32),
+            to: (f32, f32),
+            frames: u64,
+        ) -> Result<Self, NokhwaError> {
+            let frames = frames.max(1);
+            Self::new(width, height, move |n| {
+                let phase = (n % (2 * frames)) as f32 / frames as f32;
+                let t = if phase <= 1.0 { phase } else { 2.0 - phase };
+                (from.0 + (to.0 - from.0) * t, from.1 + (to.1 - from.1) * t)
+            })
+        }
+
+        pub fn with_frame_count(mut self, frame_count: u64) -> Self {
+            self.frame_count = Some(frame_count);
+            self
+        }
+
+        pub fn with_radius(mut self, radius: f32) -> Self {
+            self.radius = radius;
+            self
+        }
+
+        pub fn with_color(mut self, color: Rgb<u8>) -> Self {
+            self.color = color;
+            self
+        }
+
+        // Paste this image (e.g. a photo of a hand) instead of drawing the blob
+        pub fn with_sprite(mut self, sprite: ImageBuffer<Rgb<u8>, Vec<u8>>) -> Self {
+            self.sprite = Some(sprite);
+            self
+        }
+
+        // Where the hand is drawn at a frame number (normalized)
+        pub fn position(&self, frame: u64) -> (f32, f32) {
+            (self.trajectory)(frame)
+        }
+
+        // Number of the next frame to be generated
+        pub fn frame_number(&self) -> u64 {
+            self.frame
+        }
+
+        fn render(&self, frame: u64) -> ImageBuffer<Rgb<u8>, Vec<u8>> {
+            let mut image = ImageBuffer::from_pixel(self.width, self.height, self.background);
+            let (x, y) = self.position(frame);
+            let (cx, cy) = (x * self.width as f32, y * self.height as f32);
+
+            match &self.sprite {
+                Some(sprite) => {
+                    let left = cx as i64 - sprite.width() as i64 / 2;
+                    let top = cy as i64 - sprite.height() as i64 / 2;
+                    image::imageops::overlay(&mut image, sprite, left, top);
+                }
+                None => {
+                    let radius = self.radius * self.height as f32;
+                    for (px, py, pixel) in image.enumerate_pixels_mut() {
+                        let (dx, dy) = (px as f32 + 0.5 - cx, py as f32 + 0.5 - cy);
+                        if dx * dx + dy * dy <= radius * radius {
+                            *pixel = self.color;
+                        }
+                    }
+                }
+            }
+            image
+        }
+    }
+
+    impl FrameSource for SyntheticSource {
+        fn describe(&self) -> String {
+            format!(
+                "synthetic ({}x{}, {})",
+                self.width,
+                self.height,
+                if self.sprite.is_some() {
+                    "sprite"
+                } else {
+                    "blob"
+                }
+            )
+        }
+
+        fn next_frame(&mut self) -> Result<ImageBuffer<Rgb<u8>, Vec<u8>>, CaptureError> {
+            if self.frame_count.is_some_and(|count| self.frame >= count) {
+                return Err(CaptureError::EndOfStream);
+            }
+            let image = self.render(self.frame);
+            self.frame += 1;
+            Ok(image)
+        }
+    }
+
+    // Image extensions FileSource picks up from a directory
+    const IMAGE_EXTENSIONS: [&str; 5] = ["png", "jpg", "jpeg", "bmp", "ppm"];
+
+    // Replays a recorded session stored as an image sequence (one file per frame, in file
+    // name order, e.g. frame_00001.png), for reproducing a precise moment while debugging.
+    // There is no video decoding: convert videos to frames first, e.g. with
+    // `ffmpeg -i session.mp4 frames/frame_%05d.png`.
+    pub struct FileSource {
+        dir: PathBuf,
+        frames: Vec<PathBuf>,
+        // Index of the frame the next `next_frame` returns
+        position: usize,
+    }
+
+    impl FileSource {
+        pub fn open(dir: &Path) -> Result<Self, NokhwaError> {
+            let open_error = |e: std::io::Error| {
+                NokhwaError::OpenDeviceError(dir.display().to_string(), e.to_string())
+            };
+            let mut frames: Vec<PathBuf> = std::fs::read_dir(dir)
+                .map_err(open_error)?
+                .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+                .filter(|path| {
+                    path.extension()
+                        .and_then(|ext| ext.to_str())
+                        .is_some_and(|ext| IMAGE_EXTENSIONS.contains(&ext.to_lowercase().as_str()))
+                })
+                .collect();
+            if frames.is_empty() {
+                return Err(NokhwaError::OpenDeviceError(
+                    dir.display().to_string(),
+                    "no image files found".into(),
+                ));
+            }
+            frames.sort();
+
+            Ok(Self {
+                dir: dir.to_path_buf(),
+                frames,
+                position: 0,
+            })
+        }
+
+        // Number of frames in the sequence
+        pub fn len(&self) -> usize {
+            self.frames.len()
+        }
+
+        pub fn is_empty(&self) -> bool {
+            self.frames.is_empty()
+        }
+
+        // Make `frame_index` the next frame returned. Seeks past the end are clamped to the
+        // last frame, so replay can always show something; returns the index seeked to.
+        pub fn seek(&mut self, frame_index: usize) -> usize {
+            self.position = frame_index.min(self.frames.len().saturating_sub(1));
+            self.position
+        }
+
+        // Index of the frame the next `next_frame` call returns
+        pub fn position(&self) -> usize {
+            self.position
+        }
+    }
+
+    impl FrameSource for FileSource {
+        fn describe(&self) -> String {
+            format!(
+                "image sequence ({} frames in {})",
+                self.frames.len(),
+                self.dir.display()
+            )
+        }
+
+        fn next_frame(&mut self) -> Result<ImageBuffer<Rgb<u8>, Vec<u8>>, CaptureError> {
+            let Some(path) = self.frames.get(self.position) else {
+                return Err(CaptureError::EndOfStream);
+            };
+            self.position += 1;
+            // An unreadable frame is skipped, the next one may be fine
+            image::open(path).map(|image| image.to_rgb8()).map_err(|e| {
+                CaptureError::Transient(NokhwaError::ReadFrameError(format!(
+                    "{}: {}",
+                    path.display(),
+                    e
+                )))
+            })
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        #[test]
+        fn closed_pipe_ends_the_stream() {
+            // One 2x2 frame, then half of another
+            let bytes: Vec<u8> = (0..18).collect();
+            let mut pipe = PipeSource::new(
+                std::boxed::Box::new(std::io::Cursor::new(bytes)),
+                2,
+                2,
+                None,
+            )
+            .unwrap();
+
+            let frame = pipe.next_frame().unwrap();
+            assert_eq!(frame.get_pixel(1, 1).0, [9, 10, 11]);
+            assert!(pipe.next_frame().unwrap_err().is_end_of_stream());
+        }
+
+        #[test]
+        fn replay_ends_after_the_last_frame() {
+            let dir = std::env::temp_dir().join(format!("fingers-replay-{}", std::process::id()));
+            std::fs::create_dir_all(&dir).unwrap();
+            for (index, value) in [10u8, 20].into_iter().enumerate() {
+                ImageBuffer::from_pixel(2, 2, Rgb([value; 3]))
+                    .save(dir.join(format!("frame_{index:05}.png")))
+                    .unwrap();
+            }
+
+            let mut replay = FileSource::open(&dir).unwrap();
+            assert_eq!(replay.next_frame().unwrap().get_pixel(0, 0).0, [10; 3]);
+            assert_eq!(replay.next_frame().unwrap().get_pixel(0, 0).0, [20; 3]);
+            assert!(replay.next_frame().unwrap_err().is_end_of_stream());
+            std::fs::remove_dir_all(&dir).unwrap();
+        }
+    }
 }
@@ -0,0 +1,119 @@
+pub mod detection_server {
+    use crate::detector::hand_detector::HandDetails;
+    use anyhow::{Result, anyhow};
+    use serde::Serialize;
+    use std::sync::{Arc, Mutex};
+    use std::thread::JoinHandle;
+    use std::time::{SystemTime, UNIX_EPOCH};
+    use tiny_http::{Header, Method, Request, Response, Server};
+
+    // The most recent frame's detections, as served by `GET /detections`
+    #[derive(Debug, Clone, Default, Serialize)]
+    pub struct Detections {
+        // Frame number since startup
+        pub frame: u64,
+        // When the frame was captured, in ms since the Unix epoch
+        pub timestamp_ms: u128,
+        pub hands: Vec<HandDetails>,
+    }
+
+    // Slot holding the latest detections. The detect loop only swaps an Arc under the
+    // lock, and requests clone that Arc and serialize outside of it, so a slow client
+    // never holds up the loop.
+    #[derive(Clone, Default)]
+    pub struct LatestDetections {
+        slot: Arc<Mutex<Arc<Detections>>>,
+    }
+
+    impl LatestDetections {
+        pub fn publish(&self, frame: u64, captured: SystemTime, hands: &[HandDetails]) {
+            let detections = Arc::new(Detections {
+                frame,
+                timestamp_ms: captured
+                    .duration_since(UNIX_EPOCH)
+                    .map(|d| d.as_millis())
+                    .unwrap_or(0),
+                hands: hands.to_vec(),
+            });
+            if let Ok(mut slot) = self.slot.lock() {
+                *slot = detections;
+            }
+        }
+
+        pub fn get(&self) -> Arc<Detections> {
+            self.slot
+                .lock()
+                .map(|slot| Arc::clone(&slot))
+                .unwrap_or_default()
+        }
+    }
+
+    // Tiny HTTP server on its own thread:
+    //   GET /detections  the latest Detections as JSON
+    //   GET /health      200 "ok" while the server is up
+    pub struct DetectionServer {
+        server: Arc<Server>,
+        latest: LatestDetections,
+        handle: Option<JoinHandle<()>>,
+    }
+
+    impl DetectionServer {
+        pub fn spawn(address: &str) -> Result<Self> {
+            let server = Arc::new(
+                Server::http(address)
+                    .map_err(|e| anyhow!("Failed to start HTTP server on {}: {}", address, e))?,
+            );
+            let latest = LatestDetections::default();
+
+            let thread_server = Arc::clone(&server);
+            let thread_latest = latest.clone();
+            let handle = std::thread::spawn(move || {
+                for request in thread_server.incoming_requests() {
+                    if let Err(e) = respond(request, &thread_latest) {
+                        eprintln!("Failed to answer HTTP request: {}", e);
+                    }
+                }
+            });
+
+            Ok(Self {
+                server,
+                latest,
+                handle: Some(handle),
+            })
+        }
+
+        // Handle for the detect loop to publish into
+        pub fn latest(&self) -> &LatestDetections {
+            &self.latest
+        }
+    }
+
+    fn respond(request: Request, latest: &LatestDetections) -> Result<()> {
+        // Ignore the query string
+        let path = request.url().split('?').next().unwrap_or_default();
+        let response = match (request.method(), path) {
+            (Method::Get, "/detections") => {
+                let json = serde_json::to_string(&*latest.get())?;
+                Response::from_string(json).with_header(
+                    Header::from_bytes(&b"Content-Type"[..], &b"application/json"[..])
+                        .map_err(|_| anyhow!("Invalid header"))?,
+                )
+            }
+            (Method::Get, "/health") => Response::from_string("ok"),
+            (Method::Get, _) => Response::from_string("not found").with_status_code(404),
+            _ => Response::from_string("method not allowed").with_status_code(405),
+        };
+        request.respond(response)?;
+        Ok(())
+    }
+
+    impl Drop for DetectionServer {
+        fn drop(&mut self) {
+            // Makes incoming_requests end so the thread exits
+            self.server.unblock();
+            if let Some(handle) = self.handle.take() {
+                let _ = handle.join();
+            }
+        }
+    }
+}
@@ -0,0 +1,346 @@
+pub mod filters {
+    use crate::detector::hand_detector::{Box, Landmark};
+    use crate::landmarker::hand_landmarker::{
+        INDEX_TIP, MIDDLE_TIP, NUM_LANDMARKS, PINKY_TIP, RING_TIP, THUMB_TIP,
+    };
+    use std::collections::{HashMap, VecDeque};
+    use std::f32::consts::PI;
+    use std::time::Instant;
+
+    const FINGERTIPS: [usize; 5] = [THUMB_TIP, INDEX_TIP, MIDDLE_TIP, RING_TIP, PINKY_TIP];
+    // Normalized distance a hand can move between frames before its filter state is dropped
+    const DEFAULT_JUMP_THRESHOLD: f32 = 0.25;
+
+    fn distance(a: (f32, f32), b: (f32, f32)) -> f32 {
+        ((a.0 - b.0).powi(2) + (a.1 - b.1).powi(2)).sqrt()
+    }
+
+    // Exponential moving average: alpha = 1.0 follows the input exactly, smaller is smoother
+    fn ema(previous: f32, current: f32, alpha: f32) -> f32 {
+        previous + alpha * (current - previous)
+    }
+
+    // Median of a few values (NaN-free input)
+    fn median(values: &mut [f32]) -> f32 {
+        values.sort_by(|a, b| a.total_cmp(b));
+        let mid = values.len() / 2;
+        if values.len().is_multiple_of(2) {
+            (values[mid - 1] + values[mid]) / 2.0
+        } else {
+            values[mid]
+        }
+    }
+
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    pub enum BoxSmoothing {
+        // EMA per edge: responsive, but a single outlier frame still pulls the box
+        Ema { alpha: f32 },
+        // Per-edge median of the last `frames` boxes: rejects single-frame outliers and is
+        // very steady, at the cost of about frames / 2 frames of latency
+        Median { frames: usize },
+    }
+
+    // Smoothing over the four bbox edges of each tracked hand (EMA or median of N).
+    // Purely for display: the raw boxes in HandDetails are left untouched for measurement.
+    pub struct BoxSmoother {
+        mode: BoxSmoothing,
+        jump_threshold: f32,
+        boxes: HashMap<u32, Box>,
+        // Recent raw boxes per hand (median mode)
+        history: HashMap<u32, VecDeque<Box>>,
+    }
+
+    impl BoxSmoother {
+        pub fn new(alpha: f32) -> Self {
+            Self::with_mode(BoxSmoothing::Ema {
+                alpha: alpha.clamp(0.0, 1.0),
+            })
+        }
+
+        // Report the per-edge median of the last `frames` boxes (at least 1)
+        pub fn median(frames: usize) -> Self {
+            Self::with_mode(BoxSmoothing::Median {
+                frames: frames.max(1),
+            })
+        }
+
+        fn with_mode(mode: BoxSmoothing) -> Self {
+            Self {
+                mode,
+                jump_threshold: DEFAULT_JUMP_THRESHOLD,
+                boxes: HashMap::new(),
+                history: HashMap::new(),
+            }
+        }
+
+        pub fn mode(&self) -> BoxSmoothing {
+            self.mode
+        }
+
+        // When a box center jumps farther than this (normalized) between frames, e.g. the
+        // user swapped hands, restart from the new box instead of sliding across the gap
+        pub fn with_jump_threshold(mut self, jump_threshold: f32) -> Self {
+            self.jump_threshold = jump_threshold;
+            self
+        }
+
+        // Feed a new raw box for a hand, returning its smoothed box
+        pub fn smooth(&mut self, id: u32, bbox: &Box) -> Box {
+            let previous = self.boxes.get(&id).filter(|previous| {
+                distance(previous.center(), bbox.center()) <= self.jump_threshold
+            });
+
+            let smoothed = match self.mode {
+                BoxSmoothing::Ema { alpha } => match previous {
+                    Some(previous) => Box {
+                        xmin: ema(previous.xmin, bbox.xmin, alpha),
+                        ymin: ema(previous.ymin, bbox.ymin, alpha),
+                        xmax: ema(previous.xmax, bbox.xmax, alpha),
+                        ymax: ema(previous.ymax, bbox.ymax, alpha),
+                    },
+                    None => *bbox,
+                },
+                BoxSmoothing::Median { frames } => {
+                    let jumped = previous.is_none();
+                    let history = self.history.entry(id).or_default();
+                    if jumped {
+                        history.clear();
+                    }
+                    history.push_back(*bbox);
+                    while history.len() > frames {
+                        history.pop_front();
+                    }
+
+                    let edge = |f: fn(&Box) -> f32| {
+                        median(&mut history.iter().map(f).collect::<Vec<f32>>())
+                    };
+                    Box {
+                        xmin: edge(|b| b.xmin),
+                        ymin: edge(|b| b.ymin),
+                        xmax: edge(|b| b.xmax),
+                        ymax: edge(|b| b.ymax),
+                    }
+                }
+            };
+            self.boxes.insert(id, smoothed);
+            smoothed
+        }
+
+        // Last smoothed box for a hand, without feeding a new value
+        pub fn get(&self, id: u32) -> Option<Box> {
+            self.boxes.get(&id).copied()
+        }
+
+        // Drop state for hands that are no longer tracked
+        pub fn retain<F: Fn(u32) -> bool>(&mut self, keep: F) {
+            self.boxes.retain(|id, _| keep(*id));
+            self.history.retain(|id, _| keep(*id));
+        }
+
+        // Drop the state of every hand
+        pub fn reset(&mut self) {
+            self.boxes.clear();
+            self.history.clear();
+        }
+    }
+
+    // One-Euro filter parameters (coordinates are normalized, time in seconds).
+    // `min_cutoff` (Hz) sets the smoothing at rest: lower is steadier but lags more.
+    // `beta` raises the cutoff with speed, so fast motion is followed with less lag.
+    // `d_cutoff` (Hz) smooths the speed estimate itself.
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    pub struct OneEuroParams {
+        pub min_cutoff: f32,
+        pub beta: f32,
+        pub d_cutoff: f32,
+    }
+
+    impl OneEuroParams {
+        pub const fn new(min_cutoff: f32, beta: f32) -> Self {
+            Self {
+                min_cutoff,
+                beta,
+                d_cutoff: 1.0,
+            }
+        }
+    }
+
+    impl Default for OneEuroParams {
+        fn default() -> Self {
+            Self::new(1.0, 5.0)
+        }
+    }
+
+    // Smoothing factor of a first order low-pass filter at `cutoff` Hz over `dt` seconds
+    fn low_pass_alpha(cutoff: f32, dt: f32) -> f32 {
+        let tau = 1.0 / (2.0 * PI * cutoff);
+        1.0 / (1.0 + tau / dt)
+    }
+
+    // One-Euro filter state for a single value
+    #[derive(Debug, Clone, Copy)]
+    struct OneEuro {
+        value: f32,
+        speed: f32,
+    }
+
+    impl OneEuro {
+        fn new(value: f32) -> Self {
+            Self { value, speed: 0.0 }
+        }
+
+        fn filter(&mut self, value: f32, dt: f32, params: &OneEuroParams) -> f32 {
+            let speed = (value - self.value) / dt;
+            self.speed = ema(self.speed, speed, low_pass_alpha(params.d_cutoff, dt));
+            let cutoff = params.min_cutoff + params.beta * self.speed.abs();
+            self.value = ema(self.value, value, low_pass_alpha(cutoff, dt));
+            self.value
+        }
+    }
+
+    // One-Euro parameters for each of the 21 hand landmarks, so fast fingertips can
+    // respond quickly while the palm stays steady.
+    //
+    // Recommended settings:
+    // - Pointing (cursor control): `pointing()`. Fingertips min_cutoff 0.5, beta 5.0 hold the
+    //   cursor still at rest but catch up on large moves; the palm uses 0.3, 1.0.
+    // - Gesturing (flicks, swipes, pinches): `gesturing()`. Fingertips min_cutoff 3.0,
+    //   beta 20.0 add almost no lag to fast motion; the palm uses 0.5, 1.0 to stay stable.
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    pub struct LandmarkSmoothing {
+        pub params: [OneEuroParams; NUM_LANDMARKS],
+    }
+
+    impl LandmarkSmoothing {
+        // The same parameters for every landmark
+        pub fn uniform(params: OneEuroParams) -> Self {
+            Self {
+                params: [params; NUM_LANDMARKS],
+            }
+        }
+
+        pub fn pointing() -> Self {
+            Self::uniform(OneEuroParams::new(0.3, 1.0))
+                .with_fingertips(OneEuroParams::new(0.5, 5.0))
+        }
+
+        pub fn gesturing() -> Self {
+            Self::uniform(OneEuroParams::new(0.5, 1.0))
+                .with_fingertips(OneEuroParams::new(3.0, 20.0))
+        }
+
+        // Override the parameters of the five fingertips
+        pub fn with_fingertips(mut self, params: OneEuroParams) -> Self {
+            for tip in FINGERTIPS {
+                self.params[tip] = params;
+            }
+            self
+        }
+
+        // Override the parameters of one landmark (see the hand_landmarker indices)
+        pub fn with_landmark(mut self, index: usize, params: OneEuroParams) -> Self {
+            if let Some(slot) = self.params.get_mut(index) {
+                *slot = params;
+            }
+            self
+        }
+    }
+
+    impl Default for LandmarkSmoothing {
+        fn default() -> Self {
+            Self::uniform(OneEuroParams::default())
+        }
+    }
+
+    struct LandmarkFilters {
+        last_update: Instant,
+        // (x, y, z) filters per landmark
+        filters: Vec<[OneEuro; 3]>,
+    }
+
+    // One-Euro filter over the landmarks of each tracked hand
+    pub struct LandmarkSmoother {
+        config: LandmarkSmoothing,
+        jump_threshold: f32,
+        hands: HashMap<u32, LandmarkFilters>,
+    }
+
+    impl LandmarkSmoother {
+        pub fn new(config: LandmarkSmoothing) -> Self {
+            Self {
+                config,
+                jump_threshold: DEFAULT_JUMP_THRESHOLD,
+                hands: HashMap::new(),
+            }
+        }
+
+        // When the wrist (the first landmark, for a subset) jumps farther than this
+        // (normalized) between frames, the hand's filters and speed estimates restart
+        // from the new landmarks
+        pub fn with_jump_threshold(mut self, jump_threshold: f32) -> Self {
+            self.jump_threshold = jump_threshold;
+            self
+        }
+
+        pub fn config(&self) -> &LandmarkSmoothing {
+            &self.config
+        }
+
+        // Smooth a hand's landmarks in place. `indices` says which landmarks they are when
+        // only a subset was extracted (see HandDetails::landmark_indices). The first frame
+        // of a hand (and the frame after a jump) passes through.
+        pub fn smooth(
+            &mut self,
+            id: u32,
+            landmarks: &mut [Landmark],
+            indices: Option<&[usize]>,
+            now: Instant,
+        ) {
+            let filters = self.hands.entry(id).or_insert_with(|| LandmarkFilters {
+                last_update: now,
+                filters: Vec::new(),
+            });
+
+            let dt = now.duration_since(filters.last_update).as_secs_f32();
+            filters.last_update = now;
+            let jumped = match (filters.filters.first(), landmarks.first()) {
+                (Some([fx, fy, _]), Some(wrist)) => {
+                    distance((fx.value, fy.value), (wrist.x, wrist.y)) > self.jump_threshold
+                }
+                _ => false,
+            };
+            if dt <= 0.0 || jumped || filters.filters.len() != landmarks.len() {
+                filters.filters = landmarks
+                    .iter()
+                    .map(|l| [OneEuro::new(l.x), OneEuro::new(l.y), OneEuro::new(l.z)])
+                    .collect();
+                return;
+            }
+
+            let default = OneEuroParams::default();
+            for (i, (landmark, [fx, fy, fz])) in landmarks
+                .iter_mut()
+                .zip(filters.filters.iter_mut())
+                .enumerate()
+            {
+                let index = indices.map_or(Some(i), |indices| indices.get(i).copied());
+                let params = index
+                    .and_then(|index| self.config.params.get(index))
+                    .unwrap_or(&default);
+                landmark.x = fx.filter(landmark.x, dt, params);
+                landmark.y = fy.filter(landmark.y, dt, params);
+                landmark.z = fz.filter(landmark.z, dt, params);
+            }
+        }
+
+        // Drop state for hands that are no longer tracked
+        pub fn retain<F: Fn(u32) -> bool>(&mut self, keep: F) {
+            self.hands.retain(|id, _| keep(*id));
+        }
+
+        // Drop the filters and speed estimates of every hand
+        pub fn reset(&mut self) {
+            self.hands.clear();
+        }
+    }
+}
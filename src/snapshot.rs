@@ -0,0 +1,196 @@
+pub mod frame_snapshot {
+    use crate::viz::overlay::{PixelOrder, unpack_pixel};
+    use anyhow::{Result, anyhow};
+    use image::codecs::bmp::BmpEncoder;
+    use image::codecs::jpeg::JpegEncoder;
+    use image::codecs::png::PngEncoder;
+    use image::{ImageBuffer, ImageEncoder, Rgb};
+    use std::collections::VecDeque;
+    use std::fs::File;
+    use std::io::BufWriter;
+    use std::path::{Path, PathBuf};
+    use std::time::{SystemTime, UNIX_EPOCH};
+
+    type Frame = ImageBuffer<Rgb<u8>, Vec<u8>>;
+
+    // Encoding of saved frames. PNG is lossless but the slowest to encode and mid-sized;
+    // JPEG is lossy, the smallest and fast to encode (the one to use when saving at frame
+    // rate); BMP is uncompressed, the fastest to write but by far the largest.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+    pub enum ImageFormat {
+        #[default]
+        Png,
+        // Quality from 1 (smallest) to 100 (best)
+        Jpeg {
+            quality: u8,
+        },
+        Bmp,
+    }
+
+    impl ImageFormat {
+        pub fn jpeg(quality: u8) -> Result<Self> {
+            if !(1..=100).contains(&quality) {
+                return Err(anyhow!(
+                    "JPEG quality must be between 1 and 100, got {}",
+                    quality
+                ));
+            }
+            Ok(ImageFormat::Jpeg { quality })
+        }
+
+        // "png", "bmp", "jpeg" (quality 90) or "jpeg:QUALITY"
+        pub fn parse(name: &str) -> Result<Self> {
+            match name.split_once(':') {
+                Some(("jpeg" | "jpg", quality)) => Self::jpeg(
+                    quality
+                        .parse()
+                        .map_err(|_| anyhow!("Invalid JPEG quality '{}'", quality))?,
+                ),
+                None if name == "jpeg" || name == "jpg" => Self::jpeg(90),
+                None if name == "png" => Ok(ImageFormat::Png),
+                None if name == "bmp" => Ok(ImageFormat::Bmp),
+                _ => Err(anyhow!(
+                    "Unknown image format '{}' (expected png, jpeg, jpeg:QUALITY or bmp)",
+                    name
+                )),
+            }
+        }
+
+        pub fn extension(&self) -> &'static str {
+            match self {
+                ImageFormat::Png => "png",
+                ImageFormat::Jpeg { .. } => "jpg",
+                ImageFormat::Bmp => "bmp",
+            }
+        }
+    }
+
+    fn unix_millis(time: SystemTime) -> u128 {
+        time.duration_since(UNIX_EPOCH)
+            .map(|d| d.as_millis())
+            .unwrap_or(0)
+    }
+
+    // snapshot-<unix time in ms>.<extension> inside dir
+    fn timestamped_path(dir: &Path, format: ImageFormat) -> PathBuf {
+        let millis = unix_millis(SystemTime::now());
+        dir.join(format!("snapshot-{}.{}", millis, format.extension()))
+    }
+
+    // Write a frame to `path` in this format
+    pub fn encode_frame(
+        frame: &ImageBuffer<Rgb<u8>, Vec<u8>>,
+        path: &Path,
+        format: ImageFormat,
+    ) -> Result<()> {
+        let mut writer = BufWriter::new(File::create(path)?);
+        let (width, height) = frame.dimensions();
+        let color = image::ExtendedColorType::Rgb8;
+        match format {
+            ImageFormat::Png => {
+                PngEncoder::new(&mut writer).write_image(frame.as_raw(), width, height, color)?
+            }
+            ImageFormat::Jpeg { quality } => JpegEncoder::new_with_quality(&mut writer, quality)
+                .write_image(frame.as_raw(), width, height, color)?,
+            ImageFormat::Bmp => {
+                BmpEncoder::new(&mut writer).write_image(frame.as_raw(), width, height, color)?
+            }
+        }
+        Ok(())
+    }
+
+    // Save a frame (e.g. the original-resolution camera frame) as a timestamped PNG
+    pub fn save_frame(frame: &ImageBuffer<Rgb<u8>, Vec<u8>>, dir: &Path) -> Result<PathBuf> {
+        save_frame_as(frame, dir, ImageFormat::Png)
+    }
+
+    // Like save_frame, in another format
+    pub fn save_frame_as(
+        frame: &ImageBuffer<Rgb<u8>, Vec<u8>>,
+        dir: &Path,
+        format: ImageFormat,
+    ) -> Result<PathBuf> {
+        std::fs::create_dir_all(dir)?;
+        let path = timestamped_path(dir, format);
+        encode_frame(frame, &path, format)?;
+        Ok(path)
+    }
+
+    // Convert a window buffer (frame plus drawn overlays) back into an image
+    pub fn buffer_to_image(
+        buffer: &[u32],
+        width: usize,
+        height: usize,
+        order: PixelOrder,
+    ) -> ImageBuffer<Rgb<u8>, Vec<u8>> {
+        ImageBuffer::from_fn(width as u32, height as u32, |x, y| {
+            let (r, g, b) = unpack_pixel(buffer[y as usize * width + x as usize], order);
+            Rgb([r, g, b])
+        })
+    }
+
+    // The last `capacity` frames, kept in memory to save what led up to an intermittent
+    // failure after the fact. The oldest frames are also dropped once they take more than
+    // `max_bytes` (a 1080p frame is about 6 MB), though the newest one is always kept.
+    pub struct FrameRing {
+        frames: VecDeque<(SystemTime, Frame)>,
+        capacity: usize,
+        max_bytes: usize,
+        bytes: usize,
+    }
+
+    impl FrameRing {
+        pub fn new(capacity: usize, max_bytes: usize) -> Self {
+            Self {
+                frames: VecDeque::new(),
+                capacity: capacity.max(1),
+                max_bytes,
+                bytes: 0,
+            }
+        }
+
+        // Keep a copy of this frame, evicting the oldest ones over the limits
+        pub fn push(&mut self, frame: &Frame) {
+            self.bytes += frame.as_raw().len();
+            self.frames.push_back((SystemTime::now(), frame.clone()));
+            while self.frames.len() > self.capacity
+                || (self.bytes > self.max_bytes && self.frames.len() > 1)
+            {
+                if let Some((_, oldest)) = self.frames.pop_front() {
+                    self.bytes -= oldest.as_raw().len();
+                }
+            }
+        }
+
+        pub fn len(&self) -> usize {
+            self.frames.len()
+        }
+
+        pub fn is_empty(&self) -> bool {
+            self.frames.is_empty()
+        }
+
+        // Memory taken by the buffered frames
+        pub fn bytes(&self) -> usize {
+            self.bytes
+        }
+
+        // Write the buffered frames, oldest first, into a new recent-<unix time in ms>
+        // directory inside `dir` as frame-<index>-<capture time in ms>.<extension>, and
+        // return that directory. The buffer keeps its frames.
+        pub fn dump_recent(&self, dir: &Path, format: ImageFormat) -> Result<PathBuf> {
+            let dump = dir.join(format!("recent-{}", unix_millis(SystemTime::now())));
+            std::fs::create_dir_all(&dump)?;
+            for (i, (captured, frame)) in self.frames.iter().enumerate() {
+                let name = format!(
+                    "frame-{:04}-{}.{}",
+                    i,
+                    unix_millis(*captured),
+                    format.extension()
+                );
+                encode_frame(frame, &dump.join(name), format)?;
+            }
+            Ok(dump)
+        }
+    }
+}
@@ -0,0 +1,445 @@
+pub mod hand_tracker {
+    use crate::detector::hand_detector::{
+        Box, HandDetails, Handedness, Landmark, intersection_over_union,
+    };
+    use std::collections::{HashMap, VecDeque};
+    use std::time::{Duration, Instant};
+
+    #[derive(Debug, Clone)]
+    pub struct TrackedHand {
+        pub id: u32,
+        pub details: HandDetails,
+        // Number of frames this hand has been tracked for
+        pub age: u32,
+        // Number of consecutive frames this hand has not been detected
+        pub missed: u32,
+        // How still the hand has been over the last frames, from 0.0 (moving, or just
+        // appeared) to 1.0 (perfectly still). See HandTracker::with_stability_window.
+        pub stability: f32,
+    }
+
+    // Frames of box centers the stability score is computed over
+    pub const DEFAULT_STABILITY_WINDOW: usize = 10;
+    // Spread of the box centers (relative to the box diagonal) at which stability is 0.5
+    const STABILITY_HALF_SPREAD: f32 = 0.05;
+
+    // Stability from recent box centers: the RMS distance of the centers from their mean,
+    // divided by the box diagonal (so it doesn't depend on how far the hand is), mapped
+    // to 1 / (1 + spread / STABILITY_HALF_SPREAD). Fewer than 2 centers give 0.0.
+    fn stability(centers: &VecDeque<(f32, f32)>, bbox: &Box) -> f32 {
+        if centers.len() < 2 {
+            return 0.0;
+        }
+        let n = centers.len() as f32;
+        let (mx, my) = centers
+            .iter()
+            .fold((0.0, 0.0), |(sx, sy), (x, y)| (sx + x / n, sy + y / n));
+        let variance = centers
+            .iter()
+            .map(|(x, y)| (x - mx).powi(2) + (y - my).powi(2))
+            .sum::<f32>()
+            / n;
+        let diagonal = (bbox.width().powi(2) + bbox.height().powi(2))
+            .sqrt()
+            .max(f32::EPSILON);
+        1.0 / (1.0 + variance.sqrt() / diagonal / STABILITY_HALF_SPREAD)
+    }
+
+    // Presence of a tracked hand with hysteresis: a hand stays present for up to
+    // keep_alive missed frames, reporting its last known detection meanwhile
+    #[derive(Debug, Clone)]
+    pub struct HandState {
+        pub id: u32,
+        pub present: bool,
+        pub details: HandDetails,
+        pub missed: u32,
+    }
+
+    // Assigns stable ids to detections across frames by greedy matching on
+    // bbox overlap, falling back to centroid distance for small/fast hands
+    pub struct HandTracker {
+        tracks: Vec<TrackedHand>,
+        next_id: u32,
+        max_missed: u32,
+        keep_alive: u32,
+        min_iou: f32,
+        max_center_distance: f32,
+        stability_window: usize,
+        // Recent box centers of each track, for the stability score
+        centers: HashMap<u32, VecDeque<(f32, f32)>>,
+    }
+
+    impl Default for HandTracker {
+        fn default() -> Self {
+            Self::new()
+        }
+    }
+
+    impl HandTracker {
+        pub fn new() -> Self {
+            Self {
+                tracks: Vec::new(),
+                next_id: 0,
+                max_missed: 5,
+                keep_alive: 3,
+                min_iou: 0.1,
+                max_center_distance: 0.15,
+                stability_window: DEFAULT_STABILITY_WINDOW,
+                centers: HashMap::new(),
+            }
+        }
+
+        pub fn with_max_missed(mut self, max_missed: u32) -> Self {
+            self.max_missed = max_missed;
+            self.keep_alive = self.keep_alive.min(max_missed);
+            self
+        }
+
+        // Number of missed frames a hand stays "present" for (capped at max_missed)
+        pub fn with_keep_alive(mut self, keep_alive: u32) -> Self {
+            self.keep_alive = keep_alive.min(self.max_missed);
+            self
+        }
+
+        // Compute stability over this many frames (at least 2). Longer windows need the hand
+        // to hold still for longer before it counts as stable.
+        pub fn with_stability_window(mut self, frames: usize) -> Self {
+            self.stability_window = frames.max(2);
+            self
+        }
+
+        fn match_score(&self, track: &TrackedHand, hand: &HandDetails) -> Option<f32> {
+            let iou = intersection_over_union(&track.details.bbox, &hand.bbox);
+            let (tx, ty) = track.details.bbox.center();
+            let (hx, hy) = hand.bbox.center();
+            let distance = ((tx - hx).powi(2) + (ty - hy).powi(2)).sqrt();
+
+            // Written so that a NaN box (both comparisons false) never matches
+            if iou >= self.min_iou || distance <= self.max_center_distance {
+                // Higher is better: overlap first, then closeness
+                Some(iou + (1.0 - distance / self.max_center_distance).max(0.0) * 0.5)
+            } else {
+                None
+            }
+        }
+
+        // Update the tracks with this frame's detections, returning the hands seen this frame
+        pub fn update(&mut self, hands: &[HandDetails]) -> Vec<TrackedHand> {
+            // Score every (track, detection) pair and match greedily, best first
+            let mut pairs = Vec::new();
+            for (t, track) in self.tracks.iter().enumerate() {
+                for (h, hand) in hands.iter().enumerate() {
+                    if let Some(score) = self.match_score(track, hand) {
+                        pairs.push((score, t, h));
+                    }
+                }
+            }
+            pairs.sort_by(|a, b| b.0.total_cmp(&a.0));
+
+            let mut track_matched = vec![false; self.tracks.len()];
+            let mut hand_matched = vec![false; hands.len()];
+            for (_, t, h) in pairs {
+                if track_matched[t] || hand_matched[h] {
+                    continue;
+                }
+                track_matched[t] = true;
+                hand_matched[h] = true;
+
+                let track = &mut self.tracks[t];
+                track.details = hands[h].clone();
+                track.age += 1;
+                track.missed = 0;
+            }
+
+            // Age out tracks that were not matched
+            for (t, track) in self.tracks.iter_mut().enumerate() {
+                if !track_matched[t] {
+                    track.missed += 1;
+                }
+            }
+            let max_missed = self.max_missed;
+            self.tracks.retain(|track| track.missed <= max_missed);
+
+            // Start new tracks for unmatched detections
+            for (h, hand) in hands.iter().enumerate() {
+                if !hand_matched[h] {
+                    self.tracks.push(TrackedHand {
+                        id: self.next_id,
+                        details: hand.clone(),
+                        age: 1,
+                        missed: 0,
+                        stability: 0.0,
+                    });
+                    self.next_id += 1;
+                }
+            }
+
+            // Stability from the box centers of the frames a hand was seen in
+            self.centers
+                .retain(|id, _| self.tracks.iter().any(|track| track.id == *id));
+            for track in self.tracks.iter_mut().filter(|track| track.missed == 0) {
+                let centers = self.centers.entry(track.id).or_default();
+                if centers.len() == self.stability_window {
+                    centers.pop_front();
+                }
+                centers.push_back(track.details.bbox.center());
+                track.stability = stability(centers, &track.details.bbox);
+            }
+
+            // Return
+            self.tracks
+                .iter()
+                .filter(|track| track.missed == 0)
+                .cloned()
+                .collect()
+        }
+
+        pub fn tracks(&self) -> &[TrackedHand] {
+            &self.tracks
+        }
+
+        // Forget every track (e.g. when a new user takes over), so the next update starts
+        // fresh. Ids keep counting up, so new hands never reuse an id from before the reset.
+        pub fn reset(&mut self) {
+            self.tracks.clear();
+            self.centers.clear();
+        }
+
+        // Presence of every live track, as of the last update
+        pub fn hand_states(&self) -> Vec<HandState> {
+            self.tracks
+                .iter()
+                .map(|track| HandState {
+                    id: track.id,
+                    present: track.missed <= self.keep_alive,
+                    details: track.details.clone(),
+                    missed: track.missed,
+                })
+                .collect()
+        }
+    }
+
+    fn lerp(a: f32, b: f32, t: f32) -> f32 {
+        a + (b - a) * t
+    }
+
+    fn lerp_landmark(a: &Landmark, b: &Landmark, t: f32) -> Landmark {
+        Landmark {
+            x: lerp(a.x, b.x, t),
+            y: lerp(a.y, b.y, t),
+            z: lerp(a.z, b.z, t),
+            visibility: lerp(a.visibility, b.visibility, t),
+            estimated: a.estimated || b.estimated,
+        }
+    }
+
+    fn lerp_landmarks(a: &[Landmark], b: &[Landmark], t: f32) -> Vec<Landmark> {
+        a.iter()
+            .zip(b)
+            .map(|(a, b)| lerp_landmark(a, b, t))
+            .collect()
+    }
+
+    // Linear blend between two detections of the same hand (t = 0.0 is a, 1.0 is b)
+    pub fn lerp_details(a: &HandDetails, b: &HandDetails, t: f32) -> HandDetails {
+        let landmarks = match (&a.landmarks, &b.landmarks) {
+            (Some(la), Some(lb)) if la.len() == lb.len() => Some(lerp_landmarks(la, lb, t)),
+            _ => b.landmarks.clone(),
+        };
+
+        HandDetails {
+            score: b.score,
+            bbox: Box {
+                xmin: lerp(a.bbox.xmin, b.bbox.xmin, t),
+                ymin: lerp(a.bbox.ymin, b.bbox.ymin, t),
+                xmax: lerp(a.bbox.xmax, b.bbox.xmax, t),
+                ymax: lerp(a.bbox.ymax, b.bbox.ymax, t),
+            },
+            wrist: lerp_landmark(&a.wrist, &b.wrist, t),
+            keypoints: lerp_landmarks(&a.keypoints, &b.keypoints, t),
+            landmarks,
+            landmark_indices: b.landmark_indices.clone(),
+            handedness: b.handedness,
+            rotated_bbox: b.rotated_bbox,
+            camera: b.camera,
+        }
+    }
+
+    // Interpolates tracked hands between the last two inference results, so overlays
+    // can be rendered at a higher rate than inference runs.
+    // Render one inference interval behind the latest result (e.g. `now - interval`)
+    // to always interpolate between known positions; later times hold the latest result.
+    #[derive(Default)]
+    pub struct DetectionInterpolator {
+        previous: HashMap<u32, (Instant, HandDetails)>,
+        latest: HashMap<u32, (Instant, HandDetails)>,
+    }
+
+    impl DetectionInterpolator {
+        pub fn new() -> Self {
+            Self::default()
+        }
+
+        pub fn reset(&mut self) {
+            self.previous.clear();
+            self.latest.clear();
+        }
+
+        // Record an inference result taken at `at`
+        pub fn push(&mut self, tracked: &[TrackedHand], at: Instant) {
+            let latest = tracked
+                .iter()
+                .map(|hand| (hand.id, (at, hand.details.clone())))
+                .collect();
+            self.previous = std::mem::replace(&mut self.latest, latest);
+        }
+
+        // Interpolated hands at render time `at`, keyed by tracking id
+        pub fn interpolate(&self, at: Instant) -> Vec<(u32, HandDetails)> {
+            self.latest
+                .iter()
+                .map(|(&id, (t1, latest))| match self.previous.get(&id) {
+                    Some((t0, previous)) if t1 > t0 => {
+                        let span = t1.duration_since(*t0).as_secs_f32();
+                        let elapsed = at.saturating_duration_since(*t0).as_secs_f32();
+                        let t = (elapsed / span).clamp(0.0, 1.0);
+                        (id, lerp_details(previous, latest, t))
+                    }
+                    // Newly seen hands have nothing to interpolate from
+                    _ => (id, latest.clone()),
+                })
+                .collect()
+        }
+    }
+
+    // Which visible hand PrimaryHandLock picks when it has none
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+    pub enum PrimarySelection {
+        // The one tracked the longest
+        #[default]
+        FirstSeen,
+        HighestScore,
+        // The best scoring hand of this handedness (needs the hand landmarker)
+        Handedness(Handedness),
+    }
+
+    impl PrimarySelection {
+        pub fn from_name(name: &str) -> Option<Self> {
+            match name {
+                "first" => Some(PrimarySelection::FirstSeen),
+                "score" => Some(PrimarySelection::HighestScore),
+                "left" => Some(PrimarySelection::Handedness(Handedness::Left)),
+                "right" => Some(PrimarySelection::Handedness(Handedness::Right)),
+                _ => None,
+            }
+        }
+    }
+
+    // Locks control to a single hand, so a second hand can't steal the cursor: once a
+    // primary hand is picked, every other hand is ignored until the primary has been out of
+    // sight for `timeout`, even while it's missing. All hands are still tracked and drawn.
+    pub struct PrimaryHandLock {
+        selection: PrimarySelection,
+        timeout: Duration,
+        // The primary hand's id and when it was last seen
+        primary: Option<(u32, Instant)>,
+    }
+
+    impl PrimaryHandLock {
+        pub fn new(selection: PrimarySelection, timeout: Duration) -> Self {
+            Self {
+                selection,
+                timeout,
+                primary: None,
+            }
+        }
+
+        pub fn primary(&self) -> Option<u32> {
+            self.primary.map(|(id, _)| id)
+        }
+
+        // Whether input from the hand with this id should be used
+        pub fn is_primary(&self, id: u32) -> bool {
+            self.primary() == Some(id)
+        }
+
+        // Call once per frame with the tracked hands. Returns the primary hand's id.
+        pub fn update(&mut self, tracked: &[TrackedHand], now: Instant) -> Option<u32> {
+            let visible = || tracked.iter().filter(|hand| hand.missed == 0);
+            if let Some((id, last_seen)) = self.primary.as_mut() {
+                if visible().any(|hand| hand.id == *id) {
+                    *last_seen = now;
+                } else if now.duration_since(*last_seen) >= self.timeout {
+                    self.primary = None;
+                }
+            }
+
+            if self.primary.is_none() {
+                let score = |hand: &&TrackedHand| hand.details.score;
+                let candidate = match self.selection {
+                    PrimarySelection::FirstSeen => visible().max_by_key(|hand| hand.age),
+                    PrimarySelection::HighestScore => {
+                        visible().max_by(|a, b| score(a).total_cmp(&score(b)))
+                    }
+                    PrimarySelection::Handedness(handedness) => visible()
+                        .filter(|hand| {
+                            hand.details
+                                .handedness
+                                .is_some_and(|(side, _)| side == handedness)
+                        })
+                        .max_by(|a, b| score(a).total_cmp(&score(b))),
+                };
+                self.primary = candidate.map(|hand| (hand.id, now));
+            }
+            self.primary()
+        }
+
+        pub fn reset(&mut self) {
+            self.primary = None;
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        fn hand(xmin: f32, ymin: f32, xmax: f32, ymax: f32) -> HandDetails {
+            let wrist = Landmark {
+                x: (xmin + xmax) / 2.0,
+                y: ymax,
+                z: 0.0,
+                visibility: 1.0,
+                estimated: false,
+            };
+            HandDetails {
+                score: 0.9,
+                bbox: Box {
+                    xmin,
+                    ymin,
+                    xmax,
+                    ymax,
+                },
+                wrist,
+                keypoints: vec![wrist; 7],
+                landmarks: None,
+                landmark_indices: None,
+                handedness: None,
+                rotated_bbox: None,
+                camera: None,
+            }
+        }
+
+        #[test]
+        fn nan_box_does_not_panic_matching() {
+            let mut tracker = HandTracker::new();
+            let first = tracker.update(&[hand(0.2, 0.2, 0.4, 0.4)]);
+            assert_eq!(first.len(), 1);
+
+            // A degenerate detection scores NaN against the track; it must not break the sort
+            let nan = hand(f32::NAN, f32::NAN, f32::NAN, f32::NAN);
+            let second = tracker.update(&[nan, hand(0.21, 0.2, 0.41, 0.4)]);
+            let kept = second.iter().find(|tracked| tracked.id == first[0].id);
+            assert!(kept.is_some_and(|tracked| tracked.details.bbox.xmin.is_finite()));
+        }
+    }
+}
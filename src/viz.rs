@@ -0,0 +1,482 @@
+pub mod overlay {
+    use crate::detector::hand_detector::{Box, HandDetails, Landmark, RotatedBox};
+    use crate::landmarker::hand_landmarker::*;
+
+    // Pairs of landmark indices to join with a line
+    pub type Connections = [(usize, usize)];
+
+    // MediaPipe's 21-point hand skeleton
+    pub const MEDIAPIPE_HAND_CONNECTIONS: &Connections = &[
+        // Thumb
+        (WRIST, THUMB_CMC),
+        (THUMB_CMC, THUMB_MCP),
+        (THUMB_MCP, THUMB_IP),
+        (THUMB_IP, THUMB_TIP),
+        // Index finger
+        (WRIST, INDEX_MCP),
+        (INDEX_MCP, INDEX_PIP),
+        (INDEX_PIP, INDEX_DIP),
+        (INDEX_DIP, INDEX_TIP),
+        // Middle finger
+        (MIDDLE_MCP, MIDDLE_PIP),
+        (MIDDLE_PIP, MIDDLE_DIP),
+        (MIDDLE_DIP, MIDDLE_TIP),
+        // Ring finger
+        (RING_MCP, RING_PIP),
+        (RING_PIP, RING_DIP),
+        (RING_DIP, RING_TIP),
+        // Pinky
+        (WRIST, PINKY_MCP),
+        (PINKY_MCP, PINKY_PIP),
+        (PINKY_PIP, PINKY_DIP),
+        (PINKY_DIP, PINKY_TIP),
+        // Palm
+        (INDEX_MCP, MIDDLE_MCP),
+        (MIDDLE_MCP, RING_MCP),
+        (RING_MCP, PINKY_MCP),
+    ];
+
+    // Distinct colors for telling tracked hands apart (0RGB)
+    pub const PALETTE: [u32; 8] = [
+        0x00FF00, // green
+        0xFF00FF, // magenta
+        0x00FFFF, // cyan
+        0xFFA500, // orange
+        0xFFFF00, // yellow
+        0x8080FF, // light blue
+        0xFF4040, // red
+        0xFFFFFF, // white
+    ];
+
+    // Outline of the active zone (grey, so it can't be mistaken for a hand's palette color)
+    pub const ZONE_COLOR: u32 = 0x808080;
+
+    // Stable color for a tracking id (ids are assigned in order, so neighbours differ)
+    pub fn palette_color(id: u32) -> u32 {
+        PALETTE[id as usize % PALETTE.len()]
+    }
+
+    // Channel order of the u32 window pixels. minifb documents 0RGB (`Argb`: red in bits
+    // 16-23, green in 8-15, blue in 0-7, top byte ignored), but if red and blue come out
+    // swapped (blue-tinted video) on a platform/backend, `Abgr` (0BGR) packs them the other way.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+    pub enum PixelOrder {
+        #[default]
+        Argb,
+        Abgr,
+    }
+
+    impl PixelOrder {
+        pub fn from_name(name: &str) -> Option<Self> {
+            match name.to_lowercase().as_str() {
+                "argb" | "0rgb" => Some(PixelOrder::Argb),
+                "abgr" | "0bgr" => Some(PixelOrder::Abgr),
+                _ => None,
+            }
+        }
+    }
+
+    // Pack 8-bit channels into a window pixel
+    pub fn pack_pixel(r: u8, g: u8, b: u8, order: PixelOrder) -> u32 {
+        let (r, g, b) = (r as u32, g as u32, b as u32);
+        match order {
+            PixelOrder::Argb => (r << 16) | (g << 8) | b,
+            PixelOrder::Abgr => (b << 16) | (g << 8) | r,
+        }
+    }
+
+    // Inverse of pack_pixel, returning (r, g, b)
+    pub fn unpack_pixel(pixel: u32, order: PixelOrder) -> (u8, u8, u8) {
+        let (high, mid, low) = ((pixel >> 16) as u8, (pixel >> 8) as u8, pixel as u8);
+        match order {
+            PixelOrder::Argb => (high, mid, low),
+            PixelOrder::Abgr => (low, mid, high),
+        }
+    }
+
+    // How far outside the frame (in frame sizes) a coordinate may be and still be drawn
+    // (clipped); anything further is a bad detection rather than a hand leaving the frame
+    const MAX_OUT_OF_FRAME: f32 = 1.0;
+
+    // Pixel position of a normalized coordinate along an axis of `size` pixels. None for
+    // NaN, infinite or far out of range values, so callers skip them instead of drawing
+    // them at the clamped edge.
+    pub fn normalized_to_pixel(v: f32, size: usize) -> Option<i32> {
+        if !v.is_finite() || !(-MAX_OUT_OF_FRAME..=1.0 + MAX_OUT_OF_FRAME).contains(&v) {
+            return None;
+        }
+        Some((v * size as f32) as i32)
+    }
+
+    // Tiny 3x5 bitmap font covering printable ASCII: digits, letters (lowercase is drawn
+    // as uppercase) and common punctuation. Each row is 3 bits, MSB left.
+    // Characters without a glyph are drawn as a space.
+    pub const GLYPH_WIDTH: usize = 3;
+    pub const GLYPH_HEIGHT: usize = 5;
+
+    fn glyph(c: char) -> [u8; GLYPH_HEIGHT] {
+        match c.to_ascii_uppercase() {
+            '0' => [0b111, 0b101, 0b101, 0b101, 0b111],
+            '1' => [0b010, 0b110, 0b010, 0b010, 0b111],
+            '2' => [0b111, 0b001, 0b111, 0b100, 0b111],
+            '3' => [0b111, 0b001, 0b111, 0b001, 0b111],
+            '4' => [0b101, 0b101, 0b111, 0b001, 0b001],
+            '5' => [0b111, 0b100, 0b111, 0b001, 0b111],
+            '6' => [0b111, 0b100, 0b111, 0b101, 0b111],
+            '7' => [0b111, 0b001, 0b010, 0b010, 0b010],
+            '8' => [0b111, 0b101, 0b111, 0b101, 0b111],
+            '9' => [0b111, 0b101, 0b111, 0b001, 0b111],
+            'A' => [0b010, 0b101, 0b111, 0b101, 0b101],
+            'B' => [0b110, 0b101, 0b110, 0b101, 0b110],
+            'C' => [0b011, 0b100, 0b100, 0b100, 0b011],
+            'D' => [0b110, 0b101, 0b101, 0b101, 0b110],
+            'E' => [0b111, 0b100, 0b110, 0b100, 0b111],
+            'F' => [0b111, 0b100, 0b110, 0b100, 0b100],
+            'G' => [0b011, 0b100, 0b101, 0b101, 0b011],
+            'H' => [0b101, 0b101, 0b111, 0b101, 0b101],
+            'I' => [0b111, 0b010, 0b010, 0b010, 0b111],
+            'J' => [0b001, 0b001, 0b001, 0b101, 0b010],
+            'K' => [0b101, 0b101, 0b110, 0b101, 0b101],
+            'L' => [0b100, 0b100, 0b100, 0b100, 0b111],
+            'M' => [0b101, 0b111, 0b111, 0b101, 0b101],
+            'N' => [0b110, 0b101, 0b101, 0b101, 0b101],
+            'O' => [0b010, 0b101, 0b101, 0b101, 0b010],
+            'P' => [0b111, 0b101, 0b111, 0b100, 0b100],
+            'Q' => [0b010, 0b101, 0b101, 0b110, 0b011],
+            'R' => [0b110, 0b101, 0b110, 0b101, 0b101],
+            'S' => [0b111, 0b100, 0b111, 0b001, 0b111],
+            'T' => [0b111, 0b010, 0b010, 0b010, 0b010],
+            'U' => [0b101, 0b101, 0b101, 0b101, 0b111],
+            'V' => [0b101, 0b101, 0b101, 0b101, 0b010],
+            'W' => [0b101, 0b101, 0b111, 0b111, 0b101],
+            'X' => [0b101, 0b101, 0b010, 0b101, 0b101],
+            'Y' => [0b101, 0b101, 0b010, 0b010, 0b010],
+            'Z' => [0b111, 0b001, 0b010, 0b100, 0b111],
+            '.' => [0b000, 0b000, 0b000, 0b000, 0b010],
+            ',' => [0b000, 0b000, 0b000, 0b010, 0b100],
+            ':' => [0b000, 0b010, 0b000, 0b010, 0b000],
+            ';' => [0b000, 0b010, 0b000, 0b010, 0b100],
+            '!' => [0b010, 0b010, 0b010, 0b000, 0b010],
+            '?' => [0b111, 0b001, 0b010, 0b000, 0b010],
+            '-' => [0b000, 0b000, 0b111, 0b000, 0b000],
+            '+' => [0b000, 0b010, 0b111, 0b010, 0b000],
+            '=' => [0b000, 0b111, 0b000, 0b111, 0b000],
+            '_' => [0b000, 0b000, 0b000, 0b000, 0b111],
+            '/' => [0b001, 0b001, 0b010, 0b100, 0b100],
+            '\\' => [0b100, 0b100, 0b010, 0b001, 0b001],
+            '%' => [0b101, 0b001, 0b010, 0b100, 0b101],
+            '*' => [0b000, 0b101, 0b010, 0b101, 0b000],
+            '#' => [0b101, 0b111, 0b101, 0b111, 0b101],
+            '(' => [0b001, 0b010, 0b010, 0b010, 0b001],
+            ')' => [0b100, 0b010, 0b010, 0b010, 0b100],
+            '[' => [0b011, 0b010, 0b010, 0b010, 0b011],
+            ']' => [0b110, 0b010, 0b010, 0b010, 0b110],
+            '<' => [0b001, 0b010, 0b100, 0b010, 0b001],
+            '>' => [0b100, 0b010, 0b001, 0b010, 0b100],
+            '\'' => [0b010, 0b010, 0b000, 0b000, 0b000],
+            '"' => [0b101, 0b101, 0b000, 0b000, 0b000],
+            '|' => [0b010, 0b010, 0b010, 0b010, 0b010],
+            _ => [0; GLYPH_HEIGHT],
+        }
+    }
+
+    // Pixel size of `text` drawn at `scale` (with one font pixel between characters)
+    pub fn text_size(text: &str, scale: usize) -> (usize, usize) {
+        let scale = scale.max(1);
+        let chars = text.chars().count();
+        let width = (chars * (GLYPH_WIDTH + 1)).saturating_sub(1) * scale;
+        (width, GLYPH_HEIGHT * scale)
+    }
+
+    // Draw text with its top left corner at pixel (x, y) of a 0RGB buffer, clipped to the
+    // buffer. The same bitmap font as Canvas::draw_text without a Canvas, e.g. for
+    // buffers that aren't a window.
+    #[allow(clippy::too_many_arguments)]
+    pub fn draw_text(
+        buffer: &mut [u32],
+        width: usize,
+        height: usize,
+        x: i32,
+        y: i32,
+        text: &str,
+        color: u32,
+        scale: usize,
+    ) {
+        Canvas::new(buffer, width, height).draw_text_px(x, y, text, scale, color);
+    }
+
+    // Draws onto a window pixel buffer using normalized (0.0 to 1.0) coordinates.
+    // Colors are given as 0RGB and packed in the canvas' pixel order.
+    // Anything outside the buffer is clipped; NaN or far out of range coordinates aren't drawn.
+    pub struct Canvas<'a> {
+        buffer: &'a mut [u32],
+        width: usize,
+        height: usize,
+        order: PixelOrder,
+    }
+
+    impl<'a> Canvas<'a> {
+        pub fn new(buffer: &'a mut [u32], width: usize, height: usize) -> Self {
+            Self {
+                buffer,
+                width,
+                height,
+                order: PixelOrder::Argb,
+            }
+        }
+
+        pub fn with_pixel_order(mut self, order: PixelOrder) -> Self {
+            self.order = order;
+            self
+        }
+
+        fn to_px(&self, x: f32, y: f32) -> Option<(i32, i32)> {
+            Some((
+                normalized_to_pixel(x, self.width)?,
+                normalized_to_pixel(y, self.height)?,
+            ))
+        }
+
+        fn put(&mut self, x: i32, y: i32, color: u32) {
+            if x >= 0 && y >= 0 && (x as usize) < self.width && (y as usize) < self.height {
+                let (r, g, b) = unpack_pixel(color, PixelOrder::Argb);
+                self.buffer[y as usize * self.width + x as usize] = pack_pixel(r, g, b, self.order);
+            }
+        }
+
+        pub fn draw_box(&mut self, bbox: &Box, color: u32) {
+            // Clamped to the edges so boxes partly outside the frame stay visible
+            let clamp = |v: i32, size: usize| v.clamp(0, size as i32 - 1);
+            let (Some((xmin, ymin)), Some((xmax, ymax))) = (
+                self.to_px(bbox.xmin, bbox.ymin),
+                self.to_px(bbox.xmax, bbox.ymax),
+            ) else {
+                return;
+            };
+            let [xmin, xmax] = [xmin, xmax].map(|x| clamp(x, self.width));
+            let [ymin, ymax] = [ymin, ymax].map(|y| clamp(y, self.height));
+
+            // Horizontal lines (top and bottom)
+            for x in xmin..=xmax {
+                self.put(x, ymin, color);
+                self.put(x, ymax, color);
+            }
+            // Vertical lines (left and right)
+            for y in ymin..=ymax {
+                self.put(xmin, y, color);
+                self.put(xmax, y, color);
+            }
+        }
+
+        pub fn draw_dot(&mut self, x: f32, y: f32, radius: i32, color: u32) {
+            let Some((cx, cy)) = self.to_px(x, y) else {
+                return;
+            };
+            for dy in -radius..=radius {
+                for dx in -radius..=radius {
+                    self.put(cx + dx, cy + dy, color);
+                }
+            }
+        }
+
+        pub fn draw_line(&mut self, from: (f32, f32), to: (f32, f32), color: u32) {
+            let (Some((x0, y0)), Some((x1, y1))) =
+                (self.to_px(from.0, from.1), self.to_px(to.0, to.1))
+            else {
+                return;
+            };
+
+            // Step along the longer axis, one pixel at a time
+            let steps = (x1 - x0).abs().max((y1 - y0).abs()).max(1);
+            for i in 0..=steps {
+                let t = i as f32 / steps as f32;
+                let x = x0 as f32 + (x1 - x0) as f32 * t;
+                let y = y0 as f32 + (y1 - y0) as f32 * t;
+                self.put(x.round() as i32, y.round() as i32, color);
+            }
+        }
+
+        // Draw text with its top left corner at (x, y), each font pixel `scale` pixels wide
+        pub fn draw_text(&mut self, x: f32, y: f32, text: &str, scale: usize, color: u32) {
+            let Some((left, top)) = self.to_px(x, y) else {
+                return;
+            };
+            self.draw_text_px(left, top, text, scale, color);
+        }
+
+        fn draw_text_px(&mut self, left: i32, top: i32, text: &str, scale: usize, color: u32) {
+            let scale = scale.max(1) as i32;
+            // One font pixel of spacing between characters
+            let advance = (GLYPH_WIDTH as i32 + 1) * scale;
+            for (i, c) in text.chars().enumerate() {
+                let origin = left + i as i32 * advance;
+                for (row, bits) in glyph(c).into_iter().enumerate() {
+                    for col in 0..GLYPH_WIDTH {
+                        if bits & (1 << (GLYPH_WIDTH - 1 - col)) == 0 {
+                            continue;
+                        }
+                        for dy in 0..scale {
+                            for dx in 0..scale {
+                                self.put(
+                                    origin + col as i32 * scale + dx,
+                                    top + row as i32 * scale + dy,
+                                    color,
+                                );
+                            }
+                        }
+                    }
+                }
+            }
+        }
+
+        // Label a box just above its top left corner (inside the box when there's no room)
+        pub fn draw_box_label(&mut self, bbox: &Box, text: &str, scale: usize, color: u32) {
+            let Some((left, top)) = self.to_px(bbox.xmin, bbox.ymin) else {
+                return;
+            };
+            let text_height = (GLYPH_HEIGHT * scale.max(1)) as i32;
+            let top = if top - text_height - 2 >= 0 {
+                top - text_height - 2
+            } else {
+                top.max(0) + 2
+            };
+            self.draw_text_px(left.max(0) + 1, top, text, scale, color);
+        }
+
+        pub fn draw_rotated_box(&mut self, rbox: &RotatedBox, color: u32) {
+            let corners = rbox.corners(self.width as f32, self.height as f32);
+            for i in 0..corners.len() {
+                self.draw_line(corners[i], corners[(i + 1) % corners.len()], color);
+            }
+        }
+
+        // Ring around (x, y) filled clockwise from the top up to `fraction` (0.0 to 1.0),
+        // e.g. for dwell progress
+        pub fn draw_progress(&mut self, x: f32, y: f32, radius: i32, fraction: f32, color: u32) {
+            let Some((cx, cy)) = self.to_px(x, y) else {
+                return;
+            };
+            let radius = radius.max(1);
+            // Enough steps to leave no gaps on the circumference
+            let steps = (std::f32::consts::TAU * radius as f32).ceil() as i32;
+            let filled = (steps as f32 * fraction.clamp(0.0, 1.0)).round() as i32;
+            for i in 0..filled {
+                let angle = i as f32 / steps as f32 * std::f32::consts::TAU;
+                let px = cx + (angle.sin() * radius as f32).round() as i32;
+                let py = cy - (angle.cos() * radius as f32).round() as i32;
+                // Two pixels thick
+                self.put(px, py, color);
+                self.put(px + 1, py, color);
+            }
+        }
+
+        // Draw the landmarks and the lines between them. Connections to landmarks that
+        // don't exist (another topology) or aren't visible are skipped.
+        pub fn draw_skeleton(
+            &mut self,
+            landmarks: &[Landmark],
+            connections: &Connections,
+            line_color: u32,
+            point_color: u32,
+        ) {
+            let visible = |i: usize| landmarks.get(i).filter(|l| l.visibility > 0.0);
+
+            for &(a, b) in connections {
+                if let (Some(a), Some(b)) = (visible(a), visible(b)) {
+                    self.draw_line((a.x, a.y), (b.x, b.y), line_color);
+                }
+            }
+            for landmark in landmarks.iter().filter(|l| l.visibility > 0.0) {
+                self.draw_dot(landmark.x, landmark.y, 1, point_color);
+            }
+        }
+
+        // Draw a tracked hand's box and skeleton in the color `color_of` gives its id
+        pub fn draw_hand(
+            &mut self,
+            id: u32,
+            bbox: &Box,
+            details: &HandDetails,
+            connections: &Connections,
+            color_of: impl Fn(u32) -> u32,
+        ) {
+            let color = color_of(id);
+            self.draw_box(bbox, color);
+            if let Some(landmarks) = details.full_landmarks() {
+                self.draw_skeleton(landmarks, connections, color, color);
+            } else if let Some(landmarks) = &details.landmarks {
+                // A subset of landmarks has no skeleton, just draw the points
+                self.draw_skeleton(landmarks, &[], color, color);
+            }
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        #[test]
+        fn packed_pixels_unpack_to_the_same_color() {
+            for order in [PixelOrder::Argb, PixelOrder::Abgr] {
+                for (r, g, b) in [
+                    (0, 0, 0),
+                    (255, 255, 255),
+                    (0x12, 0x34, 0x56),
+                    (255, 0, 128),
+                ] {
+                    assert_eq!(unpack_pixel(pack_pixel(r, g, b, order), order), (r, g, b));
+                }
+            }
+            assert_eq!(pack_pixel(0x12, 0x34, 0x56, PixelOrder::Argb), 0x0012_3456);
+            assert_eq!(pack_pixel(0x12, 0x34, 0x56, PixelOrder::Abgr), 0x0056_3412);
+        }
+
+        const WHITE: u32 = 0x00FF_FFFF;
+
+        fn lit(buffer: &[u32]) -> usize {
+            buffer.iter().filter(|&&pixel| pixel == WHITE).count()
+        }
+
+        #[test]
+        fn draw_text_lights_the_glyph_pixels() {
+            let (width, height) = (16, 12);
+            let mut buffer = vec![0; width * height];
+            draw_text(&mut buffer, width, height, 1, 1, "Hi", WHITE, 2);
+
+            // H has 11 font pixels and I 9, each drawn as a 2x2 square
+            assert_eq!(lit(&buffer), (11 + 9) * 4);
+            let (text_width, text_height) = text_size("Hi", 2);
+            assert_eq!((text_width, text_height), (14, 10));
+            for (i, &pixel) in buffer.iter().enumerate() {
+                let (x, y) = (i % width, i / width);
+                let inside = (1..1 + text_width).contains(&x) && (1..1 + text_height).contains(&y);
+                assert!(
+                    pixel == 0 || inside,
+                    "pixel ({}, {}) outside the text",
+                    x,
+                    y
+                );
+            }
+            // The top of H is lit at its sides only, I starts after one font pixel of spacing
+            assert_eq!(buffer[width + 1], WHITE);
+            assert_eq!(buffer[width + 3], 0);
+            assert_eq!(buffer[width + 5], WHITE);
+            assert_eq!(buffer[width + 7], 0);
+            assert_eq!(buffer[width + 9], WHITE);
+        }
+
+        #[test]
+        fn draw_text_is_clipped_to_the_buffer() {
+            let (width, height) = (4, 10);
+            let mut buffer = vec![0; width * height];
+            // Only the last two of H's three columns are inside
+            draw_text(&mut buffer, width, height, -2, 0, "H", WHITE, 2);
+            assert_eq!(lit(&buffer), 6 * 4);
+
+            // Unknown characters take up space but draw nothing
+            let mut buffer = vec![0; width * height];
+            draw_text(&mut buffer, width, height, 0, 0, "~", WHITE, 1);
+            assert_eq!(lit(&buffer), 0);
+        }
+    }
+}